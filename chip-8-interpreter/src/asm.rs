@@ -0,0 +1,426 @@
+use std::collections::HashMap;
+
+use crate::{error::Chip8Error, platform::Platform};
+
+/// Mot du code source, avec la ligne et la colonne auxquelles il commence.
+#[derive(Clone, Copy)]
+struct Token<'a> {
+    text: &'a str,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Token<'a> {
+    /// Crée le mot contenu dans `code[start..end]`, sans ses espaces.
+    fn new(code: &'a str, start: usize, end: usize, line: usize) -> Self {
+        let text = &code[start..end];
+        let trimmed = text.trim_start();
+
+        Self {
+            text: trimmed.trim_end(),
+            line,
+            column: start + text.len() - trimmed.len() + 1,
+        }
+    }
+
+    fn error(&self, message: String) -> Chip8Error {
+        Chip8Error::Assembly {
+            line: self.line,
+            column: self.column,
+            message,
+        }
+    }
+}
+
+enum StatementKind<'a> {
+    Instruction { mnemonic: Token<'a>, operands: Vec<Token<'a>> },
+    /// Directive `db`, un octet par opérande.
+    Bytes(Vec<Token<'a>>),
+    /// Directive `dw`, un mot de 16 bits par opérande.
+    Words(Vec<Token<'a>>),
+}
+
+/// Opérande d'une instruction, les registres et les mots réservés étant reconnus à leur nom.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Operand {
+    Register(u8),
+    /// Plage de registres `Vx-Vy` du XO-CHIP.
+    Range(u8, u8),
+    I,
+    /// Mémoire pointée par I, `[I]`.
+    IndirectI,
+    K,
+    Dt,
+    St,
+    Hf,
+    B,
+    R,
+    Audio,
+    Pitch,
+    /// Adresse sur 16 bits de `LD I, LONG NNNN`.
+    Long,
+    /// Nombre, label ou constante.
+    Value,
+}
+
+fn parse_register(text: &str) -> Option<u8> {
+    let digit = text.strip_prefix(['V', 'v'])?;
+
+    if digit.len() == 1 {
+        u8::from_str_radix(digit, 16).ok()
+    } else {
+        None
+    }
+}
+
+fn classify(text: &str) -> Operand {
+    if let Some(x) = parse_register(text) {
+        return Operand::Register(x);
+    }
+
+    if let Some((x, y)) = text.split_once('-') {
+        if let (Some(x), Some(y)) = (parse_register(x.trim()), parse_register(y.trim())) {
+            return Operand::Range(x, y);
+        }
+    }
+
+    if long_value(text).is_some() {
+        return Operand::Long;
+    }
+
+    match &text.to_ascii_uppercase()[..] {
+        "I" => Operand::I,
+        "[I]" => Operand::IndirectI,
+        "K" => Operand::K,
+        "DT" => Operand::Dt,
+        "ST" => Operand::St,
+        "HF" => Operand::Hf,
+        "B" => Operand::B,
+        "R" => Operand::R,
+        "AUDIO" => Operand::Audio,
+        "PITCH" => Operand::Pitch,
+        _ => Operand::Value,
+    }
+}
+
+/// Position dans `text` de l'adresse qui suit le mot `LONG`.
+fn long_value(text: &str) -> Option<usize> {
+    let keyword = text.get(..4)?;
+
+    if keyword.eq_ignore_ascii_case("LONG") && text[4..].starts_with(char::is_whitespace) {
+        Some(text.len() - text[4..].trim_start().len())
+    } else {
+        None
+    }
+}
+
+fn is_identifier(text: &str) -> bool {
+    let mut chars = text.chars();
+
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+}
+
+/// Un label ou une constante ne peut pas porter le nom d'un registre, ni un nom entièrement
+/// écrit en chiffres hexadécimaux comme `BE` qui se lirait comme un octet des instructions.
+fn is_symbol_name(text: &str) -> bool {
+    is_identifier(text) && parse_register(text).is_none() && parse_number(text, 16).is_none()
+}
+
+/// Lit un nombre : `$` ou `0x` pour l'hexadécimal, `%` pour le binaire, sinon dans la base
+/// `radix`.
+fn parse_number(text: &str, radix: u32) -> Option<u32> {
+    if let Some(hex) = text.strip_prefix('$').or(text.strip_prefix("0x")) {
+        u32::from_str_radix(hex, 16).ok()
+    } else if let Some(binary) = text.strip_prefix('%') {
+        u32::from_str_radix(binary, 2).ok()
+    } else {
+        u32::from_str_radix(text, radix).ok()
+    }
+}
+
+/// Valeur d'un nombre, d'un label ou d'une constante, qui doit être au plus `max`.
+/// Les nombres sans préfixe sont lus dans la base `radix` : 16 pour les octets des instructions,
+/// écrits comme dans le désassemblage (`LD V0, 10` vaut 0x10), et 10 partout ailleurs (`db 10`
+/// vaut 0x0A). Les noms de symboles ne pouvant pas être lus comme des nombres, il n'y a pas
+/// d'ambiguïté.
+fn resolve(token: &Token, symbols: &HashMap<String, u32>, radix: u32, max: u32) -> Result<u32, Chip8Error> {
+    let value = match parse_number(token.text, radix) {
+        Some(t) => t,
+        None if is_identifier(token.text) => match symbols.get(token.text) {
+            Some(t) => *t,
+            None => return Err(token.error(format!("undefined symbol \"{}\"", token.text))),
+        },
+        None => return Err(token.error(format!("invalid value \"{}\"", token.text))),
+    };
+
+    if value > max {
+        return Err(token.error(format!("value ${value:X} is greater than ${max:X}")));
+    }
+
+    Ok(value)
+}
+
+/// Découpe les opérandes séparés par des virgules qui commencent à `code[start..]`.
+fn split_operands<'a>(code: &'a str, start: usize, line: usize) -> Result<Vec<Token<'a>>, Chip8Error> {
+    if code[start..].trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut operands = Vec::new();
+    let mut operand_start = start;
+
+    for (index, c) in code[start..].char_indices() {
+        if c == ',' {
+            operands.push(Token::new(code, operand_start, start + index, line));
+            operand_start = start + index + 1;
+        }
+    }
+
+    operands.push(Token::new(code, operand_start, code.len(), line));
+
+    match operands.iter().find(|operand| operand.text.is_empty()) {
+        Some(operand) => Err(operand.error("missing operand".to_string())),
+        None => Ok(operands),
+    }
+}
+
+/// Mot qui commence à `code[start..]`, et position qui le suit.
+fn next_word(code: &str, start: usize, line: usize) -> Option<(Token<'_>, usize)> {
+    let begin = start + code[start..].find(|c: char| !c.is_whitespace())?;
+    let end = code[begin..].find(char::is_whitespace).map_or(code.len(), |index| begin + index);
+
+    Some((Token::new(code, begin, end, line), end))
+}
+
+/// Taille en octets d'une instruction, LDHI et `LD I, LONG` faisant 4 octets.
+fn instruction_size(mnemonic: &Token, operands: &[Token]) -> u32 {
+    let long = mnemonic.text.eq_ignore_ascii_case("LD") && operands.get(1).is_some_and(|operand| classify(operand.text) == Operand::Long);
+
+    if mnemonic.text.eq_ignore_ascii_case("LDHI") || long {
+        4
+    } else {
+        2
+    }
+}
+
+/// Encode une instruction écrite avec les mnémoniques du désassembleur de l'interpréteur.
+/// Les octets sans préfixe sont en hexadécimal comme dans le désassemblage, les autres nombres
+/// sont en décimal.
+fn encode(mnemonic: &Token, operands: &[Token], symbols: &HashMap<String, u32>, platform: &Platform) -> Result<Vec<u8>, Chip8Error> {
+    let superchip = platform.supports_superchip();
+    let xo_chip = platform.supports_xo_chip();
+    let chip8x = platform.supports_chip8x();
+    let megachip = platform.supports_megachip();
+
+    let byte = |index: usize| resolve(&operands[index], symbols, 16, 0xFF).map(|value| value as u16);
+    let nibble = |index: usize| resolve(&operands[index], symbols, 10, 0xF).map(|value| value as u16);
+    let address = |index: usize| resolve(&operands[index], symbols, 10, 0xFFF).map(|value| value as u16);
+
+    let kinds: Vec<Operand> = operands.iter().map(|operand| classify(operand.text)).collect();
+    let name = mnemonic.text.to_ascii_uppercase();
+
+    // Mot de 16 bits qui suit les instructions sur 4 octets.
+    let mut extra = None;
+
+    use Operand::*;
+    let opcode = match (&name[..], &kinds[..]) {
+        ("CLS", []) => 0x00E0,
+        ("RET", []) => 0x00EE,
+        ("SCD", [Value]) if superchip => 0x00C0 | nibble(0)?,
+        ("SCU", [Value]) if megachip => 0x00B0 | nibble(0)?,
+        ("SCU", [Value]) if xo_chip => 0x00D0 | nibble(0)?,
+        ("SCR", []) if superchip => 0x00FB,
+        ("SCL", []) if superchip => 0x00FC,
+        ("EXIT", []) if superchip => 0x00FD,
+        ("LOW", []) if superchip => 0x00FE,
+        ("HIGH", []) if superchip => 0x00FF,
+        ("MEGAOFF", []) if megachip => 0x0010,
+        ("MEGAON", []) if megachip => 0x0011,
+        ("LDHI", [I, Value]) if megachip => {
+            let value = resolve(&operands[1], symbols, 10, 0xFFFFFF)?;
+            extra = Some(value as u16);
+
+            0x0100 | (value >> 16) as u16
+        }
+        ("LDPAL", [Value]) if megachip => 0x0200 | byte(0)?,
+        ("SPRW", [Value]) if megachip => 0x0300 | byte(0)?,
+        ("SPRH", [Value]) if megachip => 0x0400 | byte(0)?,
+        ("ALPHA", [Value]) if megachip => 0x0500 | byte(0)?,
+        ("DIGISND", [Value]) if megachip => 0x0600 | nibble(0)?,
+        ("STOPSND", []) if megachip => 0x0700,
+        ("BMODE", [Value]) if megachip => 0x0800 | nibble(0)?,
+        ("CCOL", [Value]) if megachip => 0x0900 | byte(0)?,
+        ("BGC", []) if chip8x => 0x02A0,
+        ("SYS", [Value]) => address(0)?,
+        ("JP", [Value]) => 0x1000 | address(0)?,
+        ("JP", [Register(0), Value]) if !chip8x => 0xB000 | address(1)?,
+        ("CALL", [Value]) => 0x2000 | address(0)?,
+        ("SE", [Register(x), Value]) => 0x3000 | (*x as u16) << 8 | byte(1)?,
+        ("SNE", [Register(x), Value]) => 0x4000 | (*x as u16) << 8 | byte(1)?,
+        ("SE", [Register(x), Register(y)]) => 0x5000 | (*x as u16) << 8 | (*y as u16) << 4,
+        ("ADDN", [Register(x), Register(y)]) if chip8x => 0x5001 | (*x as u16) << 8 | (*y as u16) << 4,
+        ("LD", [IndirectI, Range(x, y)]) if xo_chip => 0x5002 | (*x as u16) << 8 | (*y as u16) << 4,
+        ("LD", [Range(x, y), IndirectI]) if xo_chip => 0x5003 | (*x as u16) << 8 | (*y as u16) << 4,
+        ("LD", [Register(x), Value]) => 0x6000 | (*x as u16) << 8 | byte(1)?,
+        ("ADD", [Register(x), Value]) => 0x7000 | (*x as u16) << 8 | byte(1)?,
+        ("LD", [Register(x), Register(y)]) => 0x8000 | (*x as u16) << 8 | (*y as u16) << 4,
+        ("OR", [Register(x), Register(y)]) => 0x8001 | (*x as u16) << 8 | (*y as u16) << 4,
+        ("AND", [Register(x), Register(y)]) => 0x8002 | (*x as u16) << 8 | (*y as u16) << 4,
+        ("XOR", [Register(x), Register(y)]) => 0x8003 | (*x as u16) << 8 | (*y as u16) << 4,
+        ("ADD", [Register(x), Register(y)]) => 0x8004 | (*x as u16) << 8 | (*y as u16) << 4,
+        ("SUB", [Register(x), Register(y)]) => 0x8005 | (*x as u16) << 8 | (*y as u16) << 4,
+        ("SHR", [Register(x)]) => 0x8006 | (*x as u16) << 8,
+        ("SHR", [Register(x), Register(y)]) => 0x8006 | (*x as u16) << 8 | (*y as u16) << 4,
+        ("SUBN", [Register(x), Register(y)]) => 0x8007 | (*x as u16) << 8 | (*y as u16) << 4,
+        ("SHL", [Register(x)]) => 0x800E | (*x as u16) << 8,
+        ("SHL", [Register(x), Register(y)]) => 0x800E | (*x as u16) << 8 | (*y as u16) << 4,
+        ("SNE", [Register(x), Register(y)]) => 0x9000 | (*x as u16) << 8 | (*y as u16) << 4,
+        ("LD", [I, Value]) => 0xA000 | address(1)?,
+        ("COL", [Register(x), Register(y)]) if chip8x => 0xB000 | (*x as u16) << 8 | (*y as u16) << 4,
+        ("COL", [Register(x), Register(y), Value]) if chip8x => 0xB000 | (*x as u16) << 8 | (*y as u16) << 4 | nibble(2)?,
+        ("RND", [Register(x), Value]) => 0xC000 | (*x as u16) << 8 | byte(1)?,
+        ("DRW", [Register(x), Register(y), Value]) => 0xD000 | (*x as u16) << 8 | (*y as u16) << 4 | nibble(2)?,
+        ("SKP", [Register(x)]) => 0xE09E | (*x as u16) << 8,
+        ("SKNP", [Register(x)]) => 0xE0A1 | (*x as u16) << 8,
+        ("SKP2", [Register(x)]) if chip8x => 0xE0F2 | (*x as u16) << 8,
+        ("SKNP2", [Register(x)]) if chip8x => 0xE0F5 | (*x as u16) << 8,
+        ("LD", [I, Long]) if xo_chip => {
+            let operand = &operands[1];
+            let offset = long_value(operand.text).unwrap_or(0);
+            let value = Token {
+                text: &operand.text[offset..],
+                line: operand.line,
+                column: operand.column + offset,
+            };
+            extra = Some(resolve(&value, symbols, 10, 0xFFFF)? as u16);
+
+            0xF000
+        }
+        ("PLANE", [Value]) if xo_chip => 0xF001 | nibble(0)? << 8,
+        ("LD", [Audio, IndirectI]) if xo_chip => 0xF002,
+        ("LD", [Register(x), Dt]) => 0xF007 | (*x as u16) << 8,
+        ("LD", [Register(x), K]) => 0xF00A | (*x as u16) << 8,
+        ("LD", [Dt, Register(x)]) => 0xF015 | (*x as u16) << 8,
+        ("LD", [St, Register(x)]) => 0xF018 | (*x as u16) << 8,
+        ("ADD", [I, Register(x)]) => 0xF01E | (*x as u16) << 8,
+        ("LD", [I, Register(x)]) => 0xF029 | (*x as u16) << 8,
+        ("LD", [Hf, Register(x)]) if superchip => 0xF030 | (*x as u16) << 8,
+        ("LD", [B, Register(x)]) => 0xF033 | (*x as u16) << 8,
+        ("LD", [Pitch, Register(x)]) if xo_chip => 0xF03A | (*x as u16) << 8,
+        ("LD", [IndirectI, Register(x)]) => 0xF055 | (*x as u16) << 8,
+        ("LD", [Register(x), IndirectI]) => 0xF065 | (*x as u16) << 8,
+        ("LD", [R, Register(x)]) if (*x as usize) < platform.rpl_flags_count() => 0xF075 | (*x as u16) << 8,
+        ("LD", [Register(x), R]) if (*x as usize) < platform.rpl_flags_count() => 0xF085 | (*x as u16) << 8,
+        _ => return Err(mnemonic.error(format!("invalid instruction \"{}\" for this platform", mnemonic.text))),
+    };
+
+    let mut bytes = opcode.to_be_bytes().to_vec();
+    if let Some(extra) = extra {
+        bytes.extend(extra.to_be_bytes());
+    }
+
+    Ok(bytes)
+}
+
+/// Assemble un programme écrit avec les mnémoniques du désassembleur, chargé à `load_address`.
+///
+/// Chaque ligne peut commencer par un label suivi de `:` et se termine par un commentaire
+/// optionnel commençant par `;`. Une constante est définie par `NOM equ valeur`, les directives
+/// `db` et `dw` ajoutent des octets et des mots de 16 bits. Les instructions qui ne sont pas
+/// disponibles sur `platform` sont refusées.
+pub fn assemble(source: &str, platform: Platform, load_address: u16) -> Result<Vec<u8>, Chip8Error> {
+    let mut symbols = HashMap::new();
+    let mut statements = Vec::new();
+    let mut address = load_address as u32;
+
+    // Premier passage : adresse des labels, valeur des constantes et taille des instructions.
+    for (index, text) in source.lines().enumerate() {
+        let line = index + 1;
+        let code = &text[..text.find(';').unwrap_or(text.len())];
+
+        let Some((mut word, mut end)) = next_word(code, 0, line) else {
+            continue;
+        };
+
+        if let Some(label) = word.text.strip_suffix(':') {
+            if !is_symbol_name(label) {
+                return Err(word.error(format!("invalid label name \"{label}\"")));
+            }
+
+            if symbols.insert(label.to_string(), address).is_some() {
+                return Err(word.error(format!("symbol \"{label}\" is already defined")));
+            }
+
+            match next_word(code, end, line) {
+                Some(next) => (word, end) = next,
+                None => continue,
+            }
+        }
+
+        // Définition d'une constante.
+        if let Some((directive, value_start)) = next_word(code, end, line).filter(|(next, _)| next.text.eq_ignore_ascii_case("equ")) {
+            if !is_symbol_name(word.text) {
+                return Err(word.error(format!("invalid constant name \"{}\"", word.text)));
+            }
+
+            let value = Token::new(code, value_start, code.len(), line);
+            if value.text.is_empty() {
+                return Err(directive.error("missing value after equ".to_string()));
+            }
+
+            let value = resolve(&value, &symbols, 10, u32::MAX)?;
+
+            if symbols.insert(word.text.to_string(), value).is_some() {
+                return Err(word.error(format!("symbol \"{}\" is already defined", word.text)));
+            }
+
+            continue;
+        }
+
+        let operands = split_operands(code, end, line)?;
+        let directive = word.text.to_ascii_lowercase();
+
+        if (directive == "db" || directive == "dw") && operands.is_empty() {
+            return Err(word.error(format!("missing value after {}", word.text)));
+        }
+
+        let (size, kind) = match &directive[..] {
+            "db" => (operands.len() as u32, StatementKind::Bytes(operands)),
+            "dw" => (operands.len() as u32 * 2, StatementKind::Words(operands)),
+            _ => (
+                instruction_size(&word, &operands),
+                StatementKind::Instruction {
+                    mnemonic: word,
+                    operands,
+                },
+            ),
+        };
+
+        statements.push(kind);
+        address += size;
+    }
+
+    // Second passage : encodage, tous les labels étant connus.
+    let mut program = Vec::new();
+
+    for statement in statements {
+        match statement {
+            StatementKind::Instruction { mnemonic, operands } => {
+                program.extend(encode(&mnemonic, &operands, &symbols, &platform)?);
+            }
+            StatementKind::Bytes(values) => {
+                for value in values {
+                    program.push(resolve(&value, &symbols, 10, 0xFF)? as u8);
+                }
+            }
+            StatementKind::Words(values) => {
+                for value in values {
+                    program.extend((resolve(&value, &symbols, 10, 0xFFFF)? as u16).to_be_bytes());
+                }
+            }
+        }
+    }
+
+    Ok(program)
+}
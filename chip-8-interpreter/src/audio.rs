@@ -0,0 +1,113 @@
+/// Taille en octets du motif audio du XO-CHIP, soit 128 échantillons de 1 bit.
+pub const AUDIO_PATTERN_SIZE: usize = 16;
+
+/// Hauteur par défaut, pour laquelle le motif est lu à 4000 bits par seconde.
+pub const DEFAULT_PITCH: u8 = 64;
+
+/// Motif utilisé tant que le programme n'en a pas chargé, un signal carré de 500Hz.
+pub const DEFAULT_AUDIO_PATTERN: [u8; AUDIO_PATTERN_SIZE] = [0xF0; AUDIO_PATTERN_SIZE];
+
+/// Amplitude des échantillons générés.
+const AMPLITUDE: i16 = i16::MAX / 4;
+
+/// Nombre de bits du motif lus par seconde pour une hauteur donnée.
+pub fn playback_rate(pitch: u8) -> f64 {
+    4000.0 * 2.0_f64.powf((pitch as f64 - 64.0) / 48.0)
+}
+
+/// Génère les échantillons PCM correspondant au motif audio du XO-CHIP.
+pub struct PatternSynth {
+    /// Position de lecture dans le motif, en bits.
+    position: f64,
+}
+
+impl PatternSynth {
+    pub fn new() -> Self {
+        Self { position: 0.0 }
+    }
+
+    /// Remplit `out` avec des échantillons PCM 16 bits mono à `sample_rate` Hz.
+    /// Si `playing` est faux, les échantillons sont silencieux et la lecture reprend au début du
+    /// motif.
+    pub fn render(&mut self, pattern: &[u8; AUDIO_PATTERN_SIZE], pitch: u8, playing: bool, sample_rate: u32, out: &mut [i16]) {
+        if !playing || sample_rate == 0 {
+            self.position = 0.0;
+            out.fill(0);
+
+            return;
+        }
+
+        let bits = (AUDIO_PATTERN_SIZE * 8) as f64;
+        let step = playback_rate(pitch) / sample_rate as f64;
+
+        for sample in out.iter_mut() {
+            let bit = self.position as usize;
+            let value = (pattern[bit / 8] >> (7 - bit % 8)) & 0x1;
+
+            *sample = if value != 0 { AMPLITUDE } else { -AMPLITUDE };
+
+            self.position = (self.position + step) % bits;
+        }
+    }
+}
+
+impl Default for PatternSynth {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Son numérisé joué par l'instruction 060N du MEGA-CHIP, composé d'échantillons non signés sur
+/// 8 bits.
+pub struct DigitizedSound {
+    /// Nombre d'échantillons lus par seconde.
+    pub rate: u16,
+    pub data: Vec<u8>,
+    /// Le son recommence au début une fois terminé.
+    pub looping: bool,
+    /// Position de lecture, en échantillons.
+    position: f64,
+}
+
+impl DigitizedSound {
+    pub fn new(rate: u16, data: Vec<u8>, looping: bool) -> Self {
+        Self {
+            rate,
+            data,
+            looping,
+            position: 0.0,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.data.is_empty() || (!self.looping && self.position as usize >= self.data.len())
+    }
+
+    /// Remplit `out` avec des échantillons PCM 16 bits mono à `sample_rate` Hz, les échantillons
+    /// situés après la fin du son étant silencieux.
+    pub fn render(&mut self, sample_rate: u32, out: &mut [i16]) {
+        if sample_rate == 0 {
+            out.fill(0);
+
+            return;
+        }
+
+        let step = self.rate as f64 / sample_rate as f64;
+
+        for sample in out.iter_mut() {
+            if self.is_finished() {
+                *sample = 0;
+                continue;
+            }
+
+            let value = self.data[self.position as usize] as i16 - 0x80;
+            *sample = value * 0x100 / 4;
+
+            self.position += step;
+
+            if self.looping && self.position as usize >= self.data.len() {
+                self.position %= self.data.len() as f64;
+            }
+        }
+    }
+}
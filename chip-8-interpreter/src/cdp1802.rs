@@ -0,0 +1,380 @@
+/// Bus auquel est relié le processeur : mémoire, ports d'entrée/sortie et lignes EF1 à EF4.
+pub trait Bus {
+    fn read(&mut self, address: u16) -> u8;
+    fn write(&mut self, address: u16, value: u8);
+    /// Valeur lue sur le bus de données par INP N, N allant de 1 à 7.
+    fn input(&mut self, port: u8) -> u8;
+    /// Valeur envoyée sur le bus de données par OUT N, N allant de 1 à 7.
+    fn output(&mut self, port: u8, value: u8);
+    /// Indique si la ligne EF`line`, allant de 1 à 4, est active.
+    fn flag(&self, line: u8) -> bool;
+}
+
+/// Processeur RCA CDP1802 du COSMAC VIP.
+/// Chaque instruction dure 2 cycles machine de 8 impulsions d'horloge, sauf les branchements et
+/// sauts longs qui en durent 3.
+pub struct Cdp1802 {
+    /// Registres 16 bits R0 à RF, R0 servant de pointeur au DMA.
+    pub r: [u16; 0x10],
+    /// Numéro du registre servant de compteur ordinal.
+    pub p: u8,
+    /// Numéro du registre servant de pointeur de données.
+    pub x: u8,
+    /// Accumulateur.
+    pub d: u8,
+    /// Retenue des opérations arithmétiques et des décalages.
+    pub df: bool,
+    /// Valeurs de X et P sauvegardées lors d'une interruption.
+    pub t: u8,
+    /// Les interruptions sont autorisées.
+    pub ie: bool,
+    /// Sortie Q, reliée au buzzer sur le COSMAC VIP.
+    pub q: bool,
+    /// Le processeur attend un DMA ou une interruption après l'instruction IDL.
+    pub idle: bool,
+}
+
+impl Cdp1802 {
+    pub fn new() -> Self {
+        let mut cpu = Self {
+            r: [0x0; 0x10],
+            p: 0x0,
+            x: 0x0,
+            d: 0x0,
+            df: false,
+            t: 0x0,
+            ie: true,
+            q: false,
+            idle: false,
+        };
+
+        cpu.reset();
+
+        cpu
+    }
+
+    /// Remet le processeur dans l'état où le place sa broche CLEAR : le programme démarre à
+    /// l'adresse 0 avec P, X et R0 à 0.
+    pub fn reset(&mut self) {
+        self.r[0] = 0x0;
+        self.p = 0x0;
+        self.x = 0x0;
+        self.ie = true;
+        self.q = false;
+        self.idle = false;
+    }
+
+    /// Adresse de la prochaine instruction.
+    pub fn pc(&self) -> u16 {
+        self.r[self.p as usize]
+    }
+
+    /// Lit l'octet qui suit l'instruction et avance le compteur ordinal.
+    fn immediate(&mut self, bus: &mut impl Bus) -> u8 {
+        let address = self.r[self.p as usize];
+        self.r[self.p as usize] = address.wrapping_add(1);
+
+        bus.read(address)
+    }
+
+    /// Octet pointé par RX.
+    fn data(&mut self, bus: &mut impl Bus) -> u8 {
+        bus.read(self.r[self.x as usize])
+    }
+
+    /// Saute à l'adresse donnée par l'octet suivant si `condition` est vraie, sinon l'ignore.
+    fn short_branch(&mut self, bus: &mut impl Bus, condition: bool) {
+        let p = self.p as usize;
+        let target = bus.read(self.r[p]);
+
+        if condition {
+            self.r[p] = (self.r[p] & 0xFF00) | target as u16;
+        } else {
+            self.r[p] = self.r[p].wrapping_add(1);
+        }
+    }
+
+    /// Saute à l'adresse donnée par les deux octets suivants si `condition` est vraie, sinon les
+    /// ignore.
+    fn long_branch(&mut self, bus: &mut impl Bus, condition: bool) {
+        let p = self.p as usize;
+
+        if condition {
+            let high = bus.read(self.r[p]);
+            let low = bus.read(self.r[p].wrapping_add(1));
+
+            self.r[p] = u16::from_be_bytes([high, low]);
+        } else {
+            self.r[p] = self.r[p].wrapping_add(2);
+        }
+    }
+
+    /// Ignore les deux octets suivants si `condition` est vraie.
+    fn long_skip(&mut self, condition: bool) {
+        if condition {
+            let p = self.p as usize;
+            self.r[p] = self.r[p].wrapping_add(2);
+        }
+    }
+
+    /// D = `a` + `b` + `carry`, DF recevant la retenue.
+    fn add(&mut self, a: u8, b: u8, carry: bool) {
+        let result = a as u16 + b as u16 + carry as u16;
+
+        self.d = result as u8;
+        self.df = result > 0xFF;
+    }
+
+    /// D = `a` - `b` - `borrow`, DF valant 1 s'il n'y a pas d'emprunt.
+    fn subtract(&mut self, a: u8, b: u8, borrow: bool) {
+        let result = a as i16 - b as i16 - borrow as i16;
+
+        self.d = result as u8;
+        self.df = result >= 0;
+    }
+
+    /// Exécute l'instruction suivante et retourne le nombre de cycles machine écoulés.
+    /// En attente après IDL, le processeur ne fait que laisser passer un cycle.
+    pub fn step(&mut self, bus: &mut impl Bus) -> u32 {
+        if self.idle {
+            return 1;
+        }
+
+        let opcode = self.immediate(bus);
+        let n = (opcode & 0xF) as usize;
+        let x = self.x as usize;
+
+        match opcode >> 4 {
+            0x0 if n == 0 => self.idle = true,
+            // LDN
+            0x0 => self.d = bus.read(self.r[n]),
+            // INC
+            0x1 => self.r[n] = self.r[n].wrapping_add(1),
+            // DEC
+            0x2 => self.r[n] = self.r[n].wrapping_sub(1),
+            0x3 => {
+                let condition = match n & 0x7 {
+                    0x0 => true,
+                    0x1 => self.q,
+                    0x2 => self.d == 0,
+                    0x3 => self.df,
+                    line => bus.flag(line as u8 - 3),
+                };
+
+                if n == 0x8 {
+                    // SKP : ignore l'octet suivant.
+                    self.r[self.p as usize] = self.r[self.p as usize].wrapping_add(1);
+                } else {
+                    // BR, BQ, BZ, BDF, B1 à B4 et leurs contraires BNQ, BNZ, BNF, BN1 à BN4.
+                    self.short_branch(bus, condition == (n < 0x8));
+                }
+            }
+            // LDA
+            0x4 => {
+                self.d = bus.read(self.r[n]);
+                self.r[n] = self.r[n].wrapping_add(1);
+            }
+            // STR
+            0x5 => bus.write(self.r[n], self.d),
+            0x6 => match n {
+                // IRX
+                0x0 => self.r[x] = self.r[x].wrapping_add(1),
+                // OUT
+                0x1..=0x7 => {
+                    let value = self.data(bus);
+                    bus.output(n as u8, value);
+                    self.r[x] = self.r[x].wrapping_add(1);
+                }
+                // 68 n'existe pas sur le CDP1802.
+                0x8 => {}
+                // INP
+                _ => {
+                    let value = bus.input(n as u8 - 8);
+                    bus.write(self.r[x], value);
+                    self.d = value;
+                }
+            },
+            0x7 => match n {
+                // RET et DIS
+                0x0 | 0x1 => {
+                    let value = self.data(bus);
+                    self.r[x] = self.r[x].wrapping_add(1);
+                    self.x = value >> 4;
+                    self.p = value & 0xF;
+                    self.ie = n == 0x0;
+                }
+                // LDXA
+                0x2 => {
+                    self.d = self.data(bus);
+                    self.r[x] = self.r[x].wrapping_add(1);
+                }
+                // STXD
+                0x3 => {
+                    bus.write(self.r[x], self.d);
+                    self.r[x] = self.r[x].wrapping_sub(1);
+                }
+                // ADC
+                0x4 => {
+                    let value = self.data(bus);
+                    self.add(value, self.d, self.df);
+                }
+                // SDB
+                0x5 => {
+                    let value = self.data(bus);
+                    self.subtract(value, self.d, !self.df);
+                }
+                // SHRC
+                0x6 => {
+                    let carry = self.d & 0x1 != 0;
+                    self.d = (self.d >> 1) | ((self.df as u8) << 7);
+                    self.df = carry;
+                }
+                // SMB
+                0x7 => {
+                    let value = self.data(bus);
+                    self.subtract(self.d, value, !self.df);
+                }
+                // SAV
+                0x8 => bus.write(self.r[x], self.t),
+                // MARK
+                0x9 => {
+                    self.t = (self.x << 4) | self.p;
+                    bus.write(self.r[2], self.t);
+                    self.x = self.p;
+                    self.r[2] = self.r[2].wrapping_sub(1);
+                }
+                // REQ et SEQ
+                0xA | 0xB => self.q = n == 0xB,
+                // ADCI
+                0xC => {
+                    let value = self.immediate(bus);
+                    self.add(value, self.d, self.df);
+                }
+                // SDBI
+                0xD => {
+                    let value = self.immediate(bus);
+                    self.subtract(value, self.d, !self.df);
+                }
+                // SHLC
+                0xE => {
+                    let carry = self.d & 0x80 != 0;
+                    self.d = (self.d << 1) | self.df as u8;
+                    self.df = carry;
+                }
+                // SMBI
+                _ => {
+                    let value = self.immediate(bus);
+                    self.subtract(self.d, value, !self.df);
+                }
+            },
+            // GLO
+            0x8 => self.d = self.r[n] as u8,
+            // GHI
+            0x9 => self.d = (self.r[n] >> 8) as u8,
+            // PLO
+            0xA => self.r[n] = (self.r[n] & 0xFF00) | self.d as u16,
+            // PHI
+            0xB => self.r[n] = (self.r[n] & 0x00FF) | ((self.d as u16) << 8),
+            0xC => {
+                match n {
+                    // LBR, LBQ, LBZ, LBDF et leurs contraires LBNQ, LBNZ, LBNF.
+                    0x0 => self.long_branch(bus, true),
+                    0x1 => self.long_branch(bus, self.q),
+                    0x2 => self.long_branch(bus, self.d == 0),
+                    0x3 => self.long_branch(bus, self.df),
+                    0x9 => self.long_branch(bus, !self.q),
+                    0xA => self.long_branch(bus, self.d != 0),
+                    0xB => self.long_branch(bus, !self.df),
+                    // NOP
+                    0x4 => {}
+                    // LSNQ, LSNZ, LSNF
+                    0x5 => self.long_skip(!self.q),
+                    0x6 => self.long_skip(self.d != 0),
+                    0x7 => self.long_skip(!self.df),
+                    // LSKP
+                    0x8 => self.long_skip(true),
+                    // LSIE, LSQ, LSZ, LSDF
+                    0xC => self.long_skip(self.ie),
+                    0xD => self.long_skip(self.q),
+                    0xE => self.long_skip(self.d == 0),
+                    _ => self.long_skip(self.df),
+                }
+
+                return 3;
+            }
+            // SEP
+            0xD => self.p = n as u8,
+            // SEX
+            0xE => self.x = n as u8,
+            _ => {
+                // Les instructions F8 à FF utilisent l'octet suivant au lieu de M(RX).
+                let value = match n {
+                    0x0..=0x5 | 0x7 => self.data(bus),
+                    0x8..=0xD | 0xF => self.immediate(bus),
+                    _ => 0x0,
+                };
+
+                match n & 0x7 {
+                    // LDX et LDI
+                    0x0 => self.d = value,
+                    // OR et ORI
+                    0x1 => self.d |= value,
+                    // AND et ANI
+                    0x2 => self.d &= value,
+                    // XOR et XRI
+                    0x3 => self.d ^= value,
+                    // ADD et ADI
+                    0x4 => self.add(value, self.d, false),
+                    // SD et SDI
+                    0x5 => self.subtract(value, self.d, false),
+                    // SHR et SHL
+                    0x6 => {
+                        if n == 0x6 {
+                            self.df = self.d & 0x1 != 0;
+                            self.d >>= 1;
+                        } else {
+                            self.df = self.d & 0x80 != 0;
+                            self.d <<= 1;
+                        }
+                    }
+                    // SM et SMI
+                    _ => self.subtract(self.d, value, false),
+                }
+            }
+        }
+
+        2
+    }
+
+    /// Répond à une demande d'interruption si elles sont autorisées : X et P sont sauvegardés
+    /// dans T, puis la routine pointée par R1 est exécutée avec R2 comme pointeur de données.
+    /// Retourne le nombre de cycles machine écoulés.
+    pub fn interrupt(&mut self) -> u32 {
+        if !self.ie {
+            return 0;
+        }
+
+        self.t = (self.x << 4) | self.p;
+        self.p = 0x1;
+        self.x = 0x2;
+        self.ie = false;
+        self.idle = false;
+
+        1
+    }
+
+    /// Cycle de DMA en sortie : retourne l'octet pointé par R0 puis incrémente R0.
+    pub fn dma_out(&mut self, bus: &mut impl Bus) -> u8 {
+        let value = bus.read(self.r[0]);
+        self.r[0] = self.r[0].wrapping_add(1);
+        self.idle = false;
+
+        value
+    }
+}
+
+impl Default for Cdp1802 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,613 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{
+    chip::Chip8,
+    disasm::{instruction_size, skip_size},
+    error::Chip8Error,
+    memory::Memory,
+    platform::Platform,
+    screen::PLANES_COUNT,
+};
+
+/// Type de lien entre deux blocs de base.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EdgeKind {
+    /// Passage à l'instruction suivante.
+    Fallthrough,
+    /// Saut inconditionnel 1NNN.
+    Jump,
+    /// Instruction suivante ignorée par un saut conditionnel.
+    Skip,
+    /// Suite du programme après le retour d'un appel 2NNN.
+    Return,
+}
+
+/// Lien d'un bloc vers le bloc qui commence à `target`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Edge {
+    pub target: u32,
+    pub kind: EdgeKind,
+}
+
+/// Instruction d'un bloc de base.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlockInstruction {
+    pub address: u32,
+    pub opcode: u16,
+    pub mnemonic: String,
+}
+
+/// Suite d'instructions exécutées les unes après les autres, sans saut entrant ni sortant au
+/// milieu.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BasicBlock {
+    pub start: u32,
+    /// Adresse qui suit la dernière instruction du bloc.
+    pub end: u32,
+    pub instructions: Vec<BlockInstruction>,
+    pub successors: Vec<Edge>,
+    /// Fonction appelée par la dernière instruction du bloc.
+    pub call: Option<u32>,
+    /// Le bloc se termine par un saut BNNN dont la cible dépend de V0.
+    pub unresolved: bool,
+}
+
+/// Fonction : son point d'entrée, les blocs atteignables depuis celui-ci sans suivre les appels,
+/// et les fonctions qu'elle appelle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Function {
+    pub entry: u32,
+    pub blocks: Vec<u32>,
+    pub calls: Vec<u32>,
+}
+
+/// Image dessinée par DRW avec une adresse chargée dans I par ANNN ou F000 NNNN.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sprite {
+    pub address: u32,
+    pub size: u32,
+    /// Blocs qui dessinent l'image.
+    pub drawn_by: Vec<u32>,
+}
+
+/// Utilisation de chaque octet du programme.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ByteKind {
+    /// Octet jamais atteint par l'analyse, probablement des données.
+    Unknown,
+    /// Octet d'une instruction atteignable.
+    Code,
+    /// Octet d'une image dessinée par DRW.
+    Sprite,
+}
+
+/// Graphe de flot de contrôle d'un programme.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ControlFlowGraph {
+    pub entry: u32,
+    pub load_address: u32,
+    pub blocks: BTreeMap<u32, BasicBlock>,
+    pub functions: BTreeMap<u32, Function>,
+    pub sprites: BTreeMap<u32, Sprite>,
+    /// Adresse des sauts BNNN, dont la cible n'est pas connue avant l'exécution.
+    pub unresolved_jumps: Vec<u32>,
+    /// Utilisation de chaque octet du programme, à partir de `load_address`.
+    pub bytes: Vec<ByteKind>,
+}
+
+/// Instruction trouvée en suivant le flot d'exécution, et les adresses qui peuvent la suivre.
+struct Decoded {
+    opcode: u16,
+    size: u32,
+    mnemonic: String,
+    successors: Vec<Edge>,
+    call: Option<u32>,
+    /// L'instruction termine son bloc même si la suivante n'est la cible d'aucun saut.
+    terminator: bool,
+    unresolved: bool,
+}
+
+/// Adresses qui peuvent suivre l'instruction `opcode` située à `address`.
+fn decode_successors(decoded: &mut Decoded, address: u32, ram: &Memory, platform: &Platform) {
+    let opcode = decoded.opcode;
+    let next = address + decoded.size;
+    let nnn = (opcode & 0x0FFF) as u32;
+
+    let fallthrough = Edge {
+        target: next,
+        kind: EdgeKind::Fallthrough,
+    };
+
+    let skip = || {
+        vec![
+            fallthrough,
+            Edge {
+                target: next + skip_size(ram, next, platform),
+                kind: EdgeKind::Skip,
+            },
+        ]
+    };
+
+    decoded.terminator = true;
+
+    decoded.successors = match opcode >> 12 {
+        0x0 if opcode == 0x00EE => vec![],
+        0x0 if opcode == 0x00FD && platform.supports_superchip() => vec![],
+        0x1 => vec![Edge {
+            target: nnn,
+            kind: EdgeKind::Jump,
+        }],
+        0x2 => {
+            decoded.call = Some(nnn);
+            vec![Edge {
+                target: next,
+                kind: EdgeKind::Return,
+            }]
+        }
+        0x3 | 0x4 | 0x9 => skip(),
+        // 5XY1 (CHIP-8X), 5XY2 et 5XY3 (XO-CHIP) ne sont pas des sauts conditionnels.
+        0x5 if opcode & 0x000F == 0x0 => skip(),
+        // Sur CHIP-8X, BXYN change la couleur de l'écran.
+        0xB if !platform.supports_chip8x() => {
+            decoded.unresolved = true;
+            vec![]
+        }
+        0xE if matches!(opcode & 0x00FF, 0x9E | 0xA1 | 0xF2 | 0xF5) => skip(),
+        _ => {
+            decoded.terminator = false;
+            vec![fallthrough]
+        }
+    };
+}
+
+/// Analyse le programme `rom` chargé à `load_address` en suivant tous les chemins d'exécution
+/// depuis le point d'entrée de la plateforme : sauts, appels, sauts conditionnels et retours.
+/// Les sauts calculés par BNNN ne sont pas suivis et sont signalés dans `unresolved_jumps`.
+pub fn analyze(rom: &[u8], platform: Platform, load_address: u16) -> Result<ControlFlowGraph, Chip8Error> {
+    let platform = platform.detect(rom, load_address);
+    let start = load_address as u32;
+    let end = start + rom.len() as u32;
+
+    let mut ram = Memory::new(end as usize);
+    ram.load(start, rom)?;
+
+    let entry = platform.start_address(load_address) as u32;
+
+    let mut code: BTreeMap<u32, Decoded> = BTreeMap::new();
+    let mut bytes = vec![ByteKind::Unknown; rom.len()];
+
+    // Premières instructions des blocs de base.
+    let mut leaders = BTreeSet::from([entry]);
+    let mut pending = vec![entry];
+
+    while let Some(address) = pending.pop() {
+        // Le registre PC est sur 16 bits, le code ne peut donc pas dépasser 64 Kio.
+        if address < start || address > u16::MAX as u32 || code.contains_key(&address) {
+            continue;
+        }
+
+        let opcode = match ram.read16(address) {
+            Ok(t) => t,
+            Err(_) => continue,
+        };
+
+        let size = instruction_size(opcode, &platform);
+        let range = (address - start) as usize..(address - start + size) as usize;
+
+        // Une instruction qui déborde du programme ou chevauche une autre n'est pas retenue.
+        if range.end > rom.len() || bytes[range.clone()].contains(&ByteKind::Code) {
+            continue;
+        }
+
+        let mnemonic = match Chip8::decode(opcode, address as u16, &ram, &platform) {
+            Ok(instruction) if !instruction.get_mnemonic().is_empty() => instruction.get_mnemonic().to_string(),
+            _ => continue,
+        };
+
+        bytes[range].fill(ByteKind::Code);
+
+        let mut decoded = Decoded {
+            opcode,
+            size,
+            mnemonic,
+            successors: Vec::new(),
+            call: None,
+            terminator: false,
+            unresolved: false,
+        };
+
+        decode_successors(&mut decoded, address, &ram, &platform);
+
+        for edge in &decoded.successors {
+            if edge.kind != EdgeKind::Fallthrough || decoded.terminator {
+                leaders.insert(edge.target);
+            }
+        }
+
+        if let Some(target) = decoded.call {
+            leaders.insert(target);
+            pending.push(target);
+        }
+
+        pending.extend(decoded.successors.iter().map(|edge| edge.target));
+        code.insert(address, decoded);
+    }
+
+    // Découpe les instructions en blocs, chacun s'arrêtant avant le prochain début de bloc.
+    let mut blocks = BTreeMap::new();
+
+    for &leader in leaders.iter().filter(|leader| code.contains_key(leader)) {
+        let mut block = BasicBlock {
+            start: leader,
+            end: leader,
+            instructions: Vec::new(),
+            successors: Vec::new(),
+            call: None,
+            unresolved: false,
+        };
+
+        while let Some(decoded) = code.get(&block.end) {
+            block.instructions.push(BlockInstruction {
+                address: block.end,
+                opcode: decoded.opcode,
+                mnemonic: decoded.mnemonic.clone(),
+            });
+            block.end += decoded.size;
+
+            if decoded.terminator || leaders.contains(&block.end) {
+                block.successors = decoded.successors.clone();
+                block.call = decoded.call;
+                block.unresolved = decoded.unresolved;
+                break;
+            }
+        }
+
+        // Seuls les blocs qui existent sont retenus comme successeurs.
+        block.successors.retain(|edge| code.contains_key(&edge.target));
+        blocks.insert(leader, block);
+    }
+
+    // Les fonctions commencent au point d'entrée et à la cible de chaque appel.
+    let mut entries: BTreeSet<u32> = blocks.values().filter_map(|block| block.call).collect();
+    entries.insert(entry);
+
+    let functions = entries
+        .into_iter()
+        .filter(|entry| blocks.contains_key(entry))
+        .map(|entry| {
+            let mut visited = BTreeSet::new();
+            let mut pending = vec![entry];
+
+            while let Some(address) = pending.pop() {
+                if visited.insert(address) {
+                    pending.extend(blocks[&address].successors.iter().map(|edge| edge.target));
+                }
+            }
+
+            let calls: BTreeSet<u32> = visited
+                .iter()
+                .filter_map(|address| blocks[address].call)
+                .filter(|target| blocks.contains_key(target))
+                .collect();
+
+            let function = Function {
+                entry,
+                blocks: visited.into_iter().collect(),
+                calls: calls.into_iter().collect(),
+            };
+
+            (entry, function)
+        })
+        .collect();
+
+    let sprites = find_sprites(&blocks, entry, &ram, &platform);
+
+    for sprite in sprites.values() {
+        let range = sprite.address.max(start)..(sprite.address + sprite.size).min(end);
+
+        for address in range {
+            let byte = &mut bytes[(address - start) as usize];
+
+            // Le code exécuté l'emporte sur les images.
+            if *byte == ByteKind::Unknown {
+                *byte = ByteKind::Sprite;
+            }
+        }
+    }
+
+    let unresolved_jumps = blocks
+        .values()
+        .filter(|block| block.unresolved)
+        .filter_map(|block| block.instructions.last())
+        .map(|instruction| instruction.address)
+        .collect();
+
+    Ok(ControlFlowGraph {
+        entry,
+        load_address: start,
+        blocks,
+        functions,
+        sprites,
+        unresolved_jumps,
+        bytes,
+    })
+}
+
+/// Ce que l'analyse sait, à un point du programme, des registres dont dépend l'image dessinée
+/// par DRW.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct DrawState {
+    /// Adresse contenue dans I.
+    i: Option<u32>,
+    /// Plans sélectionnés par FN01 sur XO-CHIP.
+    planes: Option<u8>,
+}
+
+impl DrawState {
+    const UNKNOWN: Self = Self { i: None, planes: None };
+
+    /// Au démarrage du programme, seul le premier plan est sélectionné.
+    const START: Self = Self { i: None, planes: Some(0x1) };
+
+    /// État après l'exécution de `instruction`.
+    fn next(self, instruction: &BlockInstruction, ram: &Memory, platform: &Platform) -> Self {
+        let opcode = instruction.opcode;
+        let mut state = self;
+
+        match opcode >> 12 {
+            0xA => state.i = Some((opcode & 0x0FFF) as u32),
+            // F000 NNNN charge une adresse sur 16 bits dans I.
+            0xF if opcode == 0xF000 && platform.supports_xo_chip() => {
+                state.i = ram.read16(instruction.address + 2).ok().map(|address| address as u32);
+            }
+            0xF if opcode & 0x00FF == 0x01 && platform.supports_xo_chip() => state.planes = Some(((opcode >> 8) & 0xF) as u8),
+            // Les autres instructions qui modifient I.
+            0xF if matches!(opcode & 0x00FF, 0x00 | 0x1E | 0x29 | 0x30 | 0x55 | 0x65) => state.i = None,
+            0x5 if matches!(opcode & 0x000F, 0x2 | 0x3) => state.i = None,
+            0x0 if opcode & 0xFF00 == 0x0100 => state.i = None,
+            _ => (),
+        }
+
+        state
+    }
+}
+
+/// Origine de l'exécution d'un bloc.
+#[derive(Clone, Copy)]
+enum Predecessor {
+    /// Point d'entrée du programme.
+    Start,
+    /// Passage à la suite, saut ou saut conditionnel depuis un autre bloc.
+    Block(u32),
+    /// Appel ou retour d'un appel, après lequel I peut avoir été modifié par la fonction.
+    Unknown,
+}
+
+/// État à l'entrée de chaque bloc. Il n'est connu que pour les blocs qui n'ont qu'un seul
+/// prédécesseur, dont ils reçoivent l'état de sortie.
+fn entry_states(blocks: &BTreeMap<u32, BasicBlock>, entry: u32, ram: &Memory, platform: &Platform) -> BTreeMap<u32, DrawState> {
+    let mut predecessors: BTreeMap<u32, Vec<Predecessor>> = BTreeMap::new();
+    predecessors.entry(entry).or_default().push(Predecessor::Start);
+
+    for block in blocks.values() {
+        for edge in &block.successors {
+            let predecessor = match edge.kind {
+                EdgeKind::Return => Predecessor::Unknown,
+                _ => Predecessor::Block(block.start),
+            };
+            predecessors.entry(edge.target).or_default().push(predecessor);
+        }
+
+        if let Some(target) = block.call {
+            predecessors.entry(target).or_default().push(Predecessor::Unknown);
+        }
+    }
+
+    let exit_state = |block: &BasicBlock, state: DrawState| {
+        block.instructions.iter().fold(state, |state, instruction| state.next(instruction, ram, platform))
+    };
+
+    // Les états se propagent le long des chaînes de blocs, éventuellement en boucle, jusqu'à ce
+    // qu'ils ne changent plus.
+    let mut states: BTreeMap<u32, DrawState> = blocks.keys().map(|&address| (address, DrawState::UNKNOWN)).collect();
+
+    loop {
+        let mut changed = false;
+
+        for (address, predecessors) in &predecessors {
+            let state = match predecessors[..] {
+                [Predecessor::Start] => DrawState::START,
+                [Predecessor::Block(predecessor)] => match blocks.get(&predecessor) {
+                    Some(block) => exit_state(block, states[&predecessor]),
+                    None => DrawState::UNKNOWN,
+                },
+                _ => DrawState::UNKNOWN,
+            };
+
+            if let Some(current) = states.get_mut(address) {
+                if *current != state {
+                    *current = state;
+                    changed = true;
+                }
+            }
+        }
+
+        if !changed {
+            return states;
+        }
+    }
+}
+
+/// Cherche les DRW dont l'adresse a été chargée dans I par ANNN ou F000 NNNN, dans le même bloc
+/// ou dans les blocs qui le précèdent tant qu'ils n'ont qu'un seul prédécesseur.
+fn find_sprites(blocks: &BTreeMap<u32, BasicBlock>, entry: u32, ram: &Memory, platform: &Platform) -> BTreeMap<u32, Sprite> {
+    let mut sprites: BTreeMap<u32, Sprite> = BTreeMap::new();
+    let states = entry_states(blocks, entry, ram, platform);
+
+    for block in blocks.values() {
+        let mut state = states[&block.start];
+
+        for instruction in &block.instructions {
+            let opcode = instruction.opcode;
+
+            if opcode >> 12 == 0xD {
+                let Some(address) = state.i else {
+                    continue;
+                };
+
+                // DXY0 dessine une image de 16x16 pixels en mode étendu.
+                let plane_size = match opcode & 0x000F {
+                    0 if platform.supports_superchip() => 32,
+                    n => n as u32,
+                };
+
+                // Sur XO-CHIP, les données de chaque plan sélectionné se suivent. Si les plans
+                // ne sont pas connus, tous sont comptés.
+                let planes = if platform.supports_xo_chip() {
+                    state.planes.map_or(PLANES_COUNT as u32, |planes| (planes as u32 & ((1 << PLANES_COUNT) - 1)).count_ones())
+                } else {
+                    1
+                };
+
+                let size = plane_size * planes;
+
+                if size == 0 {
+                    continue;
+                }
+
+                let sprite = sprites.entry(address).or_insert(Sprite {
+                    address,
+                    size,
+                    drawn_by: Vec::new(),
+                });
+
+                sprite.size = sprite.size.max(size);
+
+                if !sprite.drawn_by.contains(&block.start) {
+                    sprite.drawn_by.push(block.start);
+                }
+            }
+
+            state = state.next(instruction, ram, platform);
+        }
+    }
+
+    sprites
+}
+
+/// Échappe `value` pour l'écrire entre guillemets dans un fichier DOT.
+fn dot_string(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+impl ControlFlowGraph {
+    /// Retourne l'utilisation de l'octet à `address`, ou `None` s'il est en dehors du programme.
+    pub fn byte_kind(&self, address: u32) -> Option<ByteKind> {
+        let offset = address.checked_sub(self.load_address)?;
+
+        self.bytes.get(offset as usize).copied()
+    }
+
+    /// Retourne le graphe au format DOT de Graphviz. Chaque fonction est regroupée dans un
+    /// cadre, les appels sont en pointillés, les sauts BNNN mènent à un nœud rouge et les
+    /// images sont reliées aux blocs qui les dessinent.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph cfg {\n    node [shape=box, fontname=\"monospace\"];\n");
+
+        // Un bloc partagé par plusieurs fonctions est placé dans celle dont le point d'entrée
+        // est le plus proche, comme le code d'une fonction dans laquelle le programme continue.
+        let mut placed = BTreeSet::new();
+
+        for function in self.functions.values().rev() {
+            let name = if function.entry == self.entry {
+                "start".to_string()
+            } else {
+                format!("sub_{:04X}", function.entry)
+            };
+
+            dot.push_str(&format!("    subgraph cluster_{:04X} {{\n        label=\"{name}\";\n", function.entry));
+
+            for address in &function.blocks {
+                if placed.insert(*address) {
+                    dot.push_str(&format!("        {};\n", self.block_node(*address)));
+                }
+            }
+
+            dot.push_str("    }\n");
+        }
+
+        for block in self.blocks.values() {
+            let node = format!("b_{:04X}", block.start);
+
+            for edge in &block.successors {
+                let attributes = match edge.kind {
+                    EdgeKind::Fallthrough => "",
+                    EdgeKind::Jump => " [label=\"jump\"]",
+                    EdgeKind::Skip => " [label=\"skip\"]",
+                    EdgeKind::Return => " [label=\"return\"]",
+                };
+
+                dot.push_str(&format!("    {node} -> b_{:04X}{attributes};\n", edge.target));
+            }
+
+            if let Some(target) = block.call.filter(|target| self.blocks.contains_key(target)) {
+                dot.push_str(&format!("    {node} -> b_{target:04X} [label=\"call\", style=dashed];\n"));
+            }
+
+            if block.unresolved {
+                dot.push_str(&format!(
+                    "    u_{:04X} [label=\"?\", shape=octagon, color=red];\n    {node} -> u_{:04X} [label=\"V0\", color=red];\n",
+                    block.start, block.start
+                ));
+            }
+        }
+
+        for sprite in self.sprites.values() {
+            dot.push_str(&format!(
+                "    s_{:04X} [label=\"sprite {:04X}\\n{} bytes\", shape=note];\n",
+                sprite.address, sprite.address, sprite.size
+            ));
+
+            for block in &sprite.drawn_by {
+                dot.push_str(&format!("    b_{block:04X} -> s_{:04X} [style=dotted];\n", sprite.address));
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Retourne le graphe des appels au format DOT de Graphviz, une fonction par nœud.
+    pub fn call_graph_to_dot(&self) -> String {
+        let mut dot = String::from("digraph calls {\n    node [shape=box, fontname=\"monospace\"];\n");
+
+        for function in self.functions.values() {
+            let label = if function.entry == self.entry {
+                "start".to_string()
+            } else {
+                format!("sub_{:04X}", function.entry)
+            };
+
+            dot.push_str(&format!("    f_{:04X} [label=\"{label}\"];\n", function.entry));
+
+            for target in &function.calls {
+                dot.push_str(&format!("    f_{:04X} -> f_{target:04X};\n", function.entry));
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Nœud d'un bloc, avec l'adresse et le texte de chaque instruction alignés à gauche.
+    fn block_node(&self, address: u32) -> String {
+        let block = &self.blocks[&address];
+        let label: String = block
+            .instructions
+            .iter()
+            .map(|instruction| format!("{:04X}  {}\\l", instruction.address, dot_string(&instruction.mnemonic)))
+            .collect();
+
+        format!("b_{address:04X} [label=\"{label}\"]")
+    }
+}
@@ -1,13 +1,23 @@
-use std::{
-    any::Any,
-    fs,
-    time::{Duration, Instant},
-};
+use std::{any::Any, fs};
 
 use crate::{
-    instruction::{self, Instruction},
+    audio::PatternSynth,
+    clock::{Clock, DEFAULT_INSTRUCTIONS_PER_FRAME},
+    error::Chip8Error,
+    font::DEFAULT_FONT_ADDRESS,
+    instruction::{self, Context, Instruction},
+    layout::{self, MemoryLayout, VIP_STACK_DEPTH},
+    machine::Machine,
+    megachip::MEGA_MEMORY_SIZE,
     memory::Memory,
+    platform::Platform,
+    profile::Profile,
+    quirks::{DisplayWait, Quirks},
     register::Registers,
+    rpl::{self, RPL_FLAGS_COUNT},
+    screen::Screen,
+    sound::{Sound, SoundEventKind, SoundSettings, Voice},
+    timing::{self, Timing},
 };
 
 pub struct CallbackData {
@@ -37,10 +47,32 @@ impl CallbackData {
     }
 }
 
+/// Callback qui ne reçoit que les données de l'hôte.
+type Callback<'a> = Box<dyn FnMut(&mut CallbackData) + 'a>;
+/// Callback qui reçoit la position d'un pixel.
+type PixelCallback<'a> = Box<dyn FnMut(&mut CallbackData, u8, u8) + 'a>;
+/// Callback qui reçoit la position d'un pixel et l'indice de sa couleur.
+type PixelColorCallback<'a> = Box<dyn FnMut(&mut CallbackData, u8, u8, u8) + 'a>;
+/// Callback qui reçoit la largeur et la hauteur de l'écran.
+type SizeCallback<'a> = Box<dyn FnMut(&mut CallbackData, u16, u16) + 'a>;
+/// Callback qui reçoit des octets, comme les drapeaux RPL.
+type BytesCallback<'a> = Box<dyn FnMut(&mut CallbackData, &[u8]) + 'a>;
+/// Callback qui reçoit l'état du buzzer.
+type BeepCallback<'a> = Box<dyn FnMut(&mut CallbackData, bool) + 'a>;
+/// Callback qui reçoit une image du MEGA-CHIP avec sa largeur et sa hauteur.
+type FrameCallback<'a> = Box<dyn FnMut(&mut CallbackData, &[u8], u16, u16) + 'a>;
+
 pub struct Chip8Callback<'a> {
-    pub(crate) clear_pixel: Box<dyn FnMut(&mut CallbackData) + 'a>,
-    pub(crate) set_pixel: Box<dyn FnMut(&mut CallbackData, u8, u8) + 'a>,
-    pub(crate) unset_pixel: Box<dyn FnMut(&mut CallbackData, u8, u8) + 'a>,
+    pub(crate) clear_pixel: Callback<'a>,
+    pub(crate) set_pixel: PixelCallback<'a>,
+    pub(crate) unset_pixel: PixelCallback<'a>,
+    pub(crate) pixel_color: PixelColorCallback<'a>,
+    pub(crate) resolution_changed: SizeCallback<'a>,
+    pub(crate) rpl_flags_saved: BytesCallback<'a>,
+    pub(crate) beep: BeepCallback<'a>,
+    pub(crate) colors_changed: Callback<'a>,
+    pub(crate) mega_frame: FrameCallback<'a>,
+    pub(crate) frame_ready: Callback<'a>,
     pub(crate) callback_data: CallbackData,
 }
 
@@ -60,73 +92,353 @@ impl<'a> Chip8Callback<'a> {
     pub fn set_unset_pixel_callback(&mut self, c: impl FnMut(&mut CallbackData, u8, u8) + 'a) {
         self.unset_pixel = Box::new(c);
     }
+
+    /// Appelée avec l'indice de couleur sur 2 bits du pixel à chaque fois qu'il change.
+    /// L'indice combine les deux plans du XO-CHIP, il vaut 0 ou 1 sur les autres plateformes.
+    pub fn set_pixel_color_callback(&mut self, c: impl FnMut(&mut CallbackData, u8, u8, u8) + 'a) {
+        self.pixel_color = Box::new(c);
+    }
+
+    /// Appelée avec la nouvelle largeur et hauteur de l'écran lorsque sa résolution change.
+    pub fn set_resolution_changed_callback(&mut self, c: impl FnMut(&mut CallbackData, u16, u16) + 'a) {
+        self.resolution_changed = Box::new(c);
+    }
+
+    /// Appelée avec tous les drapeaux RPL lorsque le programme en modifie avec FX75.
+    pub fn set_rpl_flags_saved_callback(&mut self, c: impl FnMut(&mut CallbackData, &[u8]) + 'a) {
+        self.rpl_flags_saved = Box::new(c);
+    }
+
+    /// Appelée avec `true` lorsque le son commence et `false` lorsqu'il s'arrête.
+    pub fn set_beep_callback(&mut self, c: impl FnMut(&mut CallbackData, bool) + 'a) {
+        self.beep = Box::new(c);
+    }
+
+    /// Appelée lorsque la couleur du fond ou des zones de l'écran du CHIP-8X change, les couleurs
+    /// peuvent alors être relues depuis l'écran.
+    pub fn set_colors_changed_callback(&mut self, c: impl FnMut(&mut CallbackData) + 'a) {
+        self.colors_changed = Box::new(c);
+    }
+
+    /// Appelée en mode MEGA-CHIP à chaque fois que 00E0 affiche une nouvelle image, avec ses
+    /// pixels RGBA ligne par ligne, sa largeur et sa hauteur.
+    pub fn set_mega_frame_callback(&mut self, c: impl FnMut(&mut CallbackData, &[u8], u16, u16) + 'a) {
+        self.mega_frame = Box::new(c);
+    }
+
+    /// Appelée à chaque fin de frame émulée, lorsque l'image est complète et peut être affichée.
+    /// Avec le quirk `display_wait`, aucun sprite n'est dessiné entre deux appels.
+    pub fn set_frame_ready_callback(&mut self, c: impl FnMut(&mut CallbackData) + 'a) {
+        self.frame_ready = Box::new(c);
+    }
 }
 
 pub struct Chip8<'a> {
     ram: Memory,
     stack: Memory,
     registers: Registers,
-    screen: [u8; 64 * 32],
-    keys: [bool; 0x10],
+    screen: Screen,
+    keys: [bool; KEYPAD_SIZE * 2],
     paused: bool,
     callbacks: Chip8Callback<'a>,
     need_to_fetch: bool,
     next_instruction: Instruction<'a>,
-    execution_instant: Instant,
+    clock: Clock,
+    quirks: Quirks,
+    platform: Platform,
+    rom_hash: u64,
+    pattern_synth: PatternSynth,
+    sound: Option<Sound>,
+    memory_layout: MemoryLayout,
 }
 
-fn add_hex_sprites(ram: &mut Memory) -> Result<(), String> {
-    // Tableau qui contient les sprites des nombres hexadécimaux allant de 'O' à 'F'.
-    let sprites: [u8; 80] = [
-        0xF0, 0x90, 0x90, 0x90, 0xF0, 0x20, 0x60, 0x20, 0x20, 0x70, 0xF0, 0x10, 0xF0, 0x80, 0xF0,
-        0xF0, 0x10, 0xF0, 0x10, 0xF0, 0x90, 0x90, 0xF0, 0x10, 0x10, 0xF0, 0x80, 0xF0, 0x10, 0xF0,
-        0xF0, 0x80, 0xF0, 0x90, 0xF0, 0xF0, 0x10, 0x20, 0x40, 0x40, 0xF0, 0x90, 0xF0, 0x90, 0xF0,
-        0xF0, 0x90, 0xF0, 0x10, 0xF0, 0xF0, 0x90, 0xF0, 0x90, 0x90, 0xE0, 0x90, 0xE0, 0x90, 0xE0,
-        0xF0, 0x80, 0x80, 0x80, 0xF0, 0xE0, 0x90, 0x90, 0x90, 0xE0, 0xF0, 0x80, 0xF0, 0x80, 0xF0,
-        0xF0, 0x80, 0xF0, 0x80, 0x80,
-    ];
+/// Taille de la RAM d'un CHIP-8 classique.
+pub const DEFAULT_MEMORY_SIZE: usize = 0x1000;
+
+/// Adresse à laquelle sont chargés les programmes.
+pub const DEFAULT_LOAD_ADDRESS: u16 = 0x200;
+
+/// Nombre de touches d'un clavier, le CHIP-8X en possédant un second.
+pub const KEYPAD_SIZE: usize = 0x10;
 
-    ram.write8_range(0, sprites.len() as u16, &sprites)?;
+/// Nombre d'adresses de retour que peut contenir la pile.
+pub const DEFAULT_STACK_DEPTH: usize = 16;
 
-    Ok(())
+/// Profondeur maximale de la pile, limitée par la taille du registre SP.
+/// Elle permet de déboguer un programme sans limite pratique au nombre d'appels imbriqués.
+pub const MAX_STACK_DEPTH: usize = 0x7FFF;
+
+/// Appel de fonction en cours, tel que l'a enregistré 2NNN.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StackFrame {
+    /// Adresse de l'instruction 2NNN.
+    pub call_site: u16,
+    /// Adresse à laquelle 00EE reprendra l'exécution.
+    pub return_address: u16,
+    /// Adresse de la fonction appelée.
+    pub target: u16,
 }
 
-impl<'a> Chip8<'a> {
-    pub fn build(program_name: &str) -> Result<Self, String> {
-        // Lit le contenu du fichier et le stock dans un Vecteur u8.
-        let content = match fs::read(program_name) {
-            Ok(t) => t,
-            Err(err) => return Err(err.to_string()),
-        };
+/// Sprites des nombres hexadécimaux allant de '0' à 'F'.
+pub const HEX_SPRITES: [u8; 80] = [
+    0xF0, 0x90, 0x90, 0x90, 0xF0, 0x20, 0x60, 0x20, 0x20, 0x70, 0xF0, 0x10, 0xF0, 0x80, 0xF0,
+    0xF0, 0x10, 0xF0, 0x10, 0xF0, 0x90, 0x90, 0xF0, 0x10, 0x10, 0xF0, 0x80, 0xF0, 0x10, 0xF0,
+    0xF0, 0x80, 0xF0, 0x90, 0xF0, 0xF0, 0x10, 0x20, 0x40, 0x40, 0xF0, 0x90, 0xF0, 0x90, 0xF0,
+    0xF0, 0x90, 0xF0, 0x10, 0xF0, 0xF0, 0x90, 0xF0, 0x90, 0x90, 0xE0, 0x90, 0xE0, 0x90, 0xE0,
+    0xF0, 0x80, 0x80, 0x80, 0xF0, 0xE0, 0x90, 0x90, 0x90, 0xE0, 0xF0, 0x80, 0xF0, 0x80, 0xF0,
+    0xF0, 0x80, 0xF0, 0x80, 0x80,
+];
+
+/// Adresse par défaut des grands sprites hexadécimaux du SUPER-CHIP, juste après les petits.
+pub const BIG_FONT_ADDRESS: u16 = 0x50;
+
+/// Sprites de 8x10 pixels des nombres hexadécimaux allant de '0' à 'F', utilisés par FX30.
+pub const BIG_HEX_SPRITES: [u8; 160] = [
+    0xFF, 0xFF, 0xC3, 0xC3, 0xC3, 0xC3, 0xC3, 0xC3, 0xFF, 0xFF, 0x18, 0x78, 0x78, 0x18, 0x18,
+    0x18, 0x18, 0x18, 0xFF, 0xFF, 0xFF, 0xFF, 0x03, 0x03, 0xFF, 0xFF, 0xC0, 0xC0, 0xFF, 0xFF,
+    0xFF, 0xFF, 0x03, 0x03, 0xFF, 0xFF, 0x03, 0x03, 0xFF, 0xFF, 0xC3, 0xC3, 0xC3, 0xC3, 0xFF,
+    0xFF, 0x03, 0x03, 0x03, 0x03, 0xFF, 0xFF, 0xC0, 0xC0, 0xFF, 0xFF, 0x03, 0x03, 0xFF, 0xFF,
+    0xFF, 0xFF, 0xC0, 0xC0, 0xFF, 0xFF, 0xC3, 0xC3, 0xFF, 0xFF, 0xFF, 0xFF, 0x03, 0x03, 0x06,
+    0x0C, 0x18, 0x18, 0x18, 0x18, 0xFF, 0xFF, 0xC3, 0xC3, 0xFF, 0xFF, 0xC3, 0xC3, 0xFF, 0xFF,
+    0xFF, 0xFF, 0xC3, 0xC3, 0xFF, 0xFF, 0x03, 0x03, 0xFF, 0xFF, 0x7E, 0xFF, 0xC3, 0xC3, 0xC3,
+    0xFF, 0xFF, 0xC3, 0xC3, 0xC3, 0xFC, 0xFC, 0xC3, 0xC3, 0xFC, 0xFC, 0xC3, 0xC3, 0xFC, 0xFC,
+    0x3C, 0xFF, 0xC3, 0xC0, 0xC0, 0xC0, 0xC0, 0xC3, 0xFF, 0x3C, 0xFC, 0xFE, 0xC3, 0xC3, 0xC3,
+    0xC3, 0xC3, 0xC3, 0xFE, 0xFC, 0xFF, 0xFF, 0xC0, 0xC0, 0xFF, 0xFF, 0xC0, 0xC0, 0xFF, 0xFF,
+    0xFF, 0xFF, 0xC0, 0xC0, 0xFF, 0xFF, 0xC0, 0xC0, 0xC0, 0xC0,
+];
+
+/// Permet de configurer la machine avant d'y charger un programme.
+pub struct Chip8Builder {
+    load_address: Option<u16>,
+    memory_size: Option<usize>,
+    font: Vec<u8>,
+    font_address: u16,
+    big_font: Vec<u8>,
+    stack_depth: usize,
+    instructions_per_frame: u32,
+    timing: Timing,
+    quirks: Quirks,
+    platform: Platform,
+    sound: Option<SoundSettings>,
+    memory_layout: MemoryLayout,
+}
 
-        println!("Program size: {}", content.len());
+impl Chip8Builder {
+    pub fn new() -> Self {
+        Self {
+            load_address: None,
+            memory_size: None,
+            font: HEX_SPRITES.to_vec(),
+            font_address: DEFAULT_FONT_ADDRESS,
+            big_font: BIG_HEX_SPRITES.to_vec(),
+            stack_depth: DEFAULT_STACK_DEPTH,
+            instructions_per_frame: DEFAULT_INSTRUCTIONS_PER_FRAME,
+            timing: Timing::Fixed,
+            quirks: Quirks::default(),
+            platform: Platform::default(),
+            sound: None,
+            memory_layout: MemoryLayout::Separate,
+        }
+    }
+
+    /// Adresse à laquelle le programme est copié et où démarre le registre PC.
+    /// Par défaut, il s'agit de l'adresse de chargement de la plateforme.
+    pub fn load_address(mut self, value: u16) -> Self {
+        self.load_address = Some(value);
+        self
+    }
 
-        let mut ram = Memory::new(0x1000);
+    /// Taille de la RAM en octets, les adresses étant au plus sur 24 bits elle ne peut pas
+    /// dépasser 16 Mio.
+    /// Par défaut, il s'agit de la taille de la RAM de la plateforme.
+    pub fn memory_size(mut self, value: usize) -> Self {
+        self.memory_size = Some(value.min(MEGA_MEMORY_SIZE));
+        self
+    }
+
+    /// Sprites des caractères hexadécimaux, copiés à l'adresse de la police.
+    pub fn font(mut self, font: &[u8]) -> Self {
+        self.font = font.to_vec();
+        self
+    }
+
+    /// Adresse à laquelle est copiée la police, 0 par défaut.
+    /// Les grands sprites sont copiés juste après.
+    pub fn font_address(mut self, value: u16) -> Self {
+        self.font_address = value;
+        self
+    }
+
+    /// Grands sprites des caractères hexadécimaux, copiés juste après la police.
+    pub fn big_font(mut self, font: &[u8]) -> Self {
+        self.big_font = font.to_vec();
+        self
+    }
+
+    /// Nombre d'appels de fonctions imbriqués que peut contenir la pile, au plus
+    /// `MAX_STACK_DEPTH`.
+    pub fn stack_depth(mut self, value: usize) -> Self {
+        self.stack_depth = value.min(MAX_STACK_DEPTH);
+        self
+    }
+
+    /// Emplacement de la pile, des registres et de l'écran.
+    /// Avec la disposition du COSMAC VIP, la pile ne peut pas dépasser `VIP_STACK_DEPTH` appels
+    /// et la RAM doit faire au moins 4 Kio.
+    /// Un programme qui déborde sur la zone de 0xEA0 à 0x1000 est tout de même accepté : ses
+    /// octets sont alors lus dans les registres V0 à VF et l'écran dès la première instruction,
+    /// sans erreur.
+    pub fn memory_layout(mut self, value: MemoryLayout) -> Self {
+        self.memory_layout = value;
+        self
+    }
+
+    pub fn instructions_per_frame(mut self, value: u32) -> Self {
+        self.instructions_per_frame = value;
+        self
+    }
+
+    /// Durée des instructions. Avec le timing du COSMAC VIP, le nombre d'instructions par frame
+    /// n'est plus utilisé.
+    pub fn timing(mut self, value: Timing) -> Self {
+        self.timing = value;
+        self
+    }
+
+    /// Interprétation des instructions ambiguës.
+    pub fn quirks(mut self, value: Quirks) -> Self {
+        self.quirks = value;
+        self
+    }
+
+    /// Jeu d'instructions reconnu par l'interpréteur.
+    /// Le CHIP-8 classique est remplacé par le CHIP-8 hires si le programme commence par son
+    /// en-tête.
+    pub fn platform(mut self, value: Platform) -> Self {
+        self.platform = value;
+        self
+    }
+
+    /// Applique tous les paramètres de la machine décrite par `profile`.
+    pub fn profile(mut self, profile: &Profile) -> Self {
+        self.platform = profile.platform;
+        self.load_address = Some(profile.load_address);
+        self.memory_size = Some(profile.memory_size);
+        self.font = profile.font.clone();
+        self.stack_depth = profile.stack_depth;
+        self.instructions_per_frame = profile.instructions_per_frame;
+        self.timing = profile.timing;
+        self.quirks = profile.quirks;
+        self
+    }
+
+    /// Active la génération du son à chaque frame émulée.
+    pub fn sound(mut self, settings: SoundSettings) -> Self {
+        self.sound = Some(settings);
+        self
+    }
+
+    pub fn build<'a>(self, rom: &[u8]) -> Result<Chip8<'a>, Chip8Error> {
+        if rom.is_empty() {
+            return Err(Chip8Error::EmptyRom);
+        }
+
+        let load_address = self.load_address.unwrap_or(self.platform.load_address());
+        let platform = self.platform.detect(rom, load_address);
+        let mut ram = Memory::new(self.memory_size.unwrap_or(platform.memory_size()));
+
+        // La disposition du COSMAC VIP utilise le haut des 4 premiers Kio de RAM.
+        if self.memory_layout == MemoryLayout::CosmacVip && ram.len() < DEFAULT_MEMORY_SIZE {
+            return Err(Chip8Error::MemoryOutOfBounds {
+                address: DEFAULT_MEMORY_SIZE - 1,
+                size: ram.len(),
+            });
+        }
+
+        // Le programme doit tenir entre son adresse de chargement et la fin de la RAM.
+        let max = ram.len().saturating_sub(load_address as usize);
+        if rom.len() > max {
+            return Err(Chip8Error::RomTooLarge {
+                size: rom.len(),
+                max,
+            });
+        }
 
         // Ajoute les sprites des nombres hexadécimaux.
-        add_hex_sprites(&mut ram)?;
+        let big_font_address = self.font_address as usize + self.font.len();
+        ram.load(self.font_address as u32, &self.font)?;
+        ram.load(big_font_address as u32, &self.big_font)?;
+
+        // Copie le programme dans la RAM.
+        ram.load(load_address as u32, rom)?;
+
+        let mut registers = Registers::new();
+        registers.pc = platform.start_address(load_address);
+        registers.font_address = self.font_address;
+        registers.big_font_address = big_font_address as u16;
+
+        let (width, height) = platform.screen_size();
 
-        // Copie le contenu du vecteur dans le buffer de la RAM.
-        ram.write8_range(0x200, content.len() as u16 + 0x200, &content)?;
+        let stack_depth = match self.memory_layout {
+            MemoryLayout::Separate => self.stack_depth,
+            MemoryLayout::CosmacVip => self.stack_depth.min(VIP_STACK_DEPTH),
+        };
+
+        let mut clock = Clock::new(self.instructions_per_frame);
+        clock.set_timing(self.timing);
 
-        Ok(Self {
+        Ok(Chip8 {
             ram,
-            stack: Memory::new(0x20),
-            registers: Registers::new(),
-            screen: [0; 64 * 32],
-            keys: [false; 0x10],
+            stack: Memory::new(stack_depth * 2),
+            registers,
+            screen: Screen::with_size(width, height),
+            keys: [false; KEYPAD_SIZE * 2],
             paused: true,
             callbacks: Chip8Callback {
                 clear_pixel: Box::new(|_| {}),
                 set_pixel: Box::new(|_, _, _| {}),
                 unset_pixel: Box::new(|_, _, _| {}),
+                pixel_color: Box::new(|_, _, _, _| {}),
+                resolution_changed: Box::new(|_, _, _| {}),
+                rpl_flags_saved: Box::new(|_, _| {}),
+                beep: Box::new(|_, _| {}),
+                colors_changed: Box::new(|_| {}),
+                mega_frame: Box::new(|_, _, _, _| {}),
+                frame_ready: Box::new(|_| {}),
                 callback_data: CallbackData { data: None },
             },
             need_to_fetch: true,
             next_instruction: Instruction::new(String::new(), 0x0000, 0x0000),
-            execution_instant: Instant::now(),
+            clock,
+            quirks: self.quirks,
+            platform,
+            rom_hash: rpl::rom_hash(rom),
+            pattern_synth: PatternSynth::new(),
+            sound: self.sound.map(Sound::new),
+            memory_layout: self.memory_layout,
         })
     }
+}
+
+impl Default for Chip8Builder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> Chip8<'a> {
+    /// Charge le programme depuis un fichier avec la configuration par défaut.
+    pub fn build(program_name: &str) -> Result<Self, Chip8Error> {
+        // Lit le contenu du fichier et le stock dans un Vecteur u8.
+        let content = fs::read(program_name)?;
+
+        Self::from_rom(&content)
+    }
+
+    /// Charge le programme depuis un tableau d'octets avec la configuration par défaut.
+    pub fn from_rom(rom: &[u8]) -> Result<Self, Chip8Error> {
+        Chip8Builder::new().build(rom)
+    }
 
     pub fn print_registers(&self) {
         println!(
@@ -155,10 +467,6 @@ impl<'a> Chip8<'a> {
         );
     }
 
-    pub fn get_elapsed_time_since_last_instruction(&self) -> Duration {
-        self.execution_instant.elapsed()
-    }
-
     pub fn need_to_fetch(&self) -> bool {
         self.need_to_fetch
     }
@@ -167,15 +475,25 @@ impl<'a> Chip8<'a> {
         self.need_to_fetch = value;
     }
 
-    pub fn fetch_next_instruction(&self) -> Result<u16, String> {
-        match self.ram.read16(self.registers.pc) {
-            Ok(o) => Ok(o),
-            Err(err) => return Err(err),
-        }
+    pub fn fetch_next_instruction(&self) -> Result<u16, Chip8Error> {
+        self.ram.read16(self.registers.pc as u32)
+    }
+
+    pub fn decode_instruction(&mut self, instruction: u16) -> Result<&str, Chip8Error> {
+        self.next_instruction = Self::decode(instruction, self.registers.pc, &self.ram, &self.platform)?;
+
+        Ok(self.next_instruction.get_disassembled())
     }
 
-    pub fn decode_instruction(&mut self, instruction: u16) -> Result<&str, String> {
-        let mut next_instruction = Instruction::new(String::new(), instruction, self.registers.pc);
+    /// Décode l'instruction située à l'adresse `pc` pour la plateforme donnée, sans l'exécuter.
+    /// La RAM n'est lue que par les instructions sur 4 octets.
+    pub(crate) fn decode(instruction: u16, pc: u16, ram: &Memory, platform: &Platform) -> Result<Instruction<'a>, Chip8Error> {
+        let mut next_instruction = Instruction::new(String::new(), instruction, pc);
+        let superchip = platform.supports_superchip();
+        let xo_chip = platform.supports_xo_chip();
+        let hires_chip8 = platform.supports_hires_chip8();
+        let chip8x = platform.supports_chip8x();
+        let megachip = platform.supports_megachip();
 
         match (instruction & 0xF000) >> 12 {
             0x0 => {
@@ -190,6 +508,145 @@ impl<'a> Chip8<'a> {
                         next_instruction.set_disassembled("RET".to_string());
                         next_instruction.set_callback(instruction::ret);
                     }
+                    0x00C0..=0x00CF if superchip => {
+                        // Décale l'écran de n lignes vers le bas.
+                        next_instruction.set_disassembled(format!(
+                            "SCD {}",
+                            next_instruction.borrow_operands().nibble
+                        ));
+                        next_instruction.set_callback(instruction::scd_nibble);
+                    }
+                    0x00B0..=0x00BF if megachip => {
+                        // Décale l'écran de n lignes vers le haut.
+                        next_instruction.set_disassembled(format!(
+                            "SCU {}",
+                            next_instruction.borrow_operands().nibble
+                        ));
+                        next_instruction.set_callback(instruction::scu_nibble);
+                    }
+                    0x00D0..=0x00DF if xo_chip => {
+                        // Décale l'écran de n lignes vers le haut.
+                        next_instruction.set_disassembled(format!(
+                            "SCU {}",
+                            next_instruction.borrow_operands().nibble
+                        ));
+                        next_instruction.set_callback(instruction::scu_nibble);
+                    }
+                    0x00FB if superchip => {
+                        // Décale l'écran de 4 pixels vers la droite.
+                        next_instruction.set_disassembled("SCR".to_string());
+                        next_instruction.set_callback(instruction::scr);
+                    }
+                    0x00FC if superchip => {
+                        // Décale l'écran de 4 pixels vers la gauche.
+                        next_instruction.set_disassembled("SCL".to_string());
+                        next_instruction.set_callback(instruction::scl);
+                    }
+                    0x00FD if superchip => {
+                        // Arrête l'interpréteur.
+                        next_instruction.set_disassembled("EXIT".to_string());
+                        next_instruction.set_callback(instruction::exit);
+                    }
+                    0x00FE if superchip => {
+                        // Passe l'écran en basse résolution.
+                        next_instruction.set_disassembled("LOW".to_string());
+                        next_instruction.set_callback(instruction::low);
+                    }
+                    0x00FF if superchip => {
+                        // Passe l'écran en haute résolution.
+                        next_instruction.set_disassembled("HIGH".to_string());
+                        next_instruction.set_callback(instruction::high);
+                    }
+                    0x0010 if megachip => {
+                        // Quitte le mode MEGA-CHIP.
+                        next_instruction.set_disassembled("MEGAOFF".to_string());
+                        next_instruction.set_callback(instruction::mega_off);
+                    }
+                    0x0011 if megachip => {
+                        // Passe en mode MEGA-CHIP : écran de 256x192 pixels en couleurs.
+                        next_instruction.set_disassembled("MEGAON".to_string());
+                        next_instruction.set_callback(instruction::mega_on);
+                    }
+                    0x0100..=0x01FF if megachip => {
+                        // Met la valeur du registre I à l'adresse sur 24 bits formée par kk et
+                        // les 16 bits qui suivent l'instruction.
+                        let low = ram.read16(pc.wrapping_add(2) as u32)?;
+                        let address = ((next_instruction.borrow_operands().kk as u32) << 16) | low as u32;
+
+                        next_instruction.set_disassembled(format!("LDHI I, ${address:06X}"));
+                        next_instruction.set_callback(instruction::ld_i_huge);
+                    }
+                    0x0200..=0x02FF if megachip => {
+                        // Charge kk couleurs de la palette depuis l'adresse I.
+                        next_instruction.set_disassembled(format!(
+                            "LDPAL {:02X}",
+                            next_instruction.borrow_operands().kk
+                        ));
+                        next_instruction.set_callback(instruction::ld_pal_byte);
+                    }
+                    0x0300..=0x03FF if megachip => {
+                        // Change la largeur des sprites.
+                        next_instruction.set_disassembled(format!(
+                            "SPRW {:02X}",
+                            next_instruction.borrow_operands().kk
+                        ));
+                        next_instruction.set_callback(instruction::sprw_byte);
+                    }
+                    0x0400..=0x04FF if megachip => {
+                        // Change la hauteur des sprites.
+                        next_instruction.set_disassembled(format!(
+                            "SPRH {:02X}",
+                            next_instruction.borrow_operands().kk
+                        ));
+                        next_instruction.set_callback(instruction::sprh_byte);
+                    }
+                    0x0500..=0x05FF if megachip => {
+                        // Change l'opacité de l'écran.
+                        next_instruction.set_disassembled(format!(
+                            "ALPHA {:02X}",
+                            next_instruction.borrow_operands().kk
+                        ));
+                        next_instruction.set_callback(instruction::alpha_byte);
+                    }
+                    0x0600..=0x060F if megachip => {
+                        // Joue le son numérisé situé à l'adresse I.
+                        next_instruction.set_disassembled(format!(
+                            "DIGISND {}",
+                            next_instruction.borrow_operands().nibble
+                        ));
+                        next_instruction.set_callback(instruction::digisnd_nibble);
+                    }
+                    0x0700 if megachip => {
+                        // Arrête le son numérisé.
+                        next_instruction.set_disassembled("STOPSND".to_string());
+                        next_instruction.set_callback(instruction::stopsnd);
+                    }
+                    0x0800..=0x080F if megachip => {
+                        // Change le mode de mélange des sprites.
+                        next_instruction.set_disassembled(format!(
+                            "BMODE {}",
+                            next_instruction.borrow_operands().nibble
+                        ));
+                        next_instruction.set_callback(instruction::bmode_nibble);
+                    }
+                    0x0900..=0x09FF if megachip => {
+                        // Change la couleur qui provoque les collisions.
+                        next_instruction.set_disassembled(format!(
+                            "CCOL {:02X}",
+                            next_instruction.borrow_operands().kk
+                        ));
+                        next_instruction.set_callback(instruction::ccol_byte);
+                    }
+                    0x02A0 if chip8x => {
+                        // Passe à la couleur de fond suivante.
+                        next_instruction.set_disassembled("BGC".to_string());
+                        next_instruction.set_callback(instruction::cycle_background);
+                    }
+                    0x0230 if hires_chip8 => {
+                        // Nettoie l'écran de 64x64 pixels du CHIP-8 hires.
+                        next_instruction.set_disassembled("CLS".to_string());
+                        next_instruction.set_callback(instruction::clean_screen);
+                    }
                     _ => {
                         // Ignorée par les interpréteurs modernes.
                         next_instruction.set_disassembled(format!(
@@ -233,15 +690,45 @@ impl<'a> Chip8<'a> {
                 ));
                 next_instruction.set_callback(instruction::sne_reg_byte);
             }
-            0x5 => {
-                // Ignore la prochaine instruction si Vx == Vy
-                next_instruction.set_disassembled(format!(
-                    "SE V{:01X}, V{:01X}",
-                    next_instruction.borrow_operands().x,
-                    next_instruction.borrow_operands().y
-                ));
-                next_instruction.set_callback(instruction::se_reg_reg);
-            }
+            0x5 => match instruction & 0x000F {
+                0x0 => {
+                    // Ignore la prochaine instruction si Vx == Vy
+                    next_instruction.set_disassembled(format!(
+                        "SE V{:01X}, V{:01X}",
+                        next_instruction.borrow_operands().x,
+                        next_instruction.borrow_operands().y
+                    ));
+                    next_instruction.set_callback(instruction::se_reg_reg);
+                }
+                0x1 if chip8x => {
+                    // Additionne chaque quartet de Vy à celui de Vx.
+                    next_instruction.set_disassembled(format!(
+                        "ADDN V{:01X}, V{:01X}",
+                        next_instruction.borrow_operands().x,
+                        next_instruction.borrow_operands().y
+                    ));
+                    next_instruction.set_callback(instruction::add_reg_reg_nibbles);
+                }
+                0x2 if xo_chip => {
+                    // Stock les registres de Vx à Vy dans la mémoire à partir de l'adresse I.
+                    next_instruction.set_disassembled(format!(
+                        "LD [I], V{:01X}-V{:01X}",
+                        next_instruction.borrow_operands().x,
+                        next_instruction.borrow_operands().y
+                    ));
+                    next_instruction.set_callback(instruction::ld_to_i_range);
+                }
+                0x3 if xo_chip => {
+                    // Lit les registres de Vx à Vy depuis la mémoire à partir de l'adresse I.
+                    next_instruction.set_disassembled(format!(
+                        "LD V{:01X}-V{:01X}, [I]",
+                        next_instruction.borrow_operands().x,
+                        next_instruction.borrow_operands().y
+                    ));
+                    next_instruction.set_callback(instruction::ld_range_from_i);
+                }
+                _ => (),
+            },
             0x6 => {
                 // Met la valeur kk dans le registre Vx.
                 next_instruction.set_disassembled(format!(
@@ -317,10 +804,15 @@ impl<'a> Chip8<'a> {
                 }
                 0x6 => {
                     // Vx = Vx >> Vy
-                    next_instruction.set_disassembled(format!(
-                        "SHR V{:01X}",
-                        next_instruction.borrow_operands().x
-                    ));
+                    // Vy n'est affiché que s'il n'est pas V0, pour que l'instruction puisse être
+                    // réassemblée.
+                    let operands = next_instruction.borrow_operands();
+                    let disassembled = match operands.y {
+                        0 => format!("SHR V{:01X}", operands.x),
+                        y => format!("SHR V{:01X}, V{y:01X}", operands.x),
+                    };
+
+                    next_instruction.set_disassembled(disassembled);
                     next_instruction.set_callback(instruction::shr_reg_reg);
                 }
                 0x7 => {
@@ -334,10 +826,13 @@ impl<'a> Chip8<'a> {
                 }
                 0xE => {
                     // Vx = Vx << Vy
-                    next_instruction.set_disassembled(format!(
-                        "SHL V{:01X}",
-                        next_instruction.borrow_operands().x
-                    ));
+                    let operands = next_instruction.borrow_operands();
+                    let disassembled = match operands.y {
+                        0 => format!("SHL V{:01X}", operands.x),
+                        y => format!("SHL V{:01X}, V{y:01X}", operands.x),
+                    };
+
+                    next_instruction.set_disassembled(disassembled);
                     next_instruction.set_callback(instruction::shl_reg_reg);
                 }
                 _ => (),
@@ -359,6 +854,26 @@ impl<'a> Chip8<'a> {
                 ));
                 next_instruction.set_callback(instruction::ld_i_addr);
             }
+            0xB if chip8x => {
+                if next_instruction.borrow_operands().nibble == 0 {
+                    // Colore les zones désignées par Vx et Vx+1 avec la couleur Vy.
+                    next_instruction.set_disassembled(format!(
+                        "COL V{:01X}, V{:01X}",
+                        next_instruction.borrow_operands().x,
+                        next_instruction.borrow_operands().y
+                    ));
+                    next_instruction.set_callback(instruction::col_reg_reg);
+                } else {
+                    // Colore n lignes à partir du pixel (Vx, Vx+1) avec la couleur Vy.
+                    next_instruction.set_disassembled(format!(
+                        "COL V{:01X}, V{:01X}, {}",
+                        next_instruction.borrow_operands().x,
+                        next_instruction.borrow_operands().y,
+                        next_instruction.borrow_operands().nibble
+                    ));
+                    next_instruction.set_callback(instruction::col_reg_reg_nibble);
+                }
+            }
             0xB => {
                 // Saute à l'adresse nnn + V0
                 next_instruction.set_disassembled(format!(
@@ -383,7 +898,13 @@ impl<'a> Chip8<'a> {
                     next_instruction.borrow_operands().y,
                     next_instruction.borrow_operands().nibble
                 ));
-                next_instruction.set_callback(instruction::drw_reg_reg_nibble);
+
+                // Sur SUPER-CHIP, une hauteur de 0 dessine un sprite de 16x16 pixels.
+                if superchip && next_instruction.borrow_operands().nibble == 0 {
+                    next_instruction.set_callback(instruction::drw_reg_reg_large);
+                } else {
+                    next_instruction.set_callback(instruction::drw_reg_reg_nibble);
+                }
             }
             0xE => match instruction & 0x00FF {
                 0x9E => {
@@ -402,9 +923,46 @@ impl<'a> Chip8<'a> {
                     ));
                     next_instruction.set_callback(instruction::sknp_reg);
                 }
+                0xF2 if chip8x => {
+                    // Ignore l'instruction suivante si la touche Vx du second clavier est appuyée.
+                    next_instruction.set_disassembled(format!(
+                        "SKP2 V{:01X}",
+                        next_instruction.borrow_operands().x
+                    ));
+                    next_instruction.set_callback(instruction::skp2_reg);
+                }
+                0xF5 if chip8x => {
+                    // Ignore l'instruction suivante si la touche Vx du second clavier n'est pas
+                    // appuyée.
+                    next_instruction.set_disassembled(format!(
+                        "SKNP2 V{:01X}",
+                        next_instruction.borrow_operands().x
+                    ));
+                    next_instruction.set_callback(instruction::sknp2_reg);
+                }
                 _ => {}
             },
             0xF => match instruction & 0x00FF {
+                0x02 if xo_chip && instruction == 0xF002 => {
+                    // Charge le motif audio depuis la mémoire à partir de l'adresse I.
+                    next_instruction.set_disassembled("LD AUDIO, [I]".to_string());
+                    next_instruction.set_callback(instruction::ld_audio_i);
+                }
+                0x01 if xo_chip => {
+                    // Sélectionne les plans de l'écran.
+                    next_instruction.set_disassembled(format!(
+                        "PLANE {}",
+                        next_instruction.borrow_operands().x
+                    ));
+                    next_instruction.set_callback(instruction::plane_nibble);
+                }
+                0x00 if xo_chip && instruction == 0xF000 => {
+                    // Met la valeur du registre I à l'adresse sur 16 bits qui suit l'instruction.
+                    let address = ram.read16(pc.wrapping_add(2) as u32)?;
+
+                    next_instruction.set_disassembled(format!("LD I, LONG ${address:04X}"));
+                    next_instruction.set_callback(instruction::ld_i_long);
+                }
                 0x07 => {
                     // Vx = DT
                     next_instruction.set_disassembled(format!(
@@ -454,6 +1012,22 @@ impl<'a> Chip8<'a> {
                     ));
                     next_instruction.set_callback(instruction::ld_i_reg);
                 }
+                0x30 if superchip => {
+                    // L'adresse vers le grand caractère Vx est stockée dans le registre I.
+                    next_instruction.set_disassembled(format!(
+                        "LD HF, V{:01X}",
+                        next_instruction.borrow_operands().x
+                    ));
+                    next_instruction.set_callback(instruction::ld_hf_reg);
+                }
+                0x3A if xo_chip => {
+                    // Hauteur du motif audio = Vx
+                    next_instruction.set_disassembled(format!(
+                        "LD PITCH, V{:01X}",
+                        next_instruction.borrow_operands().x
+                    ));
+                    next_instruction.set_callback(instruction::ld_pitch_reg);
+                }
                 0x33 => {
                     // Stock la représentation BCD de Vx dans les adresses à partir de I.
                     next_instruction.set_disassembled(format!(
@@ -478,50 +1052,261 @@ impl<'a> Chip8<'a> {
                     ));
                     next_instruction.set_callback(instruction::ld_reg_from_i);
                 }
+                0x75 if (next_instruction.borrow_operands().x as usize) < platform.rpl_flags_count() => {
+                    // Stock les registres de V0 à Vx dans les drapeaux RPL.
+                    next_instruction.set_disassembled(format!(
+                        "LD R, V{:01X}",
+                        next_instruction.borrow_operands().x
+                    ));
+                    next_instruction.set_callback(instruction::ld_r_reg);
+                }
+                0x85 if (next_instruction.borrow_operands().x as usize) < platform.rpl_flags_count() => {
+                    // Lit les registres de V0 à Vx depuis les drapeaux RPL.
+                    next_instruction.set_disassembled(format!(
+                        "LD V{:01X}, R",
+                        next_instruction.borrow_operands().x
+                    ));
+                    next_instruction.set_callback(instruction::ld_reg_r);
+                }
                 _ => (),
             },
             _ => (),
         }
 
-        self.next_instruction = next_instruction;
+        Ok(next_instruction)
+    }
 
-        Ok(self.next_instruction.get_disassembled())
+    pub fn execute_instruction(&mut self) -> Result<(), Chip8Error> {
+        // DXYN attend la fin de la frame en cours pour dessiner au début de la suivante, comme
+        // l'interpréteur du COSMAC VIP qui attend l'interruption de l'affichage.
+        // L'instruction n'est pas exécutée et PC ne change pas, elle sera donc relue.
+        let display_wait = self.clock.timing() == Timing::CosmacVip
+            || match self.quirks.display_wait {
+                DisplayWait::None => false,
+                DisplayWait::Always => true,
+                DisplayWait::LoresOnly => !self.screen.is_high_resolution(),
+            };
+
+        if display_wait && self.next_instruction.get_instruction() >> 12 == 0xD && !self.clock.is_frame_start() {
+            self.clock.finish_frame();
+            self.end_frame(self.clock.frames() - 1);
+
+            return Ok(());
+        }
+
+        // État dont dépend la durée de l'instruction avec le timing du COSMAC VIP.
+        // Avec la disposition du COSMAC VIP, la zone de la RAM lue avant l'instruction.
+        let vip_area = match self.memory_layout {
+            MemoryLayout::Separate => None,
+            MemoryLayout::CosmacVip => Some(layout::load_vip_state(
+                &self.ram,
+                &mut self.stack,
+                &mut self.registers,
+                &mut self.screen,
+                &mut self.callbacks,
+            )?),
+        };
+
+        let v = self.registers.v;
+        let pc = self.registers.pc;
+
+        let result = self.next_instruction.execute(&mut Context {
+            ram: &mut self.ram,
+            stack: &mut self.stack,
+            registers: &mut self.registers,
+            keys: &self.keys,
+            screen: &mut self.screen,
+            callbacks: &mut self.callbacks,
+            quirks: &self.quirks,
+            platform: self.platform,
+        });
+
+        // Un débordement de la pile arrête la machine, la pile restant dans l'état où elle
+        // était pour pouvoir être inspectée.
+        if let Err(Chip8Error::StackOverflow | Chip8Error::StackUnderflow) = result {
+            self.registers.halted = true;
+        }
+
+        result?;
+
+        if let Some(area) = vip_area {
+            layout::store_vip_state(&mut self.ram, &self.stack, &self.registers, &self.screen, &area)?;
+        }
+
+        let frames = match self.clock.timing() {
+            Timing::Fixed => self.clock.tick() as u32,
+            Timing::CosmacVip => {
+                let instruction = self.next_instruction.get_instruction();
+                let skipped = self.registers.pc == pc.wrapping_add(4);
+
+                self.clock.tick_cycles(timing::vip_cycles(instruction, &v, skipped))
+            }
+        };
+
+        // Une instruction longue peut terminer plusieurs frames, qui se terminent chacune à leur
+        // tour avec leur propre numéro.
+        let end = self.clock.frames();
+        for frame in end - frames as u64..end {
+            self.end_frame(frame);
+        }
+
+        Ok(())
     }
 
-    pub fn execute_instruction(&mut self) {
-        let period = 1.0_f64 / 60.0_f64;
-        let nanos = period * 1_000_000_000.0_f64;
+    /// Termine la frame émulée numéro `frame`.
+    fn end_frame(&mut self, frame: u64) {
+        // Les timers ont une fréquence de 60Hz, ils sont donc décrémentés à chaque fin de frame.
+        self.end_sound_frame(frame);
+        self.registers.tick_timers();
 
-        self.next_instruction.execute(
-            &mut self.ram,
-            &mut self.stack,
-            &mut self.registers,
-            &self.keys,
-            &mut self.screen,
-            &mut self.callbacks,
-        );
+        (self.callbacks.frame_ready)(&mut self.callbacks.callback_data);
+    }
+
+    /// Génère le son de la frame qui vient de se terminer, avant que le Sound Timer ne soit
+    /// décrémenté.
+    fn end_sound_frame(&mut self, frame: u64) {
+        let sound = match &mut self.sound {
+            Some(t) => t,
+            None => return,
+        };
+
+        let voice = if let Some(sample) = &mut self.registers.sample {
+            Voice::Digitized(sample)
+        } else if self.platform.supports_xo_chip() {
+            Voice::Pattern(&mut self.pattern_synth, &self.registers.pattern, self.registers.pitch)
+        } else {
+            Voice::Beeper
+        };
+
+        let event = sound.end_frame(frame, self.registers.st, voice);
+
+        if let Some(kind) = event {
+            (self.callbacks.beep)(&mut self.callbacks.callback_data, kind == SoundEventKind::BeepStart);
+        }
+
+        if matches!(&self.registers.sample, Some(sample) if sample.is_finished()) {
+            self.registers.sample = None;
+        }
+    }
+
+    /// Récupère, décode puis exécute une instruction.
+    /// Si une instruction a déjà été décodée, c'est elle qui est exécutée.
+    fn cycle(&mut self) -> Result<(), Chip8Error> {
+        if self.need_to_fetch {
+            let instruction = self.fetch_next_instruction()?;
+            self.decode_instruction(instruction)?;
+        }
+
+        self.execute_instruction()?;
+        self.need_to_fetch = true;
+
+        Ok(())
+    }
 
-        // Décrémente le Delay Timer s'il a été défini.
-        // Le timer a une fréquence de 60Hz.
-        if self.registers.get_elapsed_time_since_last_dt() >= Duration::from_nanos(nanos as u64) {
-            if self.registers.dt > 0 {
-                self.registers.dt -= 1;
+    /// Exécute `cycles` instructions.
+    pub fn step(&mut self, cycles: u32) -> Result<(), Chip8Error> {
+        for _ in 0..cycles {
+            if self.registers.halted {
+                break;
             }
 
-            self.registers.reset_dt_time();
+            self.cycle()?;
         }
 
-        // Décrémente le Sound Timer s'il a été défini.
-        // Le timer a une fréquence de 60Hz.
-        if self.registers.get_elapsed_time_since_last_st() >= Duration::from_nanos(nanos as u64) {
-            if self.registers.st > 0 {
-                self.registers.st -= 1;
+        Ok(())
+    }
+
+    /// Exécute les instructions jusqu'à la fin de la frame émulée en cours.
+    pub fn run_frame(&mut self) -> Result<(), Chip8Error> {
+        let frame = self.clock.frames();
+
+        while self.clock.frames() == frame && !self.registers.halted {
+            self.cycle()?;
+        }
+
+        Ok(())
+    }
+
+    /// Indique si le programme s'est arrêté avec l'instruction 00FD, ou à cause d'un débordement
+    /// de la pile.
+    pub fn is_halted(&self) -> bool {
+        self.registers.halted
+    }
+
+    /// Empreinte du programme chargé, qui identifie ses drapeaux RPL sauvegardés.
+    pub fn rom_hash(&self) -> u64 {
+        self.rom_hash
+    }
+
+    pub fn borrow_rpl_flags(&self) -> &[u8; RPL_FLAGS_COUNT] {
+        &self.registers.rpl
+    }
+
+    pub fn set_rpl_flags(&mut self, flags: &[u8; RPL_FLAGS_COUNT]) {
+        self.registers.rpl = *flags;
+    }
+
+    /// Remplit `out` avec les échantillons PCM 16 bits mono du son en cours : le son numérisé du
+    /// MEGA-CHIP jusqu'à sa fin, sinon le motif audio du XO-CHIP joué tant que le Sound Timer
+    /// n'est pas nul. L'hôte récupère ainsi le son au rythme qui lui convient, par exemple
+    /// `sample_rate / 60` échantillons après chaque frame.
+    /// Le synthétiseur et le son numérisé sont ceux utilisés par `Sound` : un hôte ne doit
+    /// utiliser que l'un des deux.
+    pub fn render_audio(&mut self, sample_rate: u32, out: &mut [i16]) {
+        if let Some(sample) = &mut self.registers.sample {
+            sample.render(sample_rate, out);
+
+            if sample.is_finished() {
+                self.registers.sample = None;
             }
 
-            self.registers.reset_st_time();
+            return;
         }
 
-        self.execution_instant = Instant::now();
+        self.pattern_synth.render(
+            &self.registers.pattern,
+            self.registers.pitch,
+            self.registers.st > 0,
+            sample_rate,
+            out,
+        );
+    }
+
+    /// Son généré à chaque frame, si le builder l'a activé.
+    pub fn borrow_mut_sound(&mut self) -> Option<&mut Sound> {
+        self.sound.as_mut()
+    }
+
+    pub fn borrow_registers(&self) -> &Registers {
+        &self.registers
+    }
+
+    pub fn borrow_screen(&self) -> &Screen {
+        &self.screen
+    }
+
+    pub fn borrow_platform(&self) -> &Platform {
+        &self.platform
+    }
+
+    pub fn borrow_quirks(&self) -> &Quirks {
+        &self.quirks
+    }
+
+    pub fn set_quirks(&mut self, quirks: Quirks) {
+        self.quirks = quirks;
+    }
+
+    pub fn borrow_clock(&self) -> &Clock {
+        &self.clock
+    }
+
+    pub fn set_instructions_per_frame(&mut self, value: u32) {
+        self.clock.set_instructions_per_frame(value);
+    }
+
+    pub fn set_timing(&mut self, value: Timing) {
+        self.clock.set_timing(value);
     }
 
     pub fn is_paused(&self) -> bool {
@@ -532,10 +1317,10 @@ impl<'a> Chip8<'a> {
         self.paused = value;
     }
 
-    pub fn set_register_value(&mut self, register_number: u8, value: u8) -> Result<(), String> {
+    pub fn set_register_value(&mut self, register_number: u8, value: u8) -> Result<(), Chip8Error> {
         let register = match self.registers.v.get_mut(register_number as usize) {
             Some(t) => t,
-            None => return Err("Invalid register number".to_string()),
+            None => return Err(Chip8Error::InvalidRegister(register_number)),
         };
 
         *register = value;
@@ -543,12 +1328,41 @@ impl<'a> Chip8<'a> {
         Ok(())
     }
 
-    pub fn set_key_pressed(&mut self, index: u8, value: bool) {
+    pub fn set_key_pressed(&mut self, index: u8, value: bool) -> Result<(), Chip8Error> {
         if index > 0xF {
-            return ();
+            return Err(Chip8Error::InvalidKey(index));
         }
 
         self.keys[index as usize] = value;
+
+        Ok(())
+    }
+
+    /// Change l'état d'une touche du second clavier du CHIP-8X.
+    pub fn set_second_key_pressed(&mut self, index: u8, value: bool) -> Result<(), Chip8Error> {
+        if index > 0xF {
+            return Err(Chip8Error::InvalidKey(index));
+        }
+
+        self.keys[KEYPAD_SIZE + index as usize] = value;
+
+        Ok(())
+    }
+
+    /// Appels de fonctions en cours, du plus ancien au plus récent.
+    pub fn call_stack(&self) -> Result<Vec<StackFrame>, Chip8Error> {
+        (0..self.registers.sp / 2)
+            .map(|index| {
+                let call_site = self.stack.read16(index as u32 * 2)?;
+                let target = self.ram.read16(call_site as u32)? & 0x0FFF;
+
+                Ok(StackFrame {
+                    call_site,
+                    return_address: call_site.wrapping_add(2),
+                    target,
+                })
+            })
+            .collect()
     }
 
     pub fn borrow_mut_callbacks(&mut self) -> &mut Chip8Callback<'a> {
@@ -559,3 +1373,29 @@ impl<'a> Chip8<'a> {
         &self.next_instruction
     }
 }
+
+impl Machine for Chip8<'_> {
+    fn run_frame(&mut self) -> Result<(), Chip8Error> {
+        Chip8::run_frame(self)
+    }
+
+    fn set_key_pressed(&mut self, index: u8, value: bool) -> Result<(), Chip8Error> {
+        Chip8::set_key_pressed(self, index, value)
+    }
+
+    fn is_halted(&self) -> bool {
+        Chip8::is_halted(self)
+    }
+
+    fn is_beeping(&self) -> bool {
+        self.registers.st > 0
+    }
+
+    fn display_size(&self) -> (usize, usize) {
+        (self.screen.width(), self.screen.height())
+    }
+
+    fn display_pixel(&self, x: usize, y: usize) -> u8 {
+        self.screen.get(x, y)
+    }
+}
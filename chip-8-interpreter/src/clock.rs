@@ -0,0 +1,131 @@
+use crate::timing::{Timing, VIP_AVAILABLE_CYCLES};
+
+/// Nombre d'instructions exécutées par frame par défaut, soit environ 500Hz.
+pub const DEFAULT_INSTRUCTIONS_PER_FRAME: u32 = 8;
+
+/// Horloge émulée : une frame dure un nombre fixe d'instructions, ou de cycles machine du
+/// COSMAC VIP, les timers sont donc décrémentés exactement une fois toutes les 60 frames par
+/// seconde émulées, sans dépendre du temps réel.
+pub struct Clock {
+    instructions_per_frame: u32,
+    frame_instructions: u32,
+    instructions: u64,
+    frames: u64,
+    timing: Timing,
+    /// Cycles machine déjà écoulés dans la frame en cours avec le timing du COSMAC VIP.
+    frame_cycles: u32,
+}
+
+impl Clock {
+    pub fn new(instructions_per_frame: u32) -> Self {
+        Self {
+            instructions_per_frame: instructions_per_frame.max(1),
+            frame_instructions: 0,
+            instructions: 0,
+            frames: 0,
+            timing: Timing::Fixed,
+            frame_cycles: 0,
+        }
+    }
+
+    /// Avance l'horloge d'une instruction.
+    /// Retourne `true` si cette instruction termine la frame en cours.
+    pub fn tick(&mut self) -> bool {
+        self.instructions += 1;
+        self.frame_instructions += 1;
+
+        if self.frame_instructions >= self.instructions_per_frame {
+            self.frame_instructions = 0;
+            self.frames += 1;
+
+            return true;
+        }
+
+        false
+    }
+
+    /// Avance l'horloge d'une instruction ayant duré `cycles` cycles machine.
+    /// Avec le timing fixe, seul le nombre d'instructions compte.
+    /// Retourne le nombre de frames terminées pendant cette instruction, une instruction longue
+    /// pouvant durer plusieurs frames.
+    pub fn tick_cycles(&mut self, cycles: u32) -> u32 {
+        if self.timing == Timing::Fixed {
+            return self.tick() as u32;
+        }
+
+        self.instructions += 1;
+        self.frame_instructions += 1;
+        self.frame_cycles += cycles;
+
+        let frames = self.frame_cycles / VIP_AVAILABLE_CYCLES;
+
+        if frames > 0 {
+            self.frame_cycles %= VIP_AVAILABLE_CYCLES;
+            self.frame_instructions = 0;
+            self.frames += frames as u64;
+        }
+
+        frames
+    }
+
+    /// Indique si aucune instruction n'a encore été exécutée dans la frame en cours.
+    pub fn is_frame_start(&self) -> bool {
+        self.frame_instructions == 0
+    }
+
+    /// Termine la frame en cours sans exécuter d'instruction.
+    pub fn finish_frame(&mut self) {
+        self.frame_instructions = 0;
+        self.frame_cycles = 0;
+        self.frames += 1;
+    }
+
+    pub fn timing(&self) -> Timing {
+        self.timing
+    }
+
+    pub fn set_timing(&mut self, value: Timing) {
+        self.timing = value;
+        self.frame_cycles = 0;
+    }
+
+    /// Nombre de cycles machine restant avant la fin de la frame en cours avec le timing du
+    /// COSMAC VIP.
+    pub fn remaining_cycles(&self) -> u32 {
+        VIP_AVAILABLE_CYCLES - self.frame_cycles
+    }
+
+    pub fn instructions_per_frame(&self) -> u32 {
+        self.instructions_per_frame
+    }
+
+    pub fn set_instructions_per_frame(&mut self, value: u32) {
+        // Une frame doit contenir au moins une instruction pour que l'horloge avance.
+        self.instructions_per_frame = value.max(1);
+
+        if self.frame_instructions >= self.instructions_per_frame {
+            self.frame_instructions = 0;
+        }
+    }
+
+    /// Nombre total d'instructions exécutées.
+    pub fn instructions(&self) -> u64 {
+        self.instructions
+    }
+
+    /// Nombre total de frames terminées.
+    pub fn frames(&self) -> u64 {
+        self.frames
+    }
+
+    /// Nombre d'instructions restant à exécuter avant la fin de la frame en cours.
+    pub fn remaining_in_frame(&self) -> u32 {
+        self.instructions_per_frame - self.frame_instructions
+    }
+}
+
+impl Default for Clock {
+    fn default() -> Self {
+        Self::new(DEFAULT_INSTRUCTIONS_PER_FRAME)
+    }
+}
@@ -0,0 +1,496 @@
+use std::{collections::BTreeMap, fmt};
+
+use crate::{asm, chip::Chip8, error::Chip8Error, memory::Memory, octo, platform::Platform};
+
+/// Nombre maximal d'octets d'une ligne de données.
+const DATA_BYTES_PER_LINE: usize = 8;
+
+/// Contenu d'une ligne du listing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LineKind {
+    /// Instruction atteignable depuis le point d'entrée du programme.
+    Code,
+    /// Octets qui ne sont jamais exécutés, affichés avec `db`.
+    Data,
+}
+
+/// Origine d'un label, de la moins prioritaire à la plus prioritaire lorsqu'une adresse est
+/// référencée de plusieurs façons.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum LabelKind {
+    /// Cible de ANNN, F000 NNNN ou 01NN NNNN.
+    Data,
+    /// Cible de 1NNN ou BNNN.
+    Jump,
+    /// Cible de 2NNN.
+    Subroutine,
+    /// Première instruction exécutée.
+    Entry,
+}
+
+impl LabelKind {
+    fn name(&self, address: u32) -> String {
+        match self {
+            LabelKind::Data => format!("data_{address:04X}"),
+            LabelKind::Jump => format!("loc_{address:04X}"),
+            LabelKind::Subroutine => format!("sub_{address:04X}"),
+            LabelKind::Entry => "start".to_string(),
+        }
+    }
+}
+
+/// Ligne du listing : une instruction ou quelques octets de données.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Line {
+    pub address: u32,
+    pub bytes: Vec<u8>,
+    /// Label placé devant la ligne lorsqu'une instruction fait référence à son adresse.
+    pub label: Option<String>,
+    pub kind: LineKind,
+    /// Instruction désassemblée, dont l'adresse cible est remplacée par son label.
+    pub text: String,
+    /// La même ligne écrite en Octo, ou ses octets lorsqu'Octo n'a pas d'instruction équivalente.
+    pub octo: String,
+}
+
+/// Désassemblage complet d'un programme, ligne par ligne dans l'ordre des adresses.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Listing {
+    pub lines: Vec<Line>,
+}
+
+/// Instruction trouvée en suivant le flot d'exécution.
+struct Decoded {
+    opcode: u16,
+    size: u32,
+    mnemonic: String,
+    /// Adresse à laquelle fait référence l'instruction.
+    target: Option<u32>,
+}
+
+/// Taille de l'instruction, F000 NNNN sur XO-CHIP et 01NN NNNN sur MEGA-CHIP faisant 4 octets.
+pub(crate) fn instruction_size(opcode: u16, platform: &Platform) -> u32 {
+    if (platform.supports_xo_chip() && opcode == 0xF000) || (platform.supports_megachip() && opcode & 0xFF00 == 0x0100) {
+        4
+    } else {
+        2
+    }
+}
+
+/// Adresses des instructions qui peuvent être exécutées après `decoded`, et label de sa cible.
+fn successors(decoded: &Decoded, address: u32, ram: &Memory, platform: &Platform) -> (Vec<u32>, Option<LabelKind>) {
+    let opcode = decoded.opcode;
+    let next = address + decoded.size;
+    let nnn = (opcode & 0x0FFF) as u32;
+
+    match opcode >> 12 {
+        0x0 => match opcode {
+            0x00EE => (vec![], None),
+            0x00FD if platform.supports_superchip() => (vec![], None),
+            0x0100..=0x01FF if platform.supports_megachip() => (vec![next], Some(LabelKind::Data)),
+            _ => (vec![next], None),
+        },
+        0x1 => (vec![nnn], Some(LabelKind::Jump)),
+        0x2 => (vec![nnn, next], Some(LabelKind::Subroutine)),
+        0x3 | 0x4 | 0x9 => (vec![next, next + skip_size(ram, next, platform)], None),
+        // 5XY1 (CHIP-8X), 5XY2 et 5XY3 (XO-CHIP) ne sont pas des sauts conditionnels.
+        0x5 if opcode & 0x000F == 0x0 => (vec![next, next + skip_size(ram, next, platform)], None),
+        0xA => (vec![next], Some(LabelKind::Data)),
+        // Seule l'adresse de la table de sauts est connue, le décalage dépend de V0.
+        0xB if !platform.supports_chip8x() => (vec![nnn], Some(LabelKind::Jump)),
+        0xE => match opcode & 0x00FF {
+            0x9E | 0xA1 | 0xF2 | 0xF5 => (vec![next, next + skip_size(ram, next, platform)], None),
+            _ => (vec![next], None),
+        },
+        0xF if opcode == 0xF000 => (vec![next], Some(LabelKind::Data)),
+        _ => (vec![next], None),
+    }
+}
+
+/// Taille de l'instruction ignorée par un saut conditionnel, comme le fait l'interpréteur.
+pub(crate) fn skip_size(ram: &Memory, address: u32, platform: &Platform) -> u32 {
+    if platform.supports_xo_chip() && matches!(ram.read16(address), Ok(0xF000)) {
+        4
+    } else {
+        2
+    }
+}
+
+/// Adresse à laquelle fait référence l'instruction, lue dans son texte désassemblé.
+fn target_address(mnemonic: &str) -> Option<u32> {
+    let (_, address) = mnemonic.rsplit_once('$')?;
+
+    u32::from_str_radix(address, 16).ok()
+}
+
+fn octo_register(text: &str) -> Option<String> {
+    let digit = text.strip_prefix('V')?;
+
+    if digit.len() == 1 {
+        Some(format!("v{}", digit.to_ascii_lowercase()))
+    } else {
+        None
+    }
+}
+
+/// Octet écrit en hexadécimal sans préfixe dans le désassemblage.
+fn octo_byte(text: &str) -> Option<String> {
+    Some(format!("0x{:02X}", u8::from_str_radix(text, 16).ok()?))
+}
+
+fn octo_address(text: &str) -> Option<String> {
+    Some(format!("0x{:03X}", u32::from_str_radix(text.strip_prefix('$')?, 16).ok()?))
+}
+
+/// Traduit une instruction désassemblée en Octo, les adresses restant numériques.
+/// Retourne `None` pour les instructions qu'Octo ne sait pas écrire, comme celles du CHIP-8X
+/// et du MEGA-CHIP.
+fn octo_statement(mnemonic: &str) -> Option<String> {
+    let (name, operands) = mnemonic.split_once(' ').unwrap_or((mnemonic, ""));
+    let operands: Vec<&str> = operands.split(", ").filter(|operand| !operand.is_empty()).collect();
+
+    let register = |index: usize| octo_register(operands[index]);
+    // Registre ou octet, pour les comparaisons.
+    let operand = |index: usize| octo_register(operands[index]).or_else(|| octo_byte(operands[index]));
+    let range = |index: usize| {
+        let (x, y) = operands[index].split_once('-')?;
+        Some(format!("{} - {}", octo_register(x)?, octo_register(y)?))
+    };
+
+    // Les sauts conditionnels ignorent l'instruction suivante, qu'Octo exécute lorsque la
+    // condition de `if ... then` est vraie : la condition est donc inversée.
+    let statement = match (name, operands.len()) {
+        ("CLS", 0) => "clear".to_string(),
+        ("RET", 0) => "return".to_string(),
+        ("SCD", 1) => format!("scroll-down {}", operands[0]),
+        ("SCU", 1) => format!("scroll-up {}", operands[0]),
+        ("SCR", 0) => "scroll-right".to_string(),
+        ("SCL", 0) => "scroll-left".to_string(),
+        ("EXIT", 0) => "exit".to_string(),
+        ("LOW", 0) => "lores".to_string(),
+        ("HIGH", 0) => "hires".to_string(),
+        ("SYS", 1) => format!("native {}", octo_address(operands[0])?),
+        ("JP", 1) => format!("jump {}", octo_address(operands[0])?),
+        ("JP", 2) if operands[0] == "V0" => format!("jump0 {}", octo_address(operands[1])?),
+        ("CALL", 1) => format!(":call {}", octo_address(operands[0])?),
+        ("SE", 2) => format!("if {} != {} then", register(0)?, operand(1)?),
+        ("SNE", 2) => format!("if {} == {} then", register(0)?, operand(1)?),
+        ("SKP", 1) => format!("if {} -key then", register(0)?),
+        ("SKNP", 1) => format!("if {} key then", register(0)?),
+        ("LD", 2) => match (operands[0], operands[1]) {
+            ("I", address) if address.starts_with('$') => format!("i := {}", octo_address(address)?),
+            ("I", long) if long.starts_with("LONG $") => format!("i := long 0x{}", &long[6..]),
+            ("I", _) => format!("i := hex {}", register(1)?),
+            ("HF", _) => format!("i := bighex {}", register(1)?),
+            ("B", _) => format!("bcd {}", register(1)?),
+            ("DT", _) => format!("delay := {}", register(1)?),
+            ("ST", _) => format!("buzzer := {}", register(1)?),
+            ("PITCH", _) => format!("pitch := {}", register(1)?),
+            ("R", _) => format!("saveflags {}", register(1)?),
+            ("AUDIO", "[I]") => "audio".to_string(),
+            ("[I]", _) => format!("save {}", register(1).or_else(|| range(1))?),
+            (_, "[I]") => format!("load {}", register(0).or_else(|| range(0))?),
+            (_, "DT") => format!("{} := delay", register(0)?),
+            (_, "K") => format!("{} := key", register(0)?),
+            (_, "R") => format!("loadflags {}", register(0)?),
+            _ => format!("{} := {}", register(0)?, operand(1)?),
+        },
+        ("ADD", 2) if operands[0] == "I" => format!("i += {}", register(1)?),
+        ("ADD", 2) => format!("{} += {}", register(0)?, operand(1)?),
+        ("OR" | "AND" | "XOR" | "SUB" | "SUBN" | "SHR" | "SHL", 1..=2) => {
+            let operator = match name {
+                "OR" => "|=",
+                "AND" => "&=",
+                "XOR" => "^=",
+                "SUB" => "-=",
+                "SUBN" => "=-",
+                "SHR" => ">>=",
+                _ => "<<=",
+            };
+
+            // SHR Vx et SHL Vx correspondent à Vy = V0.
+            let y = if operands.len() == 2 { register(1)? } else { "v0".to_string() };
+
+            format!("{} {operator} {y}", register(0)?)
+        }
+        ("RND", 2) => format!("{} := random {}", register(0)?, octo_byte(operands[1])?),
+        ("DRW", 3) => format!("sprite {} {} {}", register(0)?, register(1)?, operands[2]),
+        ("PLANE", 1) => format!("plane {}", operands[0]),
+        _ => return None,
+    };
+
+    Some(statement)
+}
+
+/// Octets écrits tels quels en Octo.
+fn octo_bytes(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("0x{byte:02X}")).collect::<Vec<_>>().join(" ")
+}
+
+/// Désassemble le programme `rom` chargé à `load_address`.
+/// Les instructions sont trouvées en suivant tous les chemins d'exécution possibles depuis le
+/// point d'entrée de la plateforme, le reste du programme est considéré comme des données.
+/// Les sauts calculés par BNNN ne sont suivis que jusqu'au début de leur table.
+pub fn disassemble(rom: &[u8], platform: Platform, load_address: u16) -> Result<Listing, Chip8Error> {
+    let platform = platform.detect(rom, load_address);
+    let start = load_address as u32;
+    let end = start + rom.len() as u32;
+
+    let mut ram = Memory::new(end as usize);
+    ram.load(start, rom)?;
+
+    let mut code: BTreeMap<u32, Decoded> = BTreeMap::new();
+    let mut labels: BTreeMap<u32, LabelKind> = BTreeMap::new();
+    let mut occupied = vec![false; rom.len()];
+
+    let entry = platform.start_address(load_address) as u32;
+    labels.insert(entry, LabelKind::Entry);
+
+    let mut pending = vec![entry];
+
+    while let Some(address) = pending.pop() {
+        // Le registre PC est sur 16 bits, le code ne peut donc pas dépasser 64 Kio.
+        if address < start || address > u16::MAX as u32 || code.contains_key(&address) {
+            continue;
+        }
+
+        let opcode = match ram.read16(address) {
+            Ok(t) => t,
+            Err(_) => continue,
+        };
+
+        let size = instruction_size(opcode, &platform);
+        let range = (address - start) as usize..(address - start + size) as usize;
+
+        // Une instruction qui déborde du programme ou chevauche une autre n'est pas retenue.
+        if range.end > rom.len() || occupied[range.clone()].iter().any(|&byte| byte) {
+            continue;
+        }
+
+        let mnemonic = match Chip8::decode(opcode, address as u16, &ram, &platform) {
+            Ok(instruction) if !instruction.get_mnemonic().is_empty() => instruction.get_mnemonic().to_string(),
+            _ => continue,
+        };
+
+        occupied[range].fill(true);
+
+        let mut decoded = Decoded {
+            opcode,
+            size,
+            mnemonic,
+            target: None,
+        };
+
+        let (next, label) = successors(&decoded, address, &ram, &platform);
+
+        if let Some(label) = label {
+            decoded.target = target_address(&decoded.mnemonic);
+
+            if let Some(target) = decoded.target {
+                let kind = labels.entry(target).or_insert(label);
+                *kind = (*kind).max(label);
+            }
+        }
+
+        pending.extend(next);
+        code.insert(address, decoded);
+    }
+
+    // Les instructions et les données, sans leurs labels.
+    let mut lines = Vec::new();
+    let mut address = start;
+
+    while address < end {
+        if let Some(decoded) = code.get_mut(&address) {
+            let offset = (address - start) as usize;
+            let bytes = rom[offset..offset + decoded.size as usize].to_vec();
+
+            // Une instruction dont le texte ne se réassemble pas en les mêmes octets, comme 0230
+            // du CHIP-8 hires affichée comme 00E0, est écrite avec `dw`.
+            let text = if asm::assemble(&decoded.mnemonic, platform, 0).ok().as_deref() == Some(&bytes[..]) {
+                decoded.mnemonic.clone()
+            } else {
+                decoded.target = None;
+
+                let words = bytes
+                    .chunks(2)
+                    .map(|word| format!("${}", hex_bytes(word)))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                format!("dw {words}")
+            };
+
+            // De même, l'instruction Octo doit redonner les mêmes octets.
+            let octo = match octo_statement(&decoded.mnemonic) {
+                Some(statement) if octo::compile(&format!(": main {statement}"), platform, address as u16).ok().as_deref() == Some(&bytes[..]) => statement,
+                _ => octo_bytes(&bytes),
+            };
+
+            lines.push(Line {
+                address,
+                bytes,
+                label: None,
+                kind: LineKind::Code,
+                text,
+                octo,
+            });
+
+            address += decoded.size;
+            continue;
+        }
+
+        // Les données s'arrêtent avant la prochaine instruction ou le prochain label.
+        let mut data_end = address + 1;
+        while data_end < end
+            && data_end - address < DATA_BYTES_PER_LINE as u32
+            && !code.contains_key(&data_end)
+            && !labels.contains_key(&data_end)
+        {
+            data_end += 1;
+        }
+
+        let bytes = rom[(address - start) as usize..(data_end - start) as usize].to_vec();
+        let text = bytes
+            .iter()
+            .map(|byte| format!("${byte:02X}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        lines.push(Line {
+            address,
+            octo: octo_bytes(&bytes),
+            bytes,
+            label: None,
+            kind: LineKind::Data,
+            text: format!("db {text}"),
+        });
+
+        address = data_end;
+    }
+
+    // Seules les adresses qui commencent une ligne reçoivent un label.
+    let names: BTreeMap<u32, String> = lines
+        .iter()
+        .filter_map(|line| Some((line.address, labels.get(&line.address)?.name(line.address))))
+        .collect();
+
+    for line in lines.iter_mut() {
+        line.label = names.get(&line.address).cloned();
+
+        let target = code.get(&line.address).and_then(|decoded| decoded.target);
+
+        if let Some(name) = target.and_then(|target| names.get(&target)) {
+            if let Some((operands, _)) = line.text.rsplit_once('$') {
+                line.text = format!("{operands}{name}");
+            }
+
+            // L'adresse est le dernier mot de l'instruction Octo.
+            if line.kind == LineKind::Code && !line.octo.starts_with("0x") {
+                if let Some((statement, _)) = line.octo.rsplit_once(' ') {
+                    line.octo = format!("{statement} {name}");
+                }
+            }
+        }
+    }
+
+    Ok(Listing { lines })
+}
+
+/// Représente `value` sous forme de chaîne JSON.
+fn json_string(value: &str) -> String {
+    let mut json = String::with_capacity(value.len() + 2);
+    json.push('"');
+
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+
+    json.push('"');
+    json
+}
+
+fn hex_bytes(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02X}")).collect()
+}
+
+impl Listing {
+    /// Retourne le listing sous forme de code source, sans les adresses ni les octets.
+    /// Assemblé par `asm::assemble` à la même adresse, il redonne exactement le programme.
+    pub fn to_source(&self) -> String {
+        let mut source = String::new();
+
+        for line in &self.lines {
+            if let Some(label) = &line.label {
+                source.push_str(&format!("{label}:\n"));
+            }
+
+            source.push_str(&format!("    {}\n", line.text));
+        }
+
+        source
+    }
+
+    /// Retourne le listing sous forme de code source Octo. Le label `main` est placé sur la
+    /// première ligne pour qu'Octo n'ajoute pas de saut au début du programme : compilé par
+    /// `octo::compile` à la même adresse, il redonne exactement le programme.
+    pub fn to_octo(&self) -> String {
+        let mut source = String::from(": main\n");
+
+        for line in &self.lines {
+            if let Some(label) = &line.label {
+                source.push_str(&format!(": {label}\n"));
+            }
+
+            source.push_str(&format!("    {}\n", line.octo));
+        }
+
+        source
+    }
+
+    /// Retourne le listing au format JSON, une ligne par objet.
+    pub fn to_json(&self) -> String {
+        let lines = self
+            .lines
+            .iter()
+            .map(|line| {
+                let label = line.label.as_deref().map_or("null".to_string(), json_string);
+                let kind = match line.kind {
+                    LineKind::Code => "code",
+                    LineKind::Data => "data",
+                };
+
+                format!(
+                    "    {{\"address\": {}, \"bytes\": \"{}\", \"label\": {label}, \"kind\": \"{kind}\", \"text\": {}}}",
+                    line.address,
+                    hex_bytes(&line.bytes),
+                    json_string(&line.text)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",\n");
+
+        format!("{{\n  \"lines\": [\n{lines}\n  ]\n}}\n")
+    }
+}
+
+impl fmt::Display for Listing {
+    /// Affiche le listing sous forme de texte : l'adresse, les octets puis l'instruction, chaque
+    /// label étant sur sa propre ligne.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in &self.lines {
+            if let Some(label) = &line.label {
+                writeln!(f, "{label}:")?;
+            }
+
+            writeln!(f, "    {:04X}  {:<16}  {}", line.address, hex_bytes(&line.bytes), line.text)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+use std::{error::Error, fmt, io};
+
+#[derive(Debug)]
+pub enum Chip8Error {
+    /// Accès à une adresse en dehors de la mémoire.
+    MemoryOutOfBounds { address: usize, size: usize },
+    /// Plage d'adresses invalide lors d'une écriture.
+    InvalidRange { start: usize, end: usize },
+    /// Appel d'une fonction alors que la pile est pleine.
+    StackOverflow,
+    /// Retour d'une fonction alors que la pile est vide.
+    StackUnderflow,
+    /// Numéro de touche en dehors du clavier hexadécimal.
+    InvalidKey(u8),
+    /// Numéro de registre en dehors de V0 à VF.
+    InvalidRegister(u8),
+    /// Instruction non reconnue par l'interpréteur.
+    UnknownInstruction(u16),
+    /// Le programme ne rentre pas dans la mémoire.
+    RomTooLarge { size: usize, max: usize },
+    /// Le fichier de police ne contient pas exactement 16 caractères.
+    InvalidFont { size: usize, expected: usize },
+    /// Une image ROM nécessaire à la machine est vide.
+    EmptyRom,
+    /// Erreur dans le code source d'un programme, à la ligne et à la colonne indiquées.
+    Assembly { line: usize, column: usize, message: String },
+    /// Erreur de l'hôte lors du chargement d'un fichier.
+    Io(io::Error),
+}
+
+impl fmt::Display for Chip8Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Chip8Error::MemoryOutOfBounds { address, size } => {
+                write!(f, "trying to access address {address:#06X} of a {size} length memory")
+            }
+            Chip8Error::InvalidRange { start, end } => {
+                write!(f, "invalid memory range {start:#06X}..{end:#06X}")
+            }
+            Chip8Error::StackOverflow => write!(f, "stack overflow"),
+            Chip8Error::StackUnderflow => write!(f, "stack underflow"),
+            Chip8Error::InvalidKey(key) => write!(f, "invalid key index {key:#04X}"),
+            Chip8Error::InvalidRegister(register) => {
+                write!(f, "invalid register number {register:#04X}")
+            }
+            Chip8Error::UnknownInstruction(instruction) => {
+                write!(f, "unknown instruction {instruction:04X}")
+            }
+            Chip8Error::RomTooLarge { size, max } => {
+                write!(f, "program is {size} bytes long but only {max} bytes are available")
+            }
+            Chip8Error::InvalidFont { size, expected } => {
+                write!(f, "font is {size} bytes long but {expected} bytes were expected")
+            }
+            Chip8Error::EmptyRom => write!(f, "ROM image is empty"),
+            Chip8Error::Assembly { line, column, message } => {
+                write!(f, "line {line}, column {column}: {message}")
+            }
+            Chip8Error::Io(err) => write!(f, "I/O error: {err}"),
+        }
+    }
+}
+
+impl Error for Chip8Error {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Chip8Error::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Chip8Error {
+    fn from(err: io::Error) -> Self {
+        Chip8Error::Io(err)
+    }
+}
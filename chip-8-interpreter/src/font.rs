@@ -0,0 +1,144 @@
+use std::{fs, path::Path};
+
+use crate::{chip::HEX_SPRITES, error::Chip8Error};
+
+/// Hauteur en octets d'un caractère de la police utilisée par FX29.
+pub const FONT_GLYPH_HEIGHT: usize = 5;
+
+/// Hauteur en octets d'un caractère de la grande police du SUPER-CHIP utilisée par FX30.
+pub const BIG_FONT_GLYPH_HEIGHT: usize = 10;
+
+/// Adresse à laquelle est copiée la police par défaut.
+pub const DEFAULT_FONT_ADDRESS: u16 = 0x0;
+
+/// Sprites des nombres hexadécimaux de l'interpréteur du COSMAC VIP, stockés dans sa ROM.
+pub const COSMAC_VIP_HEX_SPRITES: [u8; 80] = [
+    0xF0, 0x90, 0x90, 0x90, 0xF0, // 0
+    0x60, 0x20, 0x20, 0x20, 0x70, // 1
+    0xF0, 0x10, 0xF0, 0x80, 0xF0, // 2
+    0xF0, 0x10, 0xF0, 0x10, 0xF0, // 3
+    0xA0, 0xA0, 0xF0, 0x20, 0x20, // 4
+    0xF0, 0x80, 0xF0, 0x10, 0xF0, // 5
+    0xF0, 0x80, 0xF0, 0x90, 0xF0, // 6
+    0xF0, 0x10, 0x10, 0x10, 0x10, // 7
+    0xF0, 0x90, 0xF0, 0x90, 0xF0, // 8
+    0xF0, 0x90, 0xF0, 0x10, 0xF0, // 9
+    0xF0, 0x90, 0xF0, 0x90, 0x90, // A
+    0xF0, 0x50, 0x70, 0x50, 0xF0, // B
+    0xF0, 0x80, 0x80, 0x80, 0xF0, // C
+    0xF0, 0x50, 0x50, 0x50, 0xF0, // D
+    0xF0, 0x80, 0xF0, 0x80, 0xF0, // E
+    0xF0, 0x80, 0xF0, 0x80, 0x80, // F
+];
+
+/// Sprites des nombres hexadécimaux de CHIPOS, le système du DREAM 6800, larges de 3 pixels.
+pub const DREAM_6800_HEX_SPRITES: [u8; 80] = [
+    0xE0, 0xA0, 0xA0, 0xA0, 0xE0, // 0
+    0x40, 0xC0, 0x40, 0x40, 0xE0, // 1
+    0xE0, 0x20, 0xE0, 0x80, 0xE0, // 2
+    0xE0, 0x20, 0xE0, 0x20, 0xE0, // 3
+    0xA0, 0xA0, 0xE0, 0x20, 0x20, // 4
+    0xE0, 0x80, 0xE0, 0x20, 0xE0, // 5
+    0xE0, 0x80, 0xE0, 0xA0, 0xE0, // 6
+    0xE0, 0x20, 0x20, 0x20, 0x20, // 7
+    0xE0, 0xA0, 0xE0, 0xA0, 0xE0, // 8
+    0xE0, 0xA0, 0xE0, 0x20, 0xE0, // 9
+    0xE0, 0xA0, 0xE0, 0xA0, 0xA0, // A
+    0xC0, 0xA0, 0xE0, 0xA0, 0xC0, // B
+    0xE0, 0x80, 0x80, 0x80, 0xE0, // C
+    0xC0, 0xA0, 0xA0, 0xA0, 0xC0, // D
+    0xE0, 0x80, 0xE0, 0x80, 0xE0, // E
+    0xE0, 0x80, 0xE0, 0x80, 0x80, // F
+];
+
+/// Sprites des nombres hexadécimaux de l'ETI-660, larges de 3 pixels.
+pub const ETI_660_HEX_SPRITES: [u8; 80] = [
+    0xE0, 0xA0, 0xA0, 0xA0, 0xE0, // 0
+    0x20, 0x20, 0x20, 0x20, 0x20, // 1
+    0xE0, 0x20, 0xE0, 0x80, 0xE0, // 2
+    0xE0, 0x20, 0xE0, 0x20, 0xE0, // 3
+    0xA0, 0xA0, 0xE0, 0x20, 0x20, // 4
+    0xE0, 0x80, 0xE0, 0x20, 0xE0, // 5
+    0xE0, 0x80, 0xE0, 0xA0, 0xE0, // 6
+    0xE0, 0x20, 0x20, 0x20, 0x20, // 7
+    0xE0, 0xA0, 0xE0, 0xA0, 0xE0, // 8
+    0xE0, 0xA0, 0xE0, 0x20, 0xE0, // 9
+    0xE0, 0xA0, 0xE0, 0xA0, 0xA0, // A
+    0x80, 0x80, 0xE0, 0xA0, 0xE0, // B
+    0xE0, 0x80, 0x80, 0x80, 0xE0, // C
+    0x20, 0x20, 0xE0, 0xA0, 0xE0, // D
+    0xE0, 0x80, 0xE0, 0x80, 0xE0, // E
+    0xE0, 0x80, 0xC0, 0x80, 0x80, // F
+];
+
+/// Sprites des nombres hexadécimaux de FISH 'N' CHIPS, aux formes arrondies.
+pub const FISH_N_CHIPS_HEX_SPRITES: [u8; 80] = [
+    0x60, 0xA0, 0xA0, 0xA0, 0xC0, // 0
+    0x40, 0xC0, 0x40, 0x40, 0xE0, // 1
+    0xC0, 0x20, 0x40, 0x80, 0xE0, // 2
+    0xC0, 0x20, 0x40, 0x20, 0xC0, // 3
+    0x20, 0xA0, 0xE0, 0x20, 0x20, // 4
+    0xE0, 0x80, 0xC0, 0x20, 0xC0, // 5
+    0x40, 0x80, 0xC0, 0xA0, 0x40, // 6
+    0xE0, 0x20, 0x60, 0x40, 0x40, // 7
+    0x40, 0xA0, 0x40, 0xA0, 0x40, // 8
+    0x40, 0xA0, 0x60, 0x20, 0x40, // 9
+    0x40, 0xA0, 0xE0, 0xA0, 0xA0, // A
+    0xC0, 0xA0, 0xC0, 0xA0, 0xC0, // B
+    0x60, 0x80, 0x80, 0x80, 0x60, // C
+    0xC0, 0xA0, 0xA0, 0xA0, 0xC0, // D
+    0xE0, 0x80, 0xC0, 0x80, 0xE0, // E
+    0xE0, 0x80, 0xC0, 0x80, 0x80, // F
+];
+
+/// Police intégrée utilisée par FX29, qui diffère selon les interpréteurs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Font {
+    /// Police historique de cet interpréteur.
+    #[default]
+    Default,
+    CosmacVip,
+    Dream6800,
+    Eti660,
+    FishNChips,
+}
+
+impl Font {
+    pub fn sprites(&self) -> &'static [u8; 80] {
+        match self {
+            Font::Default => &HEX_SPRITES,
+            Font::CosmacVip => &COSMAC_VIP_HEX_SPRITES,
+            Font::Dream6800 => &DREAM_6800_HEX_SPRITES,
+            Font::Eti660 => &ETI_660_HEX_SPRITES,
+            Font::FishNChips => &FISH_N_CHIPS_HEX_SPRITES,
+        }
+    }
+
+    /// Retourne la police correspondant au nom donné en ligne de commande.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "default" => Some(Font::Default),
+            "vip" => Some(Font::CosmacVip),
+            "dream6800" => Some(Font::Dream6800),
+            "eti660" => Some(Font::Eti660),
+            "fish" => Some(Font::FishNChips),
+            _ => None,
+        }
+    }
+}
+
+/// Lit une police dans un fichier contenant les sprites des 16 caractères hexadécimaux les uns
+/// après les autres, chacun haut de `glyph_height` octets.
+pub fn load_font(path: &Path, glyph_height: usize) -> Result<Vec<u8>, Chip8Error> {
+    let font = fs::read(path)?;
+    let expected = glyph_height * 0x10;
+
+    if font.len() != expected {
+        return Err(Chip8Error::InvalidFont {
+            size: font.len(),
+            expected,
+        });
+    }
+
+    Ok(font)
+}
@@ -1,6 +1,17 @@
 use rand::Rng;
 
-use crate::{chip::Chip8Callback, memory::Memory, register::Registers};
+use crate::{
+    audio::DigitizedSound,
+    chip::{Chip8Callback, KEYPAD_SIZE},
+    error::Chip8Error,
+    font::{BIG_FONT_GLYPH_HEIGHT, FONT_GLYPH_HEIGHT},
+    megachip::BlendMode,
+    memory::Memory,
+    platform::Platform,
+    quirks::{MemoryIncrement, Quirks},
+    register::Registers,
+    screen::{Screen, COLOR_ZONE_HEIGHT, COLOR_ZONE_WIDTH, PLANES_COUNT},
+};
 
 pub struct Operands {
     pub nnn: u16,
@@ -10,12 +21,29 @@ pub struct Operands {
     pub kk: u8,
 }
 
+/// État de la machine sur lequel travaillent les instructions.
+pub struct Context<'c, 'a> {
+    pub ram: &'c mut Memory,
+    pub stack: &'c mut Memory,
+    pub registers: &'c mut Registers,
+    pub keys: &'c [bool],
+    pub screen: &'c mut Screen,
+    pub callbacks: &'c mut Chip8Callback<'a>,
+    pub quirks: &'c Quirks,
+    pub platform: Platform,
+}
+
+/// Fonction qui exécute une instruction à partir de son opcode et de ses opérandes.
+pub type Exec<'a> = Box<dyn FnMut(u16, &Operands, &mut Context<'_, 'a>) -> Result<(), Chip8Error> + 'a>;
+
 pub struct Instruction<'a> {
     disassembled: String,
+    /// Instruction désassemblée sans son adresse, vide si elle est inconnue.
+    mnemonic: String,
     instruction: u16,
     pc: u16,
     operands: Operands,
-    exec: Box<dyn FnMut(u16, &Operands, &mut Memory, &mut Memory, &mut Registers, &[bool], &mut [u8], &mut Chip8Callback<'a>) + 'a>,
+    exec: Exec<'a>,
 }
 
 impl<'a> Instruction<'a> {
@@ -32,6 +60,7 @@ impl<'a> Instruction<'a> {
         let kk = (instruction & 0x00FF) as u8;
 
         Self {
+            mnemonic: disassembled.clone(),
             disassembled,
             instruction,
             pc,
@@ -40,19 +69,28 @@ impl<'a> Instruction<'a> {
         }
     }
 
-    pub fn execute(&mut self, ram: &mut Memory, stack: &mut Memory, reg: &mut Registers, keys: &[bool], screen: &mut [u8], callback: &mut Chip8Callback<'a>) {
-        (self.exec)(self.instruction, &self.operands, ram, stack, reg, keys, screen, callback);
+    pub fn execute(&mut self, context: &mut Context<'_, 'a>) -> Result<(), Chip8Error> {
+        (self.exec)(self.instruction, &self.operands, context)
     }
 
     pub fn set_disassembled(&mut self, value: String) {
         self.disassembled = format!("{:04X} - {value}", self.pc);
+        self.mnemonic = value;
     }
 
     pub fn get_disassembled(&self) -> &str {
         &self.disassembled
     }
 
-    pub fn set_callback(&mut self, exec: impl FnMut(u16, &Operands, &mut Memory, &mut Memory, &mut Registers, &[bool], &mut [u8], &mut Chip8Callback<'a>) + 'a) {
+    pub fn get_mnemonic(&self) -> &str {
+        &self.mnemonic
+    }
+
+    pub fn get_instruction(&self) -> u16 {
+        self.instruction
+    }
+
+    pub fn set_callback(&mut self, exec: impl FnMut(u16, &Operands, &mut Context<'_, 'a>) -> Result<(), Chip8Error> + 'a) {
         self.exec = Box::new(exec);
     }
 
@@ -61,344 +99,974 @@ impl<'a> Instruction<'a> {
     }
 }
 
-fn unknown_instruction(instruction: u16, _: &Operands, _: &mut Memory, _: &mut Memory, _: &mut Registers, _: &[bool], _: &mut [u8], _: &mut Chip8Callback) {
-    eprintln!("[CHIP-8] Unknown instruction: {instruction}");
+fn unknown_instruction(instruction: u16, _: &Operands, _: &mut Context) -> Result<(), Chip8Error> {
+    Err(Chip8Error::UnknownInstruction(instruction))
+}
+
+pub(crate) fn clean_screen(_: u16, _: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    // En mode MEGA-CHIP, l'image dessinée depuis le dernier 00E0 est affichée puis effacée.
+    if context.screen.mega().is_enabled() {
+        context.screen.mega_mut().present();
+        (context.callbacks.mega_frame)(&mut context.callbacks.callback_data, context.screen.mega().frame(), context.screen.width() as u16, context.screen.height() as u16);
+
+        context.registers.advance(2);
+
+        return Ok(());
+    }
+
+    context.screen.clear();
+
+    // Sur XO-CHIP, les plans qui ne sont pas sélectionnés restent affichés.
+    (context.callbacks.clear_pixel)(&mut context.callbacks.callback_data);
+    refresh_lit_pixels(context.screen, context.callbacks);
+
+    context.registers.advance(2);
+
+    Ok(())
+}
+
+pub(crate) fn ret(_: u16, _: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    // La pile est vide, il n'y a aucune fonction depuis laquelle retourner.
+    if context.registers.sp < 2 {
+        return Err(Chip8Error::StackUnderflow);
+    }
+
+    context.registers.sp -= 2;
+    context.registers.pc = context.stack.read16(context.registers.sp as u32)?;
+    context.registers.advance(2);
+
+    Ok(())
+}
+
+/// Renvoie l'état d'un pixel aux callbacks.
+pub(crate) fn update_pixel(screen: &Screen, callbacks: &mut Chip8Callback, x: usize, y: usize) {
+    let color = screen.get(x, y);
+
+    if color != 0 {
+        (callbacks.set_pixel)(&mut callbacks.callback_data, x as u8, y as u8);
+    } else {
+        (callbacks.unset_pixel)(&mut callbacks.callback_data, x as u8, y as u8);
+    }
+
+    (callbacks.pixel_color)(&mut callbacks.callback_data, x as u8, y as u8, color);
+}
+
+/// Renvoie l'état de chaque pixel aux callbacks après une modification de tout l'écran.
+/// En mode MEGA-CHIP, l'écran n'est transmis qu'à chaque 00E0.
+fn refresh_screen(screen: &Screen, callbacks: &mut Chip8Callback) {
+    if screen.mega().is_enabled() {
+        return;
+    }
+
+    for y in 0..screen.height() {
+        for x in 0..screen.width() {
+            update_pixel(screen, callbacks, x, y);
+        }
+    }
+}
+
+/// Renvoie uniquement les pixels allumés aux callbacks, après que l'écran ait été effacé.
+fn refresh_lit_pixels(screen: &Screen, callbacks: &mut Chip8Callback) {
+    for y in 0..screen.height() {
+        for x in 0..screen.width() {
+            if screen.get(x, y) != 0 {
+                update_pixel(screen, callbacks, x, y);
+            }
+        }
+    }
+}
+
+/// Distance de défilement sur l'écran courant, divisée par deux en basse résolution avec le
+/// quirk `lores_half_scroll`.
+fn scroll_distance(n: usize, context: &Context) -> usize {
+    if context.quirks.lores_half_scroll && !context.screen.is_high_resolution() {
+        n / 2
+    } else {
+        n
+    }
+}
+
+pub(crate) fn scd_nibble(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    context.screen.scroll_down(scroll_distance(operands.nibble as usize, context));
+    refresh_screen(context.screen, context.callbacks);
+
+    context.registers.advance(2);
+
+    Ok(())
+}
+
+pub(crate) fn scu_nibble(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    context.screen.scroll_up(scroll_distance(operands.nibble as usize, context));
+    refresh_screen(context.screen, context.callbacks);
+
+    context.registers.advance(2);
+
+    Ok(())
+}
+
+pub(crate) fn scr(_: u16, _: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    context.screen.scroll_right(scroll_distance(4, context));
+    refresh_screen(context.screen, context.callbacks);
+
+    context.registers.advance(2);
+
+    Ok(())
+}
+
+pub(crate) fn scl(_: u16, _: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    context.screen.scroll_left(scroll_distance(4, context));
+    refresh_screen(context.screen, context.callbacks);
+
+    context.registers.advance(2);
+
+    Ok(())
+}
+
+pub(crate) fn exit(_: u16, _: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    // Le registre PC n'est pas incrémenté, l'interpréteur reste sur cette instruction.
+    context.registers.halted = true;
+
+    Ok(())
+}
+
+fn set_resolution(high_resolution: bool, registers: &mut Registers, screen: &mut Screen, callbacks: &mut Chip8Callback) {
+    screen.set_high_resolution(high_resolution);
+
+    (callbacks.resolution_changed)(&mut callbacks.callback_data, screen.width() as u16, screen.height() as u16);
+    (callbacks.clear_pixel)(&mut callbacks.callback_data);
+
+    registers.advance(2);
+}
+
+pub(crate) fn low(_: u16, _: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    set_resolution(false, context.registers, context.screen, context.callbacks);
+
+    Ok(())
+}
+
+pub(crate) fn high(_: u16, _: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    set_resolution(true, context.registers, context.screen, context.callbacks);
+
+    Ok(())
+}
+
+pub(crate) fn cycle_background(_: u16, _: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    context.screen.cycle_background();
+    (context.callbacks.colors_changed)(&mut context.callbacks.callback_data);
+
+    context.registers.advance(2);
+
+    Ok(())
 }
 
-pub(crate) fn clean_screen(_: u16, _: &Operands, _: &mut Memory, _: &mut Memory, registers: &mut Registers, _: &[bool], screen: &mut [u8], callbacks: &mut Chip8Callback) {
+fn set_mega_mode(value: bool, registers: &mut Registers, screen: &mut Screen, callbacks: &mut Chip8Callback) {
+    screen.set_mega_mode(value);
+
+    (callbacks.resolution_changed)(&mut callbacks.callback_data, screen.width() as u16, screen.height() as u16);
     (callbacks.clear_pixel)(&mut callbacks.callback_data);
 
-    screen.fill(0);
+    registers.advance(2);
+}
+
+pub(crate) fn mega_off(_: u16, _: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    set_mega_mode(false, context.registers, context.screen, context.callbacks);
 
-    registers.pc += 2;
+    Ok(())
 }
 
-pub(crate) fn ret(_: u16, _: &Operands, _: &mut Memory, stack: &mut Memory, registers: &mut Registers, _: &[bool], _: &mut [u8], _: &mut Chip8Callback) {
-    registers.sp -= 2;
-    registers.pc = stack.read16(registers.sp as u16).unwrap() + 2;
+pub(crate) fn mega_on(_: u16, _: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    set_mega_mode(true, context.registers, context.screen, context.callbacks);
+
+    Ok(())
+}
+
+pub(crate) fn ld_i_huge(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    // L'adresse sur 24 bits est formée de kk et des 16 bits qui suivent l'instruction.
+    let low = context.ram.read16(context.registers.pc.wrapping_add(2) as u32)?;
+    context.registers.i = ((operands.kk as u32) << 16) | low as u32;
+
+    context.registers.advance(4);
+
+    Ok(())
+}
+
+pub(crate) fn ld_pal_byte(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    // Charge kk couleurs ARGB depuis l'adresse I, à partir de la couleur 1.
+    for color in 0..operands.kk as u32 {
+        let mut argb = [0; 4];
+
+        for (offset, component) in argb.iter_mut().enumerate() {
+            *component = context.ram.read8(context.registers.i.wrapping_add(color * 4 + offset as u32))?;
+        }
+
+        context.screen.mega_mut().set_palette_color(color as usize + 1, argb);
+    }
+
+    context.registers.advance(2);
+
+    Ok(())
 }
 
-pub(crate) fn jp_addr(_: u16, operands: &Operands, _: &mut Memory, _: &mut Memory, registers: &mut Registers, _: &[bool], _: &mut [u8], _: &mut Chip8Callback) {
-    registers.pc = operands.nnn;
+pub(crate) fn sprw_byte(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    context.screen.mega_mut().set_sprite_width(operands.kk);
+
+    context.registers.advance(2);
+
+    Ok(())
+}
+
+pub(crate) fn sprh_byte(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    context.screen.mega_mut().set_sprite_height(operands.kk);
+
+    context.registers.advance(2);
+
+    Ok(())
+}
+
+pub(crate) fn alpha_byte(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    context.screen.mega_mut().set_alpha(operands.kk);
+
+    context.registers.advance(2);
+
+    Ok(())
+}
+
+pub(crate) fn bmode_nibble(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    context.screen.mega_mut().set_blend_mode(BlendMode::from_value(operands.nibble));
+
+    context.registers.advance(2);
+
+    Ok(())
+}
+
+pub(crate) fn ccol_byte(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    context.screen.mega_mut().set_collision_color(operands.kk);
+
+    context.registers.advance(2);
+
+    Ok(())
 }
 
-pub(crate) fn call_addr(_: u16, operands: &Operands, _: &mut Memory, stack: &mut Memory, registers: &mut Registers, _: &[bool], _: &mut [u8], _: &mut Chip8Callback) {
+pub(crate) fn digisnd_nibble(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    // L'en-tête du son contient sa fréquence sur 16 bits puis son nombre d'échantillons sur
+    // 24 bits, les échantillons commençant 6 octets après I.
+    let rate = context.ram.read16(context.registers.i)?;
+    let length = ((context.ram.read8(context.registers.i.wrapping_add(2))? as u32) << 16) | context.ram.read16(context.registers.i.wrapping_add(3))? as u32;
+
+    let mut data = Vec::with_capacity(length as usize);
+    for offset in 0..length {
+        data.push(context.ram.read8(context.registers.i.wrapping_add(6 + offset))?);
+    }
+
+    // 0600 joue le son en boucle, 0601 une seule fois.
+    context.registers.sample = Some(DigitizedSound::new(rate, data, operands.nibble == 0));
+
+    context.registers.advance(2);
+
+    Ok(())
+}
+
+pub(crate) fn stopsnd(_: u16, _: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    context.registers.sample = None;
+
+    context.registers.advance(2);
+
+    Ok(())
+}
+
+pub(crate) fn jp_addr(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    context.registers.pc = operands.nnn;
+
+    Ok(())
+}
+
+pub(crate) fn call_addr(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    // La pile est pleine, impossible d'y ajouter une nouvelle adresse.
+    if context.registers.sp as usize + 2 > context.stack.len() {
+        return Err(Chip8Error::StackOverflow);
+    }
+
     // Stock dans la pile l'adresse actuelle.
-    if let Err(_err) = stack.write16(registers.sp as u16, registers.pc) {
-        return (); // TODO: Err(err);
+    context.stack.write16(context.registers.sp as u32, context.registers.pc)?;
+
+    context.registers.sp += 2;
+
+    context.registers.pc = operands.nnn;
+
+    Ok(())
+}
+
+/// Saute l'instruction suivante, qui fait 4 octets s'il s'agit de F000 NNNN sur XO-CHIP.
+/// Sur les autres plateformes, F000 est une instruction de 2 octets comme les autres.
+fn skip_next_instruction(context: &mut Context) {
+    let next = context.ram.read16(context.registers.pc.wrapping_add(2) as u32);
+
+    if context.platform.supports_xo_chip() && matches!(next, Ok(0xF000)) {
+        context.registers.advance(6);
+    } else {
+        context.registers.advance(4);
     }
+}
 
-    registers.sp += 2;
+pub(crate) fn se_reg_byte(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    if context.registers.v[operands.x as usize] == operands.kk {
+        skip_next_instruction(context);
+    } else {
+        context.registers.advance(2);
+    }
 
-    registers.pc = operands.nnn;
+    Ok(())
 }
 
-pub(crate) fn se_reg_byte(_: u16, operands: &Operands, _: &mut Memory, _: &mut Memory, registers: &mut Registers, _: &[bool], _: &mut [u8], _: &mut Chip8Callback) {
-    if registers.v[operands.x as usize] == operands.kk {
-        registers.pc += 4;
+pub(crate) fn sne_reg_byte(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    if context.registers.v[operands.x as usize] != operands.kk {
+        skip_next_instruction(context);
     } else {
-        registers.pc += 2;
+        context.registers.advance(2);
     }
+
+    Ok(())
 }
 
-pub(crate) fn sne_reg_byte(_: u16, operands: &Operands, _: &mut Memory, _: &mut Memory, registers: &mut Registers, _: &[bool], _: &mut [u8], _: &mut Chip8Callback) {
-    if registers.v[operands.x as usize] != operands.kk {
-        registers.pc += 4;
+pub(crate) fn se_reg_reg(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    if context.registers.v[operands.x as usize] == context.registers.v[operands.y as usize] {
+        skip_next_instruction(context);
     } else {
-        registers.pc += 2;
+        context.registers.advance(2);
     }
+
+    Ok(())
 }
 
-pub(crate) fn se_reg_reg(_: u16, operands: &Operands, _: &mut Memory, _: &mut Memory, registers: &mut Registers, _: &[bool], _: &mut [u8], _: &mut Chip8Callback) {
-    if registers.v[operands.x as usize] == registers.v[operands.y as usize] {
-        registers.pc += 4;
+/// Registres de Vx à Vy, dans l'ordre inverse si X est supérieur à Y.
+fn register_range(operands: &Operands) -> Vec<usize> {
+    let (x, y) = (operands.x as usize, operands.y as usize);
+
+    if x <= y {
+        (x..=y).collect()
     } else {
-        registers.pc += 2;
+        (y..=x).rev().collect()
     }
 }
 
-pub(crate) fn ld_reg_byte(_: u16, operands: &Operands, _: &mut Memory, _: &mut Memory, registers: &mut Registers, _: &[bool], _: &mut [u8], _: &mut Chip8Callback) {
-    registers.v[operands.x as usize] = operands.kk;
+pub(crate) fn ld_to_i_range(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    // Contrairement à FX55, I n'est jamais modifié.
+    for (offset, index) in register_range(operands).into_iter().enumerate() {
+        context.ram.write8(context.registers.i.wrapping_add(offset as u32), context.registers.v[index])?;
+    }
 
-    registers.pc += 2;
+    context.registers.advance(2);
+
+    Ok(())
 }
 
-pub(crate) fn add_reg_byte(_: u16, operands: &Operands, _: &mut Memory, _: &mut Memory, registers: &mut Registers, _: &[bool], _: &mut [u8], _: &mut Chip8Callback) {
-    registers.v[operands.x as usize] = registers.v[operands.x as usize].wrapping_add(operands.kk);
+pub(crate) fn ld_range_from_i(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    for (offset, index) in register_range(operands).into_iter().enumerate() {
+        context.registers.v[index] = context.ram.read8(context.registers.i.wrapping_add(offset as u32))?;
+    }
 
-    registers.pc += 2;
+    context.registers.advance(2);
+
+    Ok(())
 }
 
-pub(crate) fn ld_reg_reg(_: u16, operands: &Operands, _: &mut Memory, _: &mut Memory, registers: &mut Registers, _: &[bool], _: &mut [u8], _: &mut Chip8Callback) {
-    registers.v[operands.x as usize] = registers.v[operands.y as usize];
+pub(crate) fn ld_reg_byte(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    context.registers.v[operands.x as usize] = operands.kk;
+
+    context.registers.advance(2);
 
-    registers.pc += 2;
+    Ok(())
 }
 
-pub(crate) fn or_reg_reg(_: u16, operands: &Operands, _: &mut Memory, _: &mut Memory, registers: &mut Registers, _: &[bool], _: &mut [u8], _: &mut Chip8Callback) {
-    registers.v[operands.x as usize] = registers.v[operands.x as usize] | registers.v[operands.y as usize];
+pub(crate) fn add_reg_reg_nibbles(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    // Chaque quartet est additionné séparément modulo 8, sans retenue ni modification de VF.
+    let sum = (context.registers.v[operands.x as usize] & 0x77) + (context.registers.v[operands.y as usize] & 0x77);
+
+    context.registers.v[operands.x as usize] = sum & 0x77;
+
+    context.registers.advance(2);
 
-    registers.pc += 2;
+    Ok(())
 }
 
-pub(crate) fn and_reg_reg(_: u16, operands: &Operands, _: &mut Memory, _: &mut Memory, registers: &mut Registers, _: &[bool], _: &mut [u8], _: &mut Chip8Callback) {
-    registers.v[operands.x as usize] = registers.v[operands.x as usize] & registers.v[operands.y as usize];
+pub(crate) fn add_reg_byte(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    context.registers.v[operands.x as usize] = context.registers.v[operands.x as usize].wrapping_add(operands.kk);
 
-    registers.pc += 2;
+    context.registers.advance(2);
+
+    Ok(())
 }
 
-pub(crate) fn xor_reg_reg(_: u16, operands: &Operands, _: &mut Memory, _: &mut Memory, registers: &mut Registers, _: &[bool], _: &mut [u8], _: &mut Chip8Callback) {
-    registers.v[operands.x as usize] = registers.v[operands.x as usize] ^ registers.v[operands.y as usize];
+pub(crate) fn ld_reg_reg(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    context.registers.v[operands.x as usize] = context.registers.v[operands.y as usize];
+
+    context.registers.advance(2);
 
-    registers.pc += 2;
+    Ok(())
 }
 
-pub(crate) fn add_reg_reg(_: u16, operands: &Operands, _: &mut Memory, _: &mut Memory, registers: &mut Registers, _: &[bool], _: &mut [u8], _: &mut Chip8Callback) {
-    let result = registers.v[operands.x as usize] as u16 + registers.v[operands.y as usize] as u16;
+pub(crate) fn or_reg_reg(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    context.registers.v[operands.x as usize] = context.registers.v[operands.x as usize] | context.registers.v[operands.y as usize];
 
-    registers.v[0xF] = (result > 255) as u8;
-    registers.v[operands.x as usize] = (result & 0xFF) as u8;
+    // Sur le COSMAC VIP, les opérations logiques remettent VF à 0.
+    if context.quirks.vf_reset {
+        context.registers.v[0xF] = 0;
+    }
 
-    registers.pc += 2;
+    context.registers.advance(2);
+
+    Ok(())
 }
 
-pub(crate) fn sub_reg_reg(_: u16, operands: &Operands, _: &mut Memory, _: &mut Memory, registers: &mut Registers, _: &[bool], _: &mut [u8], _: &mut Chip8Callback) {
-    // Si Vx > Vy, met la valeur de VF à 1.
-    registers.v[0xF] = (registers.v[operands.x as usize] > registers.v[operands.y as usize]) as u8;
+pub(crate) fn and_reg_reg(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    context.registers.v[operands.x as usize] = context.registers.v[operands.x as usize] & context.registers.v[operands.y as usize];
+
+    // Sur le COSMAC VIP, les opérations logiques remettent VF à 0.
+    if context.quirks.vf_reset {
+        context.registers.v[0xF] = 0;
+    }
 
-    registers.v[operands.x as usize] = registers.v[operands.x as usize].wrapping_sub(registers.v[operands.y as usize]);
+    context.registers.advance(2);
 
-    registers.pc += 2;
+    Ok(())
 }
 
-pub(crate) fn shr_reg_reg(_: u16, operands: &Operands, _: &mut Memory, _: &mut Memory, registers: &mut Registers, _: &[bool], _: &mut [u8], _: &mut Chip8Callback) {
-    // Récupère la valeur actuelle de Vx.
-    let value = registers.v[operands.x as usize];
+pub(crate) fn xor_reg_reg(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    context.registers.v[operands.x as usize] = context.registers.v[operands.x as usize] ^ context.registers.v[operands.y as usize];
+
+    // Sur le COSMAC VIP, les opérations logiques remettent VF à 0.
+    if context.quirks.vf_reset {
+        context.registers.v[0xF] = 0;
+    }
+
+    context.registers.advance(2);
+
+    Ok(())
+}
+
+pub(crate) fn add_reg_reg(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    let result = context.registers.v[operands.x as usize] as u16 + context.registers.v[operands.y as usize] as u16;
+
+    context.registers.v[operands.x as usize] = (result & 0xFF) as u8;
+
+    // S'il y a une retenue, met VF à 1.
+    // VF est écrit en dernier pour que le drapeau soit conservé lorsque X vaut F.
+    context.registers.v[0xF] = (result > 255) as u8;
+
+    context.registers.advance(2);
+
+    Ok(())
+}
+
+pub(crate) fn sub_reg_reg(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    let (x, y) = (context.registers.v[operands.x as usize], context.registers.v[operands.y as usize]);
+
+    context.registers.v[operands.x as usize] = x.wrapping_sub(y);
 
-    // Si le bit de poids faible est à 1, met VF à 1.
-    registers.v[0xF] = ((value & 0x1) > 0) as u8;
+    // S'il n'y a pas d'emprunt, c'est-à-dire si Vx >= Vy, met VF à 1.
+    // VF est écrit en dernier pour que le drapeau soit conservé lorsque X vaut F.
+    context.registers.v[0xF] = (x >= y) as u8;
+
+    context.registers.advance(2);
+
+    Ok(())
+}
+
+pub(crate) fn shr_reg_reg(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    // Récupère la valeur à décaler, Vy sur le COSMAC VIP et Vx sinon.
+    let value = if context.quirks.shift_uses_vy {
+        context.registers.v[operands.y as usize]
+    } else {
+        context.registers.v[operands.x as usize]
+    };
 
     // Décale de 1 bit vers la droite.
+    context.registers.v[operands.x as usize] = value >> 1;
 
-    registers.v[operands.x as usize] = value >> 1;
+    // Si le bit de poids faible était à 1, met VF à 1.
+    // VF est écrit en dernier pour que le drapeau soit conservé lorsque X vaut F.
+    context.registers.v[0xF] = value & 0x1;
 
-    registers.pc += 2;
+    context.registers.advance(2);
+
+    Ok(())
 }
 
-pub(crate) fn subn_reg_reg(_: u16, operands: &Operands, _: &mut Memory, _: &mut Memory, registers: &mut Registers, _: &[bool], _: &mut [u8], _: &mut Chip8Callback) {
-    // Si Vy > Vx, met la valeur de VF à 1.
-    registers.v[0xF] = (registers.v[operands.y as usize] > registers.v[operands.x as usize]) as u8;
+pub(crate) fn subn_reg_reg(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    let (x, y) = (context.registers.v[operands.x as usize], context.registers.v[operands.y as usize]);
+
+    context.registers.v[operands.x as usize] = y.wrapping_sub(x);
 
-    registers.v[operands.x as usize] = registers.v[operands.y as usize] - registers.v[operands.x as usize];
+    // S'il n'y a pas d'emprunt, c'est-à-dire si Vy >= Vx, met VF à 1.
+    // VF est écrit en dernier pour que le drapeau soit conservé lorsque X vaut F.
+    context.registers.v[0xF] = (y >= x) as u8;
 
-    registers.pc += 2;
+    context.registers.advance(2);
+
+    Ok(())
 }
 
-pub(crate) fn shl_reg_reg(_: u16, operands: &Operands, _: &mut Memory, _: &mut Memory, registers: &mut Registers, _: &[bool], _: &mut [u8], _: &mut Chip8Callback) {
-    let value = registers.v[operands.x as usize];
+pub(crate) fn shl_reg_reg(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    let value = if context.quirks.shift_uses_vy {
+        context.registers.v[operands.y as usize]
+    } else {
+        context.registers.v[operands.x as usize]
+    };
+
+    // Décale de 1 bit vers la gauche.
+    context.registers.v[operands.x as usize] = value << 1;
 
-    // Si le bit de poids fort est à 1, met VF à 1.
-    registers.v[0xF] = ((value & 0x80) > 0) as u8;
+    // Si le bit de poids fort était à 1, met VF à 1.
+    // VF est écrit en dernier pour que le drapeau soit conservé lorsque X vaut F.
+    context.registers.v[0xF] = value >> 7;
 
-    // Décale de Vy bits vers la gauche.
-    registers.v[operands.x as usize] = value << 1;
+    context.registers.advance(2);
 
-    registers.pc += 2;
+    Ok(())
 }
 
-pub(crate) fn sne_reg_reg(_: u16, operands: &Operands, _: &mut Memory, _: &mut Memory, registers: &mut Registers, _: &[bool], _: &mut [u8], _: &mut Chip8Callback) {
-    if registers.v[operands.x as usize] != registers.v[operands.y as usize] {
-        registers.pc += 4;
+pub(crate) fn sne_reg_reg(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    if context.registers.v[operands.x as usize] != context.registers.v[operands.y as usize] {
+        skip_next_instruction(context);
     } else {
-        registers.pc += 2;
+        context.registers.advance(2);
     }
+
+    Ok(())
 }
 
-pub(crate) fn ld_i_addr(_: u16, operands: &Operands, _: &mut Memory, _: &mut Memory, registers: &mut Registers, _: &[bool], _: &mut [u8], _: &mut Chip8Callback) {
-    registers.i = operands.nnn;
+pub(crate) fn ld_i_addr(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    context.registers.i = operands.nnn as u32;
+
+    context.registers.advance(2);
 
-    registers.pc += 2;
+    Ok(())
 }
 
-pub(crate) fn jp_v0_addr(_: u16, operands: &Operands, _: &mut Memory, _: &mut Memory, registers: &mut Registers, _: &[bool], _: &mut [u8], _: &mut Chip8Callback) {
-    registers.pc = operands.nnn + registers.v[0x0] as u16;
+pub(crate) fn ld_i_long(_: u16, _: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    // L'adresse sur 16 bits est stockée dans les 2 octets qui suivent l'instruction.
+    context.registers.i = context.ram.read16(context.registers.pc.wrapping_add(2) as u32)? as u32;
+
+    context.registers.advance(4);
+
+    Ok(())
+}
+
+pub(crate) fn jp_v0_addr(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    // Sur CHIP-48 et SUPER-CHIP, l'instruction est interprétée comme BXNN.
+    let register = if context.quirks.jump_uses_vx { operands.x } else { 0x0 };
+
+    context.registers.pc = operands.nnn + context.registers.v[register as usize] as u16;
+
+    Ok(())
 }
 
-pub(crate) fn rnd_reg_byte(_: u16, operands: &Operands, _: &mut Memory, _: &mut Memory, registers: &mut Registers, _: &[bool], _: &mut [u8], _: &mut Chip8Callback) {
+/// Valeur du registre qui suit Vx, dont se servent les instructions de couleur du CHIP-8X pour
+/// la position verticale.
+/// VF n'ayant pas de registre suivant, l'instruction échoue alors avant de colorer quoi que ce
+/// soit.
+fn next_register(registers: &Registers, x: u8) -> Result<u8, Chip8Error> {
+    match registers.v.get(x as usize + 1) {
+        Some(value) => Ok(*value),
+        None => Err(Chip8Error::InvalidRegister(x + 1)),
+    }
+}
+
+pub(crate) fn col_reg_reg(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    // Les 4 bits de poids faible donnent la première zone, ceux de poids fort le nombre de zones
+    // supplémentaires.
+    let horizontal = context.registers.v[operands.x as usize];
+    let vertical = next_register(context.registers, operands.x)?;
+
+    context.screen.set_foreground_color(
+        (horizontal & 0xF) as usize,
+        (horizontal >> 4) as usize + 1,
+        (vertical & 0xF) as usize * COLOR_ZONE_HEIGHT,
+        ((vertical >> 4) as usize + 1) * COLOR_ZONE_HEIGHT,
+        context.registers.v[operands.y as usize],
+    );
+    (context.callbacks.colors_changed)(&mut context.callbacks.callback_data);
+
+    context.registers.advance(2);
+
+    Ok(())
+}
+
+pub(crate) fn col_reg_reg_nibble(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    // Colore n lignes d'une seule zone, à partir du pixel situé en (Vx, Vx+1).
+    let x = context.registers.v[operands.x as usize] as usize;
+    let y = next_register(context.registers, operands.x)? as usize;
+
+    context.screen.set_foreground_color(
+        x / COLOR_ZONE_WIDTH,
+        1,
+        y,
+        operands.nibble as usize,
+        context.registers.v[operands.y as usize],
+    );
+    (context.callbacks.colors_changed)(&mut context.callbacks.callback_data);
+
+    context.registers.advance(2);
+
+    Ok(())
+}
+
+pub(crate) fn rnd_reg_byte(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
     let mut rng = rand::thread_rng();
 
     let random_number = rng.gen_range(0..256) as u8;
 
-    registers.v[operands.x as usize] = random_number & operands.kk;
+    context.registers.v[operands.x as usize] = random_number & operands.kk;
+
+    context.registers.advance(2);
 
-    registers.pc += 2;
+    Ok(())
 }
 
-pub(crate) fn drw_reg_reg_nibble(_: u16, operands: &Operands, ram: &mut Memory, _: &mut Memory, registers: &mut Registers, _: &[bool], screen: &mut [u8], callbacks: &mut Chip8Callback) {
+/// Dessine un sprite de `width` pixels de large (8 ou 16) et `height` lignes, lu à partir de I.
+/// Sur XO-CHIP, le sprite est dessiné sur chaque plan sélectionné, les données de chaque plan
+/// se suivant en mémoire.
+fn draw_sprite(operands: &Operands, context: &mut Context, width: usize, height: usize) -> Result<(), Chip8Error> {
+    let Context {
+        ram,
+        registers,
+        screen,
+        callbacks,
+        quirks,
+        ..
+    } = context;
+
     // Initialise le Carry Flag à 0.
     registers.v[0xF] = 0;
 
-    if operands.x > 0xF || operands.y > 0xF {
-        eprintln!("[CHIP-8] Error when drawing: x or y out of bound: {:02x} {:02x}", operands.x, operands.y);
+    // La position de départ est toujours ramenée dans l'écran.
+    let x = registers.v[operands.x as usize] as usize % screen.width();
+    let y = registers.v[operands.y as usize] as usize % screen.height();
 
-        return ();
-    }
+    // Chaque ligne du sprite fait 1 octet, ou 2 octets pour les grands sprites.
+    let bytes_per_row = width / 8;
 
-    // Un sprite ne peut pas faire plus de 15 pixels de hauteur.
-    if operands.nibble > 15 {
-        eprintln!("[CHIP-8] Error when drawing: nibble is out of bound: {}", operands.nibble);
+    let mut address = registers.i;
 
-        return ();
-    }
+    for plane_index in 0..PLANES_COUNT {
+        let plane = 1 << plane_index;
+
+        if screen.selected_planes() & plane == 0 {
+            continue;
+        }
 
-    // Pour chaque ligne du sprite à afficher.
-    for row in 0..operands.nibble {
-        // Si le pixel sort de l'écran vers le bas, le ramène en haut de l'écran.
-        // 'wrapping_add' est une fonction qui permet d'ajouter un entier sans paniquer
-        // s'il y a un overflow.
-        let yy = (registers.v[operands.y as usize].wrapping_add(row)) % 32;
+        // Pour chaque ligne du sprite à afficher.
+        for row in 0..height {
+            let mut yy = y + row;
 
-        let sprite = match ram.read8(registers.i + row as u16) {
-            Ok(o) => o,
-            Err(_err) => return (), // TODO: Err(err),
-        };
+            // Si le pixel sort de l'écran vers le bas, il est soit coupé, soit ramené en haut
+            // de l'écran.
+            if yy >= screen.height() {
+                if quirks.clip_sprites {
+                    break;
+                }
 
-        // Pour chaque bit de l'octet.
-        for col in 0..8 {
-            // Si le pixel sort de l'écran vers la droite, le ramène à gauche de
-            // l'écran.
-            let xx = (registers.v[operands.x as usize] + col) % 64;
+                yy %= screen.height();
+            }
 
-            // Récupère l'état du pixel actuellement affiché à l'écran.
-            let current_pixel =
-                screen.get_mut(yy as usize * 64 + xx as usize).unwrap();
+            let mut sprite = 0_u16;
+            for byte in 0..bytes_per_row {
+                let offset = (row * bytes_per_row + byte) as u32;
+                sprite = (sprite << 8) | ram.read8(address.wrapping_add(offset))? as u16;
+            }
 
-            // Le dernier décalement vers la droite permet de récupérer uniquement le
-            // dernier bit.
-            let sprite_bit = (sprite & (0x80 >> col)) >> (7 - col);
+            // Pour chaque bit de la ligne.
+            for col in 0..width {
+                let mut xx = x + col;
 
-            // Si on veut allumer alors que c'est déjà allumé, on éteint.
-            if (sprite_bit & *current_pixel) != 0 {
-                // Le Carry Flag est mit à 1 lorsqu'un pixel est éteint car il y a une collision.
-                registers.v[0xF] = 1;
+                // Si le pixel sort de l'écran vers la droite, il est soit coupé, soit ramené à
+                // gauche de l'écran.
+                if xx >= screen.width() {
+                    if quirks.clip_sprites {
+                        break;
+                    }
+
+                    xx %= screen.width();
+                }
+
+                // Le dernier décalement vers la droite permet de récupérer uniquement le
+                // dernier bit.
+                let sprite_bit = (sprite >> (width - 1 - col)) & 0x1;
+
+                if sprite_bit == 0 {
+                    continue;
+                }
+
+                // Les spécifications indiquent que le pixel actuel doit être XORed avec le
+                // sprite. Le Carry Flag est mit à 1 lorsqu'un pixel est éteint car il y a une
+                // collision.
+                if screen.xor(xx, yy, plane) {
+                    registers.v[0xF] = 1;
+                }
+
+                update_pixel(screen, callbacks, xx, yy);
             }
+        }
+
+        // Les données du plan suivant se trouvent juste après celles de ce plan.
+        address = address.wrapping_add((height * bytes_per_row) as u32);
+    }
+
+    registers.advance(2);
+
+    Ok(())
+}
+
+/// Dessine un sprite du MEGA-CHIP, dont chaque octet est l'indice de la couleur d'un pixel.
+/// Ses dimensions sont celles choisies par 03NN et 04NN, et il est coupé au bord de l'écran.
+fn draw_mega_sprite(operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    let Context { ram, registers, screen, .. } = context;
+
+    registers.v[0xF] = 0;
 
-            // Les spécifications indiquent que le pixel actuel doit être XORed avec le
-            // sprite.
-            *current_pixel ^= sprite_bit;
+    let x = registers.v[operands.x as usize] as usize % screen.width();
+    let y = registers.v[operands.y as usize] as usize % screen.height();
+    let (width, height) = (screen.mega().sprite_width(), screen.mega().sprite_height());
 
-            if *current_pixel != 0 {
-                (callbacks.set_pixel)(&mut callbacks.callback_data, xx, yy);
-            } else {
-                (callbacks.unset_pixel)(&mut callbacks.callback_data, xx, yy);
+    for row in 0..height.min(screen.height() - y) {
+        for col in 0..width.min(screen.width() - x) {
+            let index = ram.read8(registers.i.wrapping_add((row * width + col) as u32))?;
+
+            if screen.mega_mut().draw_pixel(x + col, y + row, index) {
+                registers.v[0xF] = 1;
             }
         }
     }
 
-    registers.pc += 2;
+    registers.advance(2);
+
+    Ok(())
+}
+
+pub(crate) fn drw_reg_reg_nibble(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    if context.screen.mega().is_enabled() {
+        return draw_mega_sprite(operands, context);
+    }
+
+    draw_sprite(operands, context, 8, operands.nibble as usize)
+}
+
+pub(crate) fn drw_reg_reg_large(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    if context.screen.mega().is_enabled() {
+        return draw_mega_sprite(operands, context);
+    }
+
+    // Sur SUPER-CHIP, DXY0 dessine un sprite de 16x16 pixels.
+    draw_sprite(operands, context, 16, 16)
+}
+
+pub(crate) fn plane_nibble(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    // Sélectionne les plans sur lesquels travaillent les instructions d'affichage.
+    context.screen.set_selected_planes(operands.x);
+
+    context.registers.advance(2);
+
+    Ok(())
+}
+
+/// Indique si la touche `key` du clavier `keys` est appuyée.
+fn key_pressed(keys: &[bool], key: u8) -> Result<bool, Chip8Error> {
+    match keys.get(key as usize) {
+        Some(pressed) => Ok(*pressed),
+        None => Err(Chip8Error::InvalidKey(key)),
+    }
+}
+
+pub(crate) fn skp_reg(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    if key_pressed(&context.keys[..KEYPAD_SIZE], context.registers.v[operands.x as usize])? {
+        skip_next_instruction(context);
+    } else {
+        context.registers.advance(2);
+    }
+
+    Ok(())
+}
+
+pub(crate) fn sknp_reg(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    if !key_pressed(&context.keys[..KEYPAD_SIZE], context.registers.v[operands.x as usize])? {
+        skip_next_instruction(context);
+    } else {
+        context.registers.advance(2);
+    }
+
+    Ok(())
 }
 
-pub(crate) fn skp_reg(_: u16, operands: &Operands, _: &mut Memory, _: &mut Memory, registers: &mut Registers, keys: &[bool], _: &mut [u8], _: &mut Chip8Callback) {
-    if keys[registers.v[operands.x as usize] as usize] {
-        registers.pc += 4;
+pub(crate) fn skp2_reg(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    // Le second clavier du CHIP-8X suit le premier dans `context.keys`.
+    if key_pressed(&context.keys[KEYPAD_SIZE..], context.registers.v[operands.x as usize])? {
+        skip_next_instruction(context);
     } else {
-        registers.pc += 2;
+        context.registers.advance(2);
     }
+
+    Ok(())
 }
 
-pub(crate) fn sknp_reg(_: u16, operands: &Operands, _: &mut Memory, _: &mut Memory, registers: &mut Registers, keys: &[bool], _: &mut [u8], _: &mut Chip8Callback) {
-    if !keys[registers.v[operands.x as usize] as usize] {
-        registers.pc += 4;
+pub(crate) fn sknp2_reg(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    if !key_pressed(&context.keys[KEYPAD_SIZE..], context.registers.v[operands.x as usize])? {
+        skip_next_instruction(context);
     } else {
-        registers.pc += 2;
+        context.registers.advance(2);
     }
+
+    Ok(())
 }
 
-pub(crate) fn ld_reg_dt(_: u16, operands: &Operands, _: &mut Memory, _: &mut Memory, registers: &mut Registers, _: &[bool], _: &mut [u8], _: &mut Chip8Callback) {
-    registers.v[operands.x as usize] = registers.dt;
+pub(crate) fn ld_reg_dt(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    context.registers.v[operands.x as usize] = context.registers.dt;
 
-    registers.pc += 2;
+    context.registers.advance(2);
+
+    Ok(())
 }
 
-pub(crate) fn ld_reg_k(_: u16, operands: &Operands, _: &mut Memory, _: &mut Memory, registers: &mut Registers, keys: &[bool], _: &mut [u8], _: &mut Chip8Callback) {
+pub(crate) fn ld_reg_k(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
     // Cela peut être n'importe quelle touche.
-    if let Some(index) = keys.iter().position(|&pressed| pressed) {
-        registers.v[operands.x as usize] = index as u8;
+    if let Some(index) = context.keys[..KEYPAD_SIZE].iter().position(|&pressed| pressed) {
+        context.registers.v[operands.x as usize] = index as u8;
 
-        registers.pc += 2;
+        context.registers.advance(2);
     }
+
+    Ok(())
+}
+
+pub(crate) fn ld_dt_reg(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    context.registers.dt = context.registers.v[operands.x as usize];
+
+    context.registers.advance(2);
+
+    Ok(())
 }
 
-pub(crate) fn ld_dt_reg(_: u16, operands: &Operands, _: &mut Memory, _: &mut Memory, registers: &mut Registers, _: &[bool], _: &mut [u8], _: &mut Chip8Callback) {
-    registers.dt = registers.v[operands.x as usize];
+pub(crate) fn ld_st_reg(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    context.registers.st = context.registers.v[operands.x as usize];
 
-    registers.pc += 2;
+    context.registers.advance(2);
+
+    Ok(())
 }
 
-pub(crate) fn ld_st_reg(_: u16, operands: &Operands, _: &mut Memory, _: &mut Memory, registers: &mut Registers, _: &[bool], _: &mut [u8], _: &mut Chip8Callback) {
-    registers.st = registers.v[operands.x as usize];
+pub(crate) fn add_i_reg(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    // Sur XO-CHIP, la RAM fait 64 Kio et I peut donc dépasser 0xFFFF.
+    context.registers.i = context.registers.i.wrapping_add(context.registers.v[operands.x as usize] as u32);
+
+    context.registers.advance(2);
 
-    registers.pc += 2;
+    Ok(())
 }
 
-pub(crate) fn add_i_reg(_: u16, operands: &Operands, _: &mut Memory, _: &mut Memory, registers: &mut Registers, _: &[bool], _: &mut [u8], _: &mut Chip8Callback) {
-    registers.i += registers.v[operands.x as usize] as u16;
+pub(crate) fn ld_i_reg(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    context.registers.i = context.registers.font_address as u32 + (context.registers.v[operands.x as usize] as u32) * FONT_GLYPH_HEIGHT as u32;
+
+    context.registers.advance(2);
 
-    registers.pc += 2;
+    Ok(())
 }
 
-pub(crate) fn ld_i_reg(_: u16, operands: &Operands, _: &mut Memory, _: &mut Memory, registers: &mut Registers, _: &[bool], _: &mut [u8], _: &mut Chip8Callback) {
-    // Comme les sprites sont stockées au tout début de la RAM, il n'y a pas besoin
-    // de faire de calcul.
-    registers.i = (registers.v[operands.x as usize] as u16) * 5;
+pub(crate) fn ld_hf_reg(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    // Les grands chiffres de 10 octets sont stockés juste après les petits.
+    context.registers.i = context.registers.big_font_address as u32 + (context.registers.v[operands.x as usize] as u32) * BIG_FONT_GLYPH_HEIGHT as u32;
 
-    registers.pc += 2;
+    context.registers.advance(2);
+
+    Ok(())
 }
 
-pub(crate) fn ld_b_reg(_: u16, operands: &Operands, ram: &mut Memory, _: &mut Memory, registers: &mut Registers, _: &[bool], _: &mut [u8], _: &mut Chip8Callback) {
-    let mut value = registers.v[operands.x as usize];
-                    
-    if let Err(_err) = ram.write8(registers.i + 2, value % 10) {
-        return (); // TODO: Err(err);
+pub(crate) fn ld_audio_i(_: u16, _: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    // Copie les 16 octets situés à l'adresse I dans le motif audio.
+    for index in 0..context.registers.pattern.len() {
+        context.registers.pattern[index] = context.ram.read8(context.registers.i.wrapping_add(index as u32))?;
     }
 
+    context.registers.advance(2);
+
+    Ok(())
+}
+
+pub(crate) fn ld_pitch_reg(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    context.registers.pitch = context.registers.v[operands.x as usize];
+
+    context.registers.advance(2);
+
+    Ok(())
+}
+
+pub(crate) fn ld_b_reg(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    let mut value = context.registers.v[operands.x as usize];
+
+    context.ram.write8(context.registers.i.wrapping_add(2), value % 10)?;
+
     value /= 10;
 
-    if let Err(_err) = ram.write8(registers.i + 1, value % 10) {
-        return (); // TODO: Err(err);
-    }
+    context.ram.write8(context.registers.i.wrapping_add(1), value % 10)?;
 
     value /= 10;
-    
-    if let Err(_err) = ram.write8(registers.i, value % 10) {
-        return (); // TODO: Err(err);
-    }
 
-    registers.pc += 2;
+    context.ram.write8(context.registers.i, value % 10)?;
+
+    context.registers.advance(2);
+
+    Ok(())
+}
+
+fn increment_i(registers: &mut Registers, x: u8, quirks: &Quirks) {
+    match quirks.memory_increment {
+        MemoryIncrement::None => (),
+        MemoryIncrement::X => registers.i = registers.i.wrapping_add(x as u32),
+        MemoryIncrement::XPlusOne => registers.i = registers.i.wrapping_add(x as u32 + 1),
+    }
 }
 
-pub(crate) fn ld_to_i_reg(_: u16, operands: &Operands, ram: &mut Memory, _: &mut Memory, registers: &mut Registers, _: &[bool], _: &mut [u8], _: &mut Chip8Callback) {
+pub(crate) fn ld_to_i_reg(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
     for index in 0..operands.x + 1 {
-        if let Err(err) = ram.write8(registers.i + index as u16, registers.v[index as usize]) {
-            eprintln!("[CHIP-8 error] {err}");
-            return (); // TODO: Err(err);
-        }
+        context.ram.write8(context.registers.i.wrapping_add(index as u32), context.registers.v[index as usize])?;
     }
 
-    registers.pc += 2;
+    increment_i(context.registers, operands.x, context.quirks);
+
+    context.registers.advance(2);
+
+    Ok(())
 }
 
-pub(crate) fn ld_reg_from_i(_: u16, operands: &Operands, ram: &mut Memory, _: &mut Memory, registers: &mut Registers, _: &[bool], _: &mut [u8], _: &mut Chip8Callback) {
+pub(crate) fn ld_reg_from_i(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
     for index in 0..operands.x + 1 {
-        registers.v[index as usize] = match ram.read8(registers.i + index as u16) {
-            Ok(o) => o,
-            Err(err) => {
-                eprintln!("[CHIP-8 error] {err}");
-                return ();
-             } // TODO: Err(err),
-        }
+        context.registers.v[index as usize] = context.ram.read8(context.registers.i.wrapping_add(index as u32))?;
+    }
+
+    increment_i(context.registers, operands.x, context.quirks);
+
+    context.registers.advance(2);
+
+    Ok(())
+}
+
+pub(crate) fn ld_r_reg(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    for index in 0..operands.x as usize + 1 {
+        context.registers.rpl[index] = context.registers.v[index];
     }
 
-    registers.pc += 2;
+    // Prévient l'hôte pour qu'il puisse sauvegarder les drapeaux.
+    (context.callbacks.rpl_flags_saved)(&mut context.callbacks.callback_data, &context.registers.rpl);
+
+    context.registers.advance(2);
+
+    Ok(())
 }
 
+pub(crate) fn ld_reg_r(_: u16, operands: &Operands, context: &mut Context) -> Result<(), Chip8Error> {
+    for index in 0..operands.x as usize + 1 {
+        context.registers.v[index] = context.registers.rpl[index];
+    }
+
+    context.registers.advance(2);
+
+    Ok(())
+}
@@ -0,0 +1,142 @@
+use crate::{
+    chip::Chip8Callback,
+    error::Chip8Error,
+    instruction::update_pixel,
+    memory::Memory,
+    register::Registers,
+    screen::Screen,
+};
+
+/// Adresse la plus basse de la pile de l'interpréteur du COSMAC VIP, qui descend depuis
+/// `VIP_WORK_AREA_ADDRESS`.
+pub const VIP_STACK_ADDRESS: u16 = 0xEA0;
+
+/// Zone de travail de l'interpréteur du COSMAC VIP, qui n'est pas émulée.
+pub const VIP_WORK_AREA_ADDRESS: u16 = 0xED0;
+
+/// Adresse des registres V0 à VF.
+pub const VIP_REGISTERS_ADDRESS: u16 = 0xEF0;
+
+/// Adresse de l'image de 64x32 pixels, un bit par pixel ligne par ligne.
+pub const VIP_DISPLAY_ADDRESS: u16 = 0xF00;
+
+/// Nombre d'adresses de retour que contient la pile du COSMAC VIP.
+pub const VIP_STACK_DEPTH: usize = (VIP_WORK_AREA_ADDRESS - VIP_STACK_ADDRESS) as usize / 2;
+
+/// Fin de la zone occupée par l'interpréteur, qui s'étend jusqu'à la fin des 4 premiers Kio.
+const VIP_AREA_END: u32 = 0x1000;
+
+const VIP_DISPLAY_WIDTH: usize = 64;
+const VIP_DISPLAY_HEIGHT: usize = 32;
+
+/// Emplacement de la pile, des registres et de l'écran.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MemoryLayout {
+    /// La pile et l'écran sont séparés de la RAM, comme sur les interpréteurs modernes.
+    #[default]
+    Separate,
+    /// La pile, les registres V et l'écran sont placés dans la RAM aux mêmes adresses que sur le
+    /// COSMAC VIP, ce qui permet aux programmes de les lire et de les modifier.
+    /// L'écran n'est placé en RAM que s'il fait 64x32 pixels.
+    CosmacVip,
+}
+
+/// Adresse en RAM de l'entrée `index` de la pile, la première étant la plus haute.
+/// Comme sur le COSMAC VIP, l'adresse de retour est celle de l'instruction qui suit l'appel.
+fn stack_entry_address(index: usize) -> u32 {
+    (VIP_WORK_AREA_ADDRESS as usize - 2 * (index + 1)) as u32
+}
+
+fn has_vip_display(screen: &Screen) -> bool {
+    screen.width() == VIP_DISPLAY_WIDTH && screen.height() == VIP_DISPLAY_HEIGHT
+}
+
+/// Octet de la RAM correspondant aux 8 pixels de l'écran qui commencent à (`x`, `y`).
+fn display_byte(screen: &Screen, x: usize, y: usize) -> u8 {
+    (0..8).fold(0, |byte, bit| (byte << 1) | (screen.get(x + bit, y) != 0) as u8)
+}
+
+/// Zone de la RAM occupée par la pile, les registres et l'écran.
+fn read_area(ram: &Memory) -> Result<Vec<u8>, Chip8Error> {
+    (VIP_STACK_ADDRESS as u32..VIP_AREA_END)
+        .map(|address| ram.read8(address))
+        .collect()
+}
+
+/// Écrit l'état de la machine dans `area`, une copie de la zone de la RAM.
+fn write_area(area: &mut [u8], stack: &Memory, registers: &Registers, screen: &Screen) -> Result<(), Chip8Error> {
+    let base = VIP_STACK_ADDRESS as usize;
+
+    for index in 0..registers.sp as usize / 2 {
+        let address = stack.read16(index as u32 * 2)?.wrapping_add(2);
+        let offset = stack_entry_address(index) as usize - base;
+
+        area[offset..offset + 2].copy_from_slice(&address.to_be_bytes());
+    }
+
+    let offset = VIP_REGISTERS_ADDRESS as usize - base;
+    area[offset..offset + registers.v.len()].copy_from_slice(&registers.v);
+
+    if has_vip_display(screen) {
+        for y in 0..VIP_DISPLAY_HEIGHT {
+            for x in (0..VIP_DISPLAY_WIDTH).step_by(8) {
+                let offset = VIP_DISPLAY_ADDRESS as usize - base + (y * VIP_DISPLAY_WIDTH + x) / 8;
+
+                area[offset] = display_byte(screen, x, y);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Relit la pile, les registres V et l'écran depuis la RAM avant l'exécution d'une instruction,
+/// au cas où le programme les aurait modifiés, et signale aux callbacks les pixels changés.
+/// Retourne le contenu de la zone, qui doit être passé à `store_vip_state`.
+pub(crate) fn load_vip_state(ram: &Memory, stack: &mut Memory, registers: &mut Registers, screen: &mut Screen, callbacks: &mut Chip8Callback) -> Result<Vec<u8>, Chip8Error> {
+    for index in 0..registers.sp as usize / 2 {
+        let address = ram.read16(stack_entry_address(index))?;
+
+        stack.write16(index as u32 * 2, address.wrapping_sub(2))?;
+    }
+
+    for (index, register) in registers.v.iter_mut().enumerate() {
+        *register = ram.read8(VIP_REGISTERS_ADDRESS as u32 + index as u32)?;
+    }
+
+    if has_vip_display(screen) {
+        for y in 0..VIP_DISPLAY_HEIGHT {
+            for x in (0..VIP_DISPLAY_WIDTH).step_by(8) {
+                let offset = (y * VIP_DISPLAY_WIDTH + x) / 8;
+                let byte = ram.read8(VIP_DISPLAY_ADDRESS as u32 + offset as u32)?;
+                let changed = byte ^ display_byte(screen, x, y);
+
+                for bit in (0..8).filter(|bit| changed & (0x80 >> bit) != 0) {
+                    // Le pixel est entièrement allumé ou éteint, quels que soient ses plans.
+                    let plane = screen.get(x + bit, y).max(1);
+                    screen.xor(x + bit, y, plane);
+
+                    update_pixel(screen, callbacks, x + bit, y);
+                }
+            }
+        }
+    }
+
+    read_area(ram)
+}
+
+/// Copie dans la RAM la pile, les registres V et l'écran après l'exécution d'une instruction.
+/// Seuls les octets que l'instruction a changés par rapport à `area`, le contenu de la zone
+/// avant son exécution, sont écrits, pour ne pas effacer ce qu'elle a écrit elle-même en RAM.
+pub(crate) fn store_vip_state(ram: &mut Memory, stack: &Memory, registers: &Registers, screen: &Screen, area: &[u8]) -> Result<(), Chip8Error> {
+    let mut state = area.to_vec();
+    write_area(&mut state, stack, registers, screen)?;
+
+    for (offset, (new, old)) in state.iter().zip(area).enumerate() {
+        if new != old {
+            ram.write8(VIP_STACK_ADDRESS as u32 + offset as u32, *new)?;
+        }
+    }
+
+    Ok(())
+}
@@ -1,4 +1,24 @@
+pub mod asm;
+pub mod audio;
+pub mod cdp1802;
+pub mod cfg;
 pub mod chip;
+pub mod clock;
+pub mod disasm;
+pub mod error;
+pub mod font;
+pub mod megachip;
 pub mod memory;
+pub mod octo;
+pub mod platform;
 pub mod instruction;
+pub mod layout;
+pub mod machine;
+pub mod profile;
+pub mod quirks;
 pub mod register;
+pub mod rpl;
+pub mod screen;
+pub mod sound;
+pub mod timing;
+pub mod vip;
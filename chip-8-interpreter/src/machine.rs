@@ -0,0 +1,24 @@
+use crate::error::Chip8Error;
+
+/// Interface commune aux machines capables d'exécuter un programme CHIP-8 : l'interpréteur
+/// `Chip8`, qui exécute directement les instructions, et le `Vip`, qui émule le COSMAC VIP
+/// exécutant l'interpréteur d'origine.
+/// Elle permet à l'hôte de piloter l'une ou l'autre, et de comparer leurs résultats.
+pub trait Machine {
+    /// Exécute le programme jusqu'à la fin de la frame émulée en cours.
+    fn run_frame(&mut self) -> Result<(), Chip8Error>;
+
+    fn set_key_pressed(&mut self, index: u8, value: bool) -> Result<(), Chip8Error>;
+
+    /// Indique si la machine ne peut plus rien exécuter.
+    fn is_halted(&self) -> bool;
+
+    /// Indique si le buzzer sonne.
+    fn is_beeping(&self) -> bool;
+
+    /// Largeur et hauteur de l'image affichée, en pixels.
+    fn display_size(&self) -> (usize, usize);
+
+    /// Indice de couleur du pixel affiché, 0 étant éteint.
+    fn display_pixel(&self, x: usize, y: usize) -> u8;
+}
@@ -0,0 +1,255 @@
+/// Résolution de l'écran en mode MEGA-CHIP.
+pub const MEGA_WIDTH: usize = 256;
+pub const MEGA_HEIGHT: usize = 192;
+
+/// Nombre de couleurs de la palette, la couleur 0 étant toujours transparente.
+pub const PALETTE_SIZE: usize = 256;
+
+/// Taille de la RAM du MEGA-CHIP, adressable sur 24 bits.
+pub const MEGA_MEMORY_SIZE: usize = 0x1000000;
+
+/// Façon dont les pixels d'un sprite sont mélangés à ceux déjà affichés, choisie par 080N.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlendMode {
+    /// Le sprite est mélangé selon la transparence de ses couleurs.
+    Normal,
+    /// Le sprite est affiché avec une opacité de 25%.
+    Alpha25,
+    /// Le sprite est affiché avec une opacité de 50%.
+    Alpha50,
+    /// Les couleurs du sprite s'ajoutent à celles de l'écran.
+    Additive,
+    /// Les couleurs du sprite sont multipliées avec celles de l'écran.
+    Multiply,
+}
+
+impl BlendMode {
+    /// Retourne le mode correspondant à l'opérande de 080N, les valeurs inconnues donnant le mode
+    /// normal.
+    pub fn from_value(value: u8) -> Self {
+        match value {
+            1 => BlendMode::Alpha25,
+            2 => BlendMode::Alpha50,
+            3 => BlendMode::Additive,
+            4 => BlendMode::Multiply,
+            _ => BlendMode::Normal,
+        }
+    }
+
+    /// Mélange la couleur RGBA `src` sur la couleur `dest`.
+    fn blend(&self, dest: [u8; 4], src: [u8; 4]) -> [u8; 4] {
+        let mix = |dest: u8, src: u8, alpha: u32| -> u8 {
+            ((src as u32 * alpha + dest as u32 * (255 - alpha)) / 255) as u8
+        };
+
+        let alpha = match self {
+            BlendMode::Normal => src[3] as u32,
+            BlendMode::Alpha25 => 64,
+            BlendMode::Alpha50 => 128,
+            BlendMode::Additive => {
+                return [
+                    dest[0].saturating_add(src[0]),
+                    dest[1].saturating_add(src[1]),
+                    dest[2].saturating_add(src[2]),
+                    0xFF,
+                ];
+            }
+            BlendMode::Multiply => {
+                return [
+                    (dest[0] as u32 * src[0] as u32 / 255) as u8,
+                    (dest[1] as u32 * src[1] as u32 / 255) as u8,
+                    (dest[2] as u32 * src[2] as u32 / 255) as u8,
+                    0xFF,
+                ];
+            }
+        };
+
+        [
+            mix(dest[0], src[0], alpha),
+            mix(dest[1], src[1], alpha),
+            mix(dest[2], src[2], alpha),
+            0xFF,
+        ]
+    }
+}
+
+/// Couleur de l'écran avant que quoi que ce soit n'y soit dessiné.
+const BACKGROUND: [u8; 4] = [0x00, 0x00, 0x00, 0xFF];
+
+/// Affichage couleur du MEGA-CHIP.
+/// Les sprites sont dessinés dans une image de travail, qui n'est affichée qu'à l'instruction
+/// 00E0. Chaque pixel d'un sprite est un indice dans la palette, 0 étant transparent.
+pub struct MegaDisplay {
+    enabled: bool,
+    /// Couleurs RGBA chargées par 02NN.
+    palette: [[u8; 4]; PALETTE_SIZE],
+    sprite_width: usize,
+    sprite_height: usize,
+    /// Opacité de toute l'image affichée, utilisée pour les fondus.
+    alpha: u8,
+    blend_mode: BlendMode,
+    /// Indice de la couleur qui provoque une collision lorsqu'un sprite la recouvre. Aucune
+    /// collision n'est détectée tant que 09NN ne l'a pas choisie.
+    collision_color: Option<u8>,
+    /// Indice de la dernière couleur dessinée sur chaque pixel de l'image de travail.
+    indices: Vec<u8>,
+    /// Image de travail.
+    back: Vec<[u8; 4]>,
+    /// Dernière image affichée, en RGBA ligne par ligne.
+    front: Vec<u8>,
+}
+
+impl MegaDisplay {
+    pub fn new() -> Self {
+        let mut palette = [[0x00, 0x00, 0x00, 0xFF]; PALETTE_SIZE];
+        palette[0] = [0x00, 0x00, 0x00, 0x00];
+
+        Self {
+            enabled: false,
+            palette,
+            sprite_width: 8,
+            sprite_height: 8,
+            alpha: 0xFF,
+            blend_mode: BlendMode::Normal,
+            collision_color: None,
+            indices: vec![0; MEGA_WIDTH * MEGA_HEIGHT],
+            back: vec![BACKGROUND; MEGA_WIDTH * MEGA_HEIGHT],
+            front: BACKGROUND.repeat(MEGA_WIDTH * MEGA_HEIGHT),
+        }
+    }
+
+    /// Indique si le mode MEGA-CHIP a été activé par 0011.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Active ou désactive le mode MEGA-CHIP, ce qui efface l'écran.
+    pub(crate) fn set_enabled(&mut self, value: bool) {
+        self.enabled = value;
+
+        self.indices.fill(0);
+        self.back.fill(BACKGROUND);
+        self.front = BACKGROUND.repeat(MEGA_WIDTH * MEGA_HEIGHT);
+    }
+
+    pub fn palette(&self) -> &[[u8; 4]; PALETTE_SIZE] {
+        &self.palette
+    }
+
+    /// Change une couleur de la palette à partir de sa valeur ARGB en mémoire.
+    pub(crate) fn set_palette_color(&mut self, index: usize, argb: [u8; 4]) {
+        if let Some(color) = self.palette.get_mut(index) {
+            *color = [argb[1], argb[2], argb[3], argb[0]];
+        }
+    }
+
+    pub fn sprite_width(&self) -> usize {
+        self.sprite_width
+    }
+
+    /// Une largeur de 0 correspond à 256 pixels.
+    pub(crate) fn set_sprite_width(&mut self, value: u8) {
+        self.sprite_width = if value == 0 { 256 } else { value as usize };
+    }
+
+    pub fn sprite_height(&self) -> usize {
+        self.sprite_height
+    }
+
+    /// Une hauteur de 0 correspond à 256 pixels.
+    pub(crate) fn set_sprite_height(&mut self, value: u8) {
+        self.sprite_height = if value == 0 { 256 } else { value as usize };
+    }
+
+    pub fn alpha(&self) -> u8 {
+        self.alpha
+    }
+
+    pub(crate) fn set_alpha(&mut self, value: u8) {
+        self.alpha = value;
+    }
+
+    pub fn blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+
+    pub(crate) fn set_blend_mode(&mut self, value: BlendMode) {
+        self.blend_mode = value;
+    }
+
+    pub fn collision_color(&self) -> Option<u8> {
+        self.collision_color
+    }
+
+    pub(crate) fn set_collision_color(&mut self, value: u8) {
+        self.collision_color = Some(value);
+    }
+
+    /// Dessine un pixel de la couleur `index` dans l'image de travail et retourne `true` s'il
+    /// recouvre la couleur de collision.
+    pub(crate) fn draw_pixel(&mut self, x: usize, y: usize, index: u8) -> bool {
+        // La couleur 0 est transparente.
+        if index == 0 {
+            return false;
+        }
+
+        let position = y * MEGA_WIDTH + x;
+        let collision = self.collision_color == Some(self.indices[position]);
+
+        self.indices[position] = index;
+        self.back[position] = self.blend_mode.blend(self.back[position], self.palette[index as usize]);
+
+        collision
+    }
+
+    /// Affiche l'image de travail avec l'opacité de l'écran, puis l'efface.
+    pub(crate) fn present(&mut self) {
+        let alpha = self.alpha as u32;
+
+        for (dest, from) in self.front.chunks_exact_mut(4).zip(&self.back) {
+            dest[0] = (from[0] as u32 * alpha / 255) as u8;
+            dest[1] = (from[1] as u32 * alpha / 255) as u8;
+            dest[2] = (from[2] as u32 * alpha / 255) as u8;
+            dest[3] = 0xFF;
+        }
+
+        self.indices.fill(0);
+        self.back.fill(BACKGROUND);
+    }
+
+    /// Dernière image affichée, en RGBA ligne par ligne.
+    pub fn frame(&self) -> &[u8] {
+        &self.front
+    }
+
+    /// Décale l'image de travail de `dx` colonnes et `dy` lignes, les pixels qui sortent de
+    /// l'écran sont perdus.
+    pub(crate) fn shift(&mut self, dx: isize, dy: isize) {
+        let indices = self.indices.clone();
+        let back = self.back.clone();
+
+        for y in 0..MEGA_HEIGHT {
+            for x in 0..MEGA_WIDTH {
+                let from_x = x as isize - dx;
+                let from_y = y as isize - dy;
+                let position = y * MEGA_WIDTH + x;
+
+                if from_x >= 0 && from_y >= 0 && (from_x as usize) < MEGA_WIDTH && (from_y as usize) < MEGA_HEIGHT {
+                    let from = from_y as usize * MEGA_WIDTH + from_x as usize;
+
+                    self.indices[position] = indices[from];
+                    self.back[position] = back[from];
+                } else {
+                    self.indices[position] = 0;
+                    self.back[position] = BACKGROUND;
+                }
+            }
+        }
+    }
+}
+
+impl Default for MegaDisplay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
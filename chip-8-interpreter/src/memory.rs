@@ -1,29 +1,42 @@
+use crate::error::Chip8Error;
+
 pub struct Memory {
     data: Vec<u8>,
 }
 
 impl Memory {
-    pub fn new(size: u16) -> Self {
+    pub fn new(size: usize) -> Self {
         Self {
-            data: vec![0; size as usize],
+            data: vec![0; size],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    fn out_of_bounds(&self, offset: u32) -> Chip8Error {
+        Chip8Error::MemoryOutOfBounds {
+            address: offset as usize,
+            size: self.data.len(),
         }
     }
 
-    pub fn read8(&self, offset: u16) -> Result<u8, String> {
+    pub fn read8(&self, offset: u32) -> Result<u8, Chip8Error> {
         if offset as usize >= self.data.len() {
-            return Err(format!("trying to read offset {offset} of a {} length memory", self.data.len()));
+            return Err(self.out_of_bounds(offset));
         }
 
         Ok(self.data[offset as usize])
     }
 
-    pub fn read16(&self, offset: u16) -> Result<u16, String> {
-        if self.data.len() < 2 {
-            return Err("this memory doesn't have enough space to read from".to_string());
-        }
-
-        if offset as usize >= self.data.len() - 1 {
-            return Err(format!("trying to read offset {offset} of a {} length memory", self.data.len()));
+    pub fn read16(&self, offset: u32) -> Result<u16, Chip8Error> {
+        if offset as usize + 1 >= self.data.len() {
+            return Err(self.out_of_bounds(offset));
         }
 
         let msb = self.data[offset as usize];
@@ -34,9 +47,9 @@ impl Memory {
         Ok(value)
     }
 
-    pub fn write8(&mut self, offset: u16, value: u8) -> Result<(), String> {
+    pub fn write8(&mut self, offset: u32, value: u8) -> Result<(), Chip8Error> {
         if offset as usize >= self.data.len() {
-            return Err(format!("trying to write a 8-bits value at offset {offset} of a {} length memory", self.data.len()));
+            return Err(self.out_of_bounds(offset));
         }
 
         self.data[offset as usize] = value;
@@ -44,13 +57,9 @@ impl Memory {
         Ok(())
     }
 
-    pub fn write16(&mut self, offset: u16, value: u16) -> Result<(), String> {
-        if self.data.len() < 2 {
-            return Err("this memory doesn't have enough space to store this data".to_string());
-        }
-
-        if offset as usize >= self.data.len() - 1 {
-            return Err(format!("trying to write a 16-bits value at offset {offset} of a {} length memory", self.data.len()));
+    pub fn write16(&mut self, offset: u32, value: u16) -> Result<(), Chip8Error> {
+        if offset as usize + 1 >= self.data.len() {
+            return Err(self.out_of_bounds(offset));
         }
 
         let msb = ((value >> 8) & 0xFF) as u8;
@@ -62,17 +71,20 @@ impl Memory {
         Ok(())
     }
 
-    pub fn write8_range(&mut self, start: u16, end: u16, content: &[u8]) -> Result<(), String> {
+    pub fn write8_range(&mut self, start: u32, end: u32, content: &[u8]) -> Result<(), Chip8Error> {
         if start == end {
             return Ok(());
         }
 
         if start > end {
-            return Err("'start' parameter cannot be superior to 'end' parameter".to_string());
+            return Err(Chip8Error::InvalidRange {
+                start: start as usize,
+                end: end as usize,
+            });
         }
 
-        if end as usize >= self.data.len() {
-            return Err("range is out of bound from memory length".to_string());
+        if end as usize > self.data.len() {
+            return Err(self.out_of_bounds(end));
         }
 
         for (dest, from) in self.data[start as usize..end as usize].iter_mut().zip(content) {
@@ -81,4 +93,20 @@ impl Memory {
 
         Ok(())
     }
+
+    /// Copie tout `content` dans la mémoire à partir de l'adresse `start`.
+    pub fn load(&mut self, start: u32, content: &[u8]) -> Result<(), Chip8Error> {
+        let end = start as usize + content.len();
+
+        if end > self.data.len() {
+            return Err(Chip8Error::MemoryOutOfBounds {
+                address: end,
+                size: self.data.len(),
+            });
+        }
+
+        self.data[start as usize..end].copy_from_slice(content);
+
+        Ok(())
+    }
 }
\ No newline at end of file
@@ -0,0 +1,1031 @@
+use std::collections::HashMap;
+
+use crate::{error::Chip8Error, platform::Platform};
+
+/// Nombre maximal de macros développées, au-delà duquel une macro est considérée comme récursive.
+const MAX_MACRO_EXPANSIONS: usize = 0x10000;
+
+/// Mots réservés du langage, qui ne peuvent pas servir de noms.
+const KEYWORDS: [&str; 48] = [
+    ":", ":alias", ":byte", ":calc", ":call", ":const", ":macro", ":next", ":org", ":pointer", ":unpack", ":breakpoint",
+    ":monitor", ";", "return", "clear", "bcd", "save", "load", "sprite", "jump", "jump0", "native", "loop", "again",
+    "while", "if", "then", "begin", "else", "end", "key", "-key", "hex", "bighex", "long", "random", "delay", "buzzer",
+    "pitch", "hires", "lores", "exit", "saveflags", "loadflags", "plane", "audio", "i",
+];
+
+/// Opérateurs binaires de `:calc`, évalués de droite à gauche sans priorité comme dans Octo.
+const BINARY_OPERATORS: [&str; 19] = [
+    "-", "+", "*", "/", "%", "&", "|", "^", "<<", ">>", "pow", "min", "max", "<", "<=", "==", "!=", ">=", ">",
+];
+
+/// Opérateurs unaires de `:calc`.
+const UNARY_OPERATORS: [&str; 14] = [
+    "-", "~", "!", "sin", "cos", "tan", "exp", "log", "abs", "sqrt", "sign", "ceil", "floor", "@",
+];
+
+/// Mot du code source, avec la ligne et la colonne auxquelles il commence.
+#[derive(Clone, Debug)]
+struct Token {
+    text: String,
+    line: usize,
+    column: usize,
+}
+
+impl Token {
+    fn error(&self, message: String) -> Chip8Error {
+        Chip8Error::Assembly {
+            line: self.line,
+            column: self.column,
+            message,
+        }
+    }
+}
+
+/// Découpe le code source en mots séparés par des espaces, les commentaires commençant par `#`.
+fn tokenize(source: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+
+    for (index, line) in source.lines().enumerate() {
+        let mut start = None;
+
+        for (column, c) in line.char_indices().chain([(line.len(), ' ')]) {
+            match (start, c.is_whitespace()) {
+                (None, false) if c == '#' => break,
+                (None, false) => start = Some(column),
+                (Some(begin), true) => {
+                    tokens.push(Token {
+                        text: line[begin..column].to_string(),
+                        line: index + 1,
+                        column: begin + 1,
+                    });
+                    start = None;
+                }
+                _ => (),
+            }
+        }
+    }
+
+    tokens
+}
+
+/// Lit un nombre décimal, hexadécimal avec `0x` ou binaire avec `0b`, éventuellement négatif.
+fn parse_number(text: &str) -> Option<i64> {
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, text),
+    };
+
+    let value = if let Some(hex) = digits.strip_prefix("0x") {
+        i64::from_str_radix(hex, 16).ok()?
+    } else if let Some(binary) = digits.strip_prefix("0b") {
+        i64::from_str_radix(binary, 2).ok()?
+    } else if digits.starts_with(|c: char| c.is_ascii_digit()) {
+        digits.parse().ok()?
+    } else {
+        return None;
+    };
+
+    Some(if negative { -value } else { value })
+}
+
+fn parse_register(text: &str) -> Option<u8> {
+    let digit = text.strip_prefix(['v', 'V'])?;
+
+    if digit.len() == 1 {
+        u8::from_str_radix(digit, 16).ok()
+    } else {
+        None
+    }
+}
+
+/// Référence à un label qui n'est pas encore défini, corrigée lorsqu'il l'est.
+#[derive(Clone, Copy)]
+enum Reference {
+    /// Adresse sur 12 bits d'une instruction.
+    Address12,
+    /// Adresse sur 16 bits, après `i := long` ou `:pointer`.
+    Address16,
+    /// Octets des deux instructions générées par `:unpack`, avec le quartet de poids fort.
+    Unpack(u8),
+}
+
+struct PendingReference {
+    address: u32,
+    kind: Reference,
+    token: Token,
+}
+
+/// Bloc de contrôle en cours.
+enum Block {
+    /// `loop`, avec l'adresse des sauts générés par chaque `while`.
+    Loop { start: u32, token: Token, whiles: Vec<u32> },
+    /// `if ... begin`, avec l'adresse du saut vers `else` ou `end`.
+    If { jump: u32, token: Token },
+    /// `else`, avec l'adresse du saut vers `end`.
+    Else { jump: u32, token: Token },
+}
+
+struct Macro {
+    args: Vec<String>,
+    body: Vec<Token>,
+}
+
+struct Compiler {
+    /// Mots restant à compiler, dans l'ordre inverse.
+    tokens: Vec<Token>,
+    platform: Platform,
+    load_address: u32,
+    here: u32,
+    /// Contenu de la mémoire à partir de l'adresse 0, et octets déjà écrits.
+    memory: Vec<u8>,
+    written: Vec<bool>,
+    labels: HashMap<String, u32>,
+    constants: HashMap<String, f64>,
+    aliases: HashMap<String, u8>,
+    macros: HashMap<String, Macro>,
+    references: HashMap<String, Vec<PendingReference>>,
+    blocks: Vec<Block>,
+    /// Le programme commence par un saut vers `main`.
+    main_jump: bool,
+    expansions: usize,
+}
+
+impl Compiler {
+    fn new(source: &str, platform: Platform, load_address: u16) -> Self {
+        let mut tokens = tokenize(source);
+        tokens.reverse();
+
+        Self {
+            tokens,
+            platform,
+            load_address: load_address as u32,
+            // Un saut vers `main` est réservé au début du programme.
+            here: load_address as u32 + 2,
+            memory: vec![0x0; load_address as usize + 2],
+            written: vec![false; load_address as usize + 2],
+            labels: HashMap::new(),
+            constants: HashMap::new(),
+            aliases: HashMap::new(),
+            macros: HashMap::new(),
+            references: HashMap::new(),
+            blocks: Vec::new(),
+            main_jump: true,
+            expansions: 0,
+        }
+    }
+
+    fn next(&mut self, previous: &Token) -> Result<Token, Chip8Error> {
+        self.tokens
+            .pop()
+            .ok_or_else(|| previous.error(format!("Unexpected end of file after '{}'.", previous.text)))
+    }
+
+    fn peek(&self, offset: usize) -> Option<&str> {
+        let index = self.tokens.len().checked_sub(offset + 1)?;
+
+        Some(&self.tokens[index].text)
+    }
+
+    fn expect(&mut self, previous: &Token, text: &str) -> Result<Token, Chip8Error> {
+        let token = self.next(previous)?;
+
+        if token.text != text {
+            return Err(token.error(format!("Expected '{text}', got '{}'.", token.text)));
+        }
+
+        Ok(token)
+    }
+
+    fn emit(&mut self, byte: u8, token: &Token) -> Result<(), Chip8Error> {
+        let address = self.here as usize;
+
+        if address >= self.platform.memory_size() {
+            return Err(token.error(format!("The program does not fit in {} bytes of memory.", self.platform.memory_size())));
+        }
+
+        if address >= self.memory.len() {
+            self.memory.resize(address + 1, 0x0);
+            self.written.resize(address + 1, false);
+        }
+
+        if self.written[address] {
+            return Err(token.error(format!("Data overlap. Address 0x{address:04X} has already been defined.")));
+        }
+
+        self.memory[address] = byte;
+        self.written[address] = true;
+        self.here += 1;
+
+        Ok(())
+    }
+
+    fn instruction(&mut self, opcode: u16, token: &Token) -> Result<(), Chip8Error> {
+        self.emit((opcode >> 8) as u8, token)?;
+        self.emit(opcode as u8, token)
+    }
+
+    /// Refuse les instructions qui ne sont pas disponibles sur la plateforme.
+    fn require(&self, supported: bool, token: &Token) -> Result<(), Chip8Error> {
+        if supported {
+            Ok(())
+        } else {
+            Err(token.error(format!("The instruction '{}' is not supported on this platform.", token.text)))
+        }
+    }
+
+    fn is_reserved(&self, name: &str) -> bool {
+        KEYWORDS.contains(&name) || parse_register(name).is_some() || parse_number(name).is_some()
+    }
+
+    /// Lit le nom d'un label, d'une constante, d'un alias ou d'une macro à définir.
+    fn definition(&mut self, previous: &Token) -> Result<Token, Chip8Error> {
+        let name = self.next(previous)?;
+
+        if self.is_reserved(&name.text) || ["{", "}", "(", ")"].contains(&&name.text[..]) {
+            return Err(name.error(format!("The name '{}' is reserved.", name.text)));
+        }
+
+        if self.labels.contains_key(&name.text) || self.constants.contains_key(&name.text) || self.aliases.contains_key(&name.text) {
+            return Err(name.error(format!("The name '{}' has already been defined.", name.text)));
+        }
+
+        Ok(name)
+    }
+
+    fn register(&mut self, previous: &Token) -> Result<u8, Chip8Error> {
+        let token = self.next(previous)?;
+
+        self.register_value(&token)
+            .ok_or_else(|| token.error(format!("Expected a register, got '{}'.", token.text)))
+    }
+
+    fn register_value(&self, token: &Token) -> Option<u8> {
+        parse_register(&token.text).or(self.aliases.get(&token.text).copied())
+    }
+
+    fn is_register(&self, offset: usize) -> bool {
+        self.peek(offset)
+            .is_some_and(|text| parse_register(text).is_some() || self.aliases.contains_key(text))
+    }
+
+    /// Valeur d'un nombre, d'une constante ou d'un label déjà défini.
+    fn known_value(&self, text: &str) -> Option<i64> {
+        parse_number(text)
+            .or(self.constants.get(text).map(|value| value.floor() as i64))
+            .or(self.labels.get(text).map(|address| *address as i64))
+    }
+
+    fn value(&mut self, previous: &Token, min: i64, max: i64, description: &str) -> Result<i64, Chip8Error> {
+        let token = self.next(previous)?;
+
+        let value = self
+            .known_value(&token.text)
+            .ok_or_else(|| token.error(format!("Undefined name '{}'.", token.text)))?;
+
+        if value < min || value > max {
+            return Err(token.error(format!("Value '{}' does not fit in {description}.", token.text)));
+        }
+
+        Ok(value)
+    }
+
+    fn byte(&mut self, previous: &Token) -> Result<u8, Chip8Error> {
+        Ok(self.value(previous, -128, 255, "a byte")? as u8)
+    }
+
+    fn nibble(&mut self, previous: &Token) -> Result<u8, Chip8Error> {
+        Ok(self.value(previous, 0, 15, "4 bits")? as u8)
+    }
+
+    /// Lit une adresse, qui peut être un label défini plus loin. `at` est l'adresse des octets
+    /// à corriger lorsque le label sera défini.
+    fn address(&mut self, previous: &Token, kind: Reference, at: u32) -> Result<u32, Chip8Error> {
+        let token = self.next(previous)?;
+
+        match self.known_value(&token.text) {
+            Some(value) => {
+                check_reference(value, kind, &token)?;
+                Ok(value as u32)
+            }
+            None if self.is_reserved(&token.text) => Err(token.error(format!("Expected an address, got '{}'.", token.text))),
+            None => {
+                self.references.entry(token.text.clone()).or_default().push(PendingReference {
+                    address: at,
+                    kind,
+                    token,
+                });
+
+                Ok(0)
+            }
+        }
+    }
+
+    fn patch(&mut self, address: u32, value: u32, kind: Reference) {
+        let address = address as usize;
+
+        match kind {
+            Reference::Address12 => {
+                self.memory[address] = (self.memory[address] & 0xF0) | ((value >> 8) & 0xF) as u8;
+                self.memory[address + 1] = value as u8;
+            }
+            Reference::Address16 => {
+                self.memory[address] = (value >> 8) as u8;
+                self.memory[address + 1] = value as u8;
+            }
+            Reference::Unpack(nibble) => {
+                self.memory[address + 1] = (nibble << 4) | ((value >> 8) & 0xF) as u8;
+                self.memory[address + 3] = value as u8;
+            }
+        }
+    }
+
+    fn define_label(&mut self, name: &Token, address: u32) -> Result<(), Chip8Error> {
+        self.labels.insert(name.text.clone(), address);
+
+        for reference in self.references.remove(&name.text).unwrap_or_default() {
+            check_reference(address as i64, reference.kind, &reference.token)?;
+            self.patch(reference.address, address, reference.kind);
+        }
+
+        Ok(())
+    }
+
+    /// Saut dont la cible sera corrigée à la fin du bloc.
+    fn forward_jump(&mut self, token: &Token) -> Result<u32, Chip8Error> {
+        let address = self.here;
+        self.instruction(0x1000, token)?;
+
+        Ok(address)
+    }
+
+    fn resolve_jump(&mut self, jump: u32, token: &Token) -> Result<(), Chip8Error> {
+        check_reference(self.here as i64, Reference::Address12, token)?;
+        self.patch(jump, self.here, Reference::Address12);
+
+        Ok(())
+    }
+
+    /// Génère les instructions qui ignorent l'instruction suivante lorsque la condition est
+    /// fausse, ou lorsqu'elle est vraie si `negated` est vrai.
+    fn conditional(&mut self, token: &Token, negated: bool) -> Result<(), Chip8Error> {
+        let x = self.register(token)? as u16;
+        let operator = self.next(token)?;
+
+        let mut text = &operator.text[..];
+        if negated {
+            text = match text {
+                "==" => "!=",
+                "!=" => "==",
+                "key" => "-key",
+                "-key" => "key",
+                "<" => ">=",
+                ">" => "<=",
+                ">=" => "<",
+                "<=" => ">",
+                _ => text,
+            };
+        }
+
+        match text {
+            "==" | "!=" => {
+                let skip_if_equal = text == "!=";
+
+                if self.is_register(0) {
+                    let y = self.register(&operator)? as u16;
+                    let opcode = if skip_if_equal { 0x5000 } else { 0x9000 };
+
+                    self.instruction(opcode | x << 8 | y << 4, &operator)
+                } else {
+                    let byte = self.byte(&operator)? as u16;
+                    let opcode = if skip_if_equal { 0x3000 } else { 0x4000 };
+
+                    self.instruction(opcode | x << 8 | byte, &operator)
+                }
+            }
+            "key" => self.instruction(0xE0A1 | x << 8, &operator),
+            "-key" => self.instruction(0xE09E | x << 8, &operator),
+            "<" | ">" | "<=" | ">=" => {
+                // VF reçoit l'opérande de droite puis la retenue de la soustraction.
+                if self.is_register(0) {
+                    let y = self.register(&operator)? as u16;
+                    self.instruction(0x8F00 | y << 4, &operator)?;
+                } else {
+                    let byte = self.byte(&operator)? as u16;
+                    self.instruction(0x6F00 | byte, &operator)?;
+                }
+
+                let subtract = if matches!(text, ">" | "<=") { 0x8F05 } else { 0x8F07 };
+                let skip = if matches!(text, "<" | ">") { 0x3F01 } else { 0x4F01 };
+
+                self.instruction(subtract | x << 4, &operator)?;
+                self.instruction(skip, &operator)
+            }
+            _ => Err(operator.error(format!("Expected a conditional operator, got '{}'.", operator.text))),
+        }
+    }
+
+    /// Compile les mots entre `{` et `}` avec le langage de `:calc`.
+    fn calc(&mut self, previous: &Token) -> Result<f64, Chip8Error> {
+        let open = self.expect(previous, "{")?;
+        let mut tokens = Vec::new();
+
+        loop {
+            let token = self.next(&open)?;
+            if token.text == "}" {
+                break;
+            }
+            tokens.push(token);
+        }
+
+        let mut position = 0;
+        let value = self.calc_expression(&tokens, &mut position, &open)?;
+
+        match tokens.get(position) {
+            Some(token) => Err(token.error(format!("Unexpected '{}' in expression.", token.text))),
+            None => Ok(value),
+        }
+    }
+
+    fn calc_expression(&self, tokens: &[Token], position: &mut usize, open: &Token) -> Result<f64, Chip8Error> {
+        let left = self.calc_term(tokens, position, open)?;
+
+        let Some(operator) = tokens.get(*position).filter(|token| BINARY_OPERATORS.contains(&&token.text[..])) else {
+            return Ok(left);
+        };
+
+        *position += 1;
+        let right = self.calc_expression(tokens, position, open)?;
+
+        let (a, b) = (left as i64, right as i64);
+        let boolean = |value: bool| if value { 1.0 } else { 0.0 };
+
+        Ok(match &operator.text[..] {
+            "-" => left - right,
+            "+" => left + right,
+            "*" => left * right,
+            "/" => left / right,
+            "%" => left % right,
+            "&" => (a & b) as f64,
+            "|" => (a | b) as f64,
+            "^" => (a ^ b) as f64,
+            "<<" => a.checked_shl(b as u32).unwrap_or(0) as f64,
+            ">>" => a.checked_shr(b as u32).unwrap_or(0) as f64,
+            "pow" => left.powf(right),
+            "min" => left.min(right),
+            "max" => left.max(right),
+            "<" => boolean(left < right),
+            "<=" => boolean(left <= right),
+            "==" => boolean(left == right),
+            "!=" => boolean(left != right),
+            ">=" => boolean(left >= right),
+            _ => boolean(left > right),
+        })
+    }
+
+    fn calc_term(&self, tokens: &[Token], position: &mut usize, open: &Token) -> Result<f64, Chip8Error> {
+        let token = tokens
+            .get(*position)
+            .ok_or_else(|| open.error("Expected a value in expression.".to_string()))?;
+        *position += 1;
+
+        if token.text == "(" {
+            let value = self.calc_expression(tokens, position, open)?;
+
+            return match tokens.get(*position) {
+                Some(close) if close.text == ")" => {
+                    *position += 1;
+                    Ok(value)
+                }
+                _ => Err(token.error("This '(' does not have a matching ')'.".to_string())),
+            };
+        }
+
+        if UNARY_OPERATORS.contains(&&token.text[..]) && parse_number(&token.text).is_none() {
+            let value = self.calc_term(tokens, position, open)?;
+
+            return Ok(match &token.text[..] {
+                "-" => -value,
+                "~" => !(value as i64) as f64,
+                "!" => if value == 0.0 { 1.0 } else { 0.0 },
+                "sin" => value.sin(),
+                "cos" => value.cos(),
+                "tan" => value.tan(),
+                "exp" => value.exp(),
+                "log" => value.ln(),
+                "abs" => value.abs(),
+                "sqrt" => value.sqrt(),
+                "sign" => if value == 0.0 { 0.0 } else { value.signum() },
+                "ceil" => value.ceil(),
+                "floor" => value.floor(),
+                // Octet déjà compilé à l'adresse donnée.
+                _ => self.memory.get(value as usize).copied().unwrap_or(0) as f64,
+            });
+        }
+
+        match &token.text[..] {
+            "HERE" => Ok(self.here as f64),
+            "PI" => Ok(std::f64::consts::PI),
+            "E" => Ok(std::f64::consts::E),
+            text => parse_number(text)
+                .map(|value| value as f64)
+                .or(self.constants.get(text).copied())
+                .or(self.labels.get(text).map(|address| *address as f64))
+                .ok_or_else(|| token.error(format!("Undefined name '{text}'."))),
+        }
+    }
+
+    fn define_macro(&mut self, token: &Token) -> Result<(), Chip8Error> {
+        let name = self.definition(token)?;
+        let mut args = Vec::new();
+
+        loop {
+            let arg = self.next(&name)?;
+            if arg.text == "{" {
+                break;
+            }
+            args.push(arg.text);
+        }
+
+        let mut body = Vec::new();
+        let mut depth = 1;
+
+        loop {
+            let token = self.next(&name)?;
+
+            match &token.text[..] {
+                "{" => depth += 1,
+                "}" => depth -= 1,
+                _ => (),
+            }
+
+            if depth == 0 {
+                break;
+            }
+
+            body.push(token);
+        }
+
+        self.macros.insert(name.text, Macro { args, body });
+
+        Ok(())
+    }
+
+    /// Remplace l'appel d'une macro par son contenu, chaque argument étant remplacé par le mot
+    /// donné lors de l'appel.
+    fn expand_macro(&mut self, token: &Token) -> Result<(), Chip8Error> {
+        self.expansions += 1;
+        if self.expansions > MAX_MACRO_EXPANSIONS {
+            return Err(token.error(format!("Too many expansions of the macro '{}'.", token.text)));
+        }
+
+        let count = self.macros[&token.text].args.len();
+        let mut values = HashMap::new();
+
+        for index in 0..count {
+            let value = self.next(token)?;
+            values.insert(self.macros[&token.text].args[index].clone(), value);
+        }
+
+        let body: Vec<Token> = self.macros[&token.text]
+            .body
+            .iter()
+            .map(|body_token| values.get(&body_token.text).cloned().unwrap_or_else(|| body_token.clone()))
+            .collect();
+
+        self.tokens.extend(body.into_iter().rev());
+
+        Ok(())
+    }
+
+    fn statement(&mut self) -> Result<(), Chip8Error> {
+        let Some(token) = self.tokens.pop() else {
+            return Ok(());
+        };
+
+        let superchip = self.platform.supports_superchip();
+        let xo_chip = self.platform.supports_xo_chip();
+
+        match &token.text[..] {
+            ":" => {
+                let name = self.definition(&token)?;
+
+                // `main` placé au tout début du programme rend inutile le saut initial.
+                if name.text == "main" && self.main_jump && self.here == self.load_address + 2 {
+                    self.main_jump = false;
+                    self.here = self.load_address;
+                }
+
+                let here = self.here;
+                self.define_label(&name, here)?;
+            }
+            ":next" => {
+                // Le label désigne le second octet de l'instruction suivante.
+                let name = self.definition(&token)?;
+                let here = self.here + 1;
+                self.define_label(&name, here)?;
+            }
+            ":alias" => {
+                let name = self.definition(&token)?;
+                let register = self.register(&name)?;
+                self.aliases.insert(name.text, register);
+            }
+            ":const" => {
+                let name = self.definition(&token)?;
+                let value = self.value(&name, i64::MIN, i64::MAX, "a constant")?;
+                self.constants.insert(name.text, value as f64);
+            }
+            ":calc" => {
+                let name = self.definition(&token)?;
+                let value = self.calc(&name)?;
+                self.constants.insert(name.text, value);
+            }
+            ":macro" => self.define_macro(&token)?,
+            ":org" => {
+                let address = self.value(&token, 0, self.platform.memory_size() as i64 - 1, "the memory")?;
+                self.here = address as u32;
+            }
+            ":byte" => {
+                let byte = if self.peek(0) == Some("{") {
+                    self.calc(&token)?.floor() as i64 as u8
+                } else {
+                    self.byte(&token)?
+                };
+
+                self.emit(byte, &token)?;
+            }
+            ":pointer" => {
+                let here = self.here;
+                let address = self.address(&token, Reference::Address16, here)?;
+                self.instruction(address as u16, &token)?;
+            }
+            ":unpack" => {
+                let nibble = self.nibble(&token)?;
+                let here = self.here;
+                let address = self.address(&token, Reference::Unpack(nibble), here)?;
+
+                self.instruction(0x6000 | (nibble as u16) << 4 | (address >> 8) as u16 & 0xF, &token)?;
+                self.instruction(0x6100 | address as u16 & 0xFF, &token)?;
+            }
+            ":call" => {
+                let here = self.here;
+                let address = self.address(&token, Reference::Address12, here)?;
+                self.instruction(0x2000 | address as u16, &token)?;
+            }
+            ":breakpoint" => {
+                // Les points d'arrêt ne sont utilisés que par le débogueur d'Octo.
+                self.next(&token)?;
+            }
+            ":monitor" => {
+                self.next(&token)?;
+                self.next(&token)?;
+            }
+            ";" | "return" => self.instruction(0x00EE, &token)?,
+            "clear" => self.instruction(0x00E0, &token)?,
+            "bcd" => {
+                let x = self.register(&token)? as u16;
+                self.instruction(0xF033 | x << 8, &token)?;
+            }
+            "save" | "load" => {
+                let x = self.register(&token)? as u16;
+
+                if self.peek(0) == Some("-") {
+                    self.require(xo_chip, &token)?;
+                    let dash = self.next(&token)?;
+                    let y = self.register(&dash)? as u16;
+                    let opcode = if token.text == "save" { 0x5002 } else { 0x5003 };
+
+                    self.instruction(opcode | x << 8 | y << 4, &token)?;
+                } else {
+                    let opcode = if token.text == "save" { 0xF055 } else { 0xF065 };
+                    self.instruction(opcode | x << 8, &token)?;
+                }
+            }
+            "saveflags" | "loadflags" => {
+                self.require(superchip, &token)?;
+                let x = self.register(&token)?;
+
+                if x as usize >= self.platform.rpl_flags_count() {
+                    return Err(token.error(format!("The register v{x:x} is greater than the number of flags.")));
+                }
+
+                let opcode = if token.text == "saveflags" { 0xF075 } else { 0xF085 };
+                self.instruction(opcode | (x as u16) << 8, &token)?;
+            }
+            "sprite" => {
+                let x = self.register(&token)? as u16;
+                let y = self.register(&token)? as u16;
+                let height = self.nibble(&token)? as u16;
+
+                self.instruction(0xD000 | x << 8 | y << 4 | height, &token)?;
+            }
+            "jump" | "jump0" | "native" => {
+                let here = self.here;
+                let address = self.address(&token, Reference::Address12, here)? as u16;
+                let opcode = match &token.text[..] {
+                    "jump" => 0x1000,
+                    "jump0" => 0xB000,
+                    _ => 0x0000,
+                };
+
+                self.instruction(opcode | address, &token)?;
+            }
+            "hires" | "lores" | "scroll-left" | "scroll-right" | "exit" => {
+                self.require(superchip, &token)?;
+                let opcode = match &token.text[..] {
+                    "hires" => 0x00FF,
+                    "lores" => 0x00FE,
+                    "scroll-left" => 0x00FC,
+                    "scroll-right" => 0x00FB,
+                    _ => 0x00FD,
+                };
+
+                self.instruction(opcode, &token)?;
+            }
+            "scroll-down" => {
+                self.require(superchip, &token)?;
+                let rows = self.nibble(&token)? as u16;
+                self.instruction(0x00C0 | rows, &token)?;
+            }
+            "scroll-up" => {
+                // Le MEGA-CHIP utilise un autre opcode que le XO-CHIP.
+                let opcode = if self.platform.supports_megachip() { 0x00B0 } else { 0x00D0 };
+                self.require(xo_chip || self.platform.supports_megachip(), &token)?;
+                let rows = self.nibble(&token)? as u16;
+                self.instruction(opcode | rows, &token)?;
+            }
+            "plane" => {
+                self.require(xo_chip, &token)?;
+                let planes = self.nibble(&token)? as u16;
+                self.instruction(0xF001 | planes << 8, &token)?;
+            }
+            "audio" => {
+                self.require(xo_chip, &token)?;
+                self.instruction(0xF002, &token)?;
+            }
+            "loop" => {
+                let start = self.here;
+                self.blocks.push(Block::Loop {
+                    start,
+                    token,
+                    whiles: Vec::new(),
+                });
+            }
+            "while" => {
+                if !self.blocks.iter().any(|block| matches!(block, Block::Loop { .. })) {
+                    return Err(token.error("This 'while' is not within a loop.".to_string()));
+                }
+
+                // La boucle est quittée lorsque la condition est fausse.
+                self.conditional(&token, true)?;
+                let jump = self.forward_jump(&token)?;
+
+                if let Some(Block::Loop { whiles, .. }) = self.blocks.iter_mut().rev().find(|block| matches!(block, Block::Loop { .. })) {
+                    whiles.push(jump);
+                }
+            }
+            "again" => match self.blocks.pop() {
+                Some(Block::Loop { start, whiles, .. }) => {
+                    self.instruction(0x1000 | start as u16, &token)?;
+
+                    for jump in whiles {
+                        self.resolve_jump(jump, &token)?;
+                    }
+                }
+                _ => return Err(token.error("This 'again' does not have a matching 'loop'.".to_string())),
+            },
+            "if" => {
+                // La condition fait 2 mots avec key et -key, 3 sinon.
+                let keyword_offset = if matches!(self.peek(1), Some("key" | "-key")) { 2 } else { 3 };
+
+                match self.peek(keyword_offset) {
+                    Some("then") => {
+                        self.conditional(&token, false)?;
+                        self.next(&token)?;
+                    }
+                    Some("begin") => {
+                        self.conditional(&token, true)?;
+                        self.next(&token)?;
+                        let jump = self.forward_jump(&token)?;
+                        self.blocks.push(Block::If { jump, token });
+                    }
+                    _ => return Err(token.error("Expected 'then' or 'begin' after the condition of this 'if'.".to_string())),
+                }
+            }
+            "else" => match self.blocks.pop() {
+                Some(Block::If { jump, .. }) => {
+                    let end_jump = self.forward_jump(&token)?;
+                    self.resolve_jump(jump, &token)?;
+                    self.blocks.push(Block::Else { jump: end_jump, token });
+                }
+                _ => return Err(token.error("This 'else' does not have a matching 'begin'.".to_string())),
+            },
+            "end" => match self.blocks.pop() {
+                Some(Block::If { jump, .. } | Block::Else { jump, .. }) => self.resolve_jump(jump, &token)?,
+                _ => return Err(token.error("This 'end' does not have a matching 'begin'.".to_string())),
+            },
+            "delay" | "buzzer" | "pitch" => {
+                self.expect(&token, ":=")?;
+                let x = self.register(&token)? as u16;
+                let opcode = match &token.text[..] {
+                    "delay" => 0xF015,
+                    "buzzer" => 0xF018,
+                    _ => {
+                        self.require(xo_chip, &token)?;
+                        0xF03A
+                    }
+                };
+
+                self.instruction(opcode | x << 8, &token)?;
+            }
+            "i" => self.i_statement(&token)?,
+            _ if self.register_value(&token).is_some() => self.register_statement(&token)?,
+            text if self.macros.contains_key(text) => self.expand_macro(&token)?,
+            text if self.known_value(text).is_some() && !self.labels.contains_key(text) => {
+                // Les nombres et les constantes seuls sont des octets de données.
+                let value = self.known_value(text).unwrap_or(0);
+
+                if !(-128..=255).contains(&value) {
+                    return Err(token.error(format!("Value '{text}' does not fit in a byte.")));
+                }
+
+                self.emit(value as u8, &token)?;
+            }
+            text if self.is_reserved(text) || ["{", "}"].contains(&text) => {
+                return Err(token.error(format!("Unexpected '{text}'.")));
+            }
+            _ => {
+                // Un nom seul appelle la fonction qui porte ce nom.
+                self.tokens.push(token.clone());
+                let here = self.here;
+                let address = self.address(&token, Reference::Address12, here)?;
+                self.instruction(0x2000 | address as u16, &token)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn i_statement(&mut self, token: &Token) -> Result<(), Chip8Error> {
+        let operator = self.next(token)?;
+
+        match &operator.text[..] {
+            ":=" => match self.peek(0) {
+                Some("hex") => {
+                    self.next(&operator)?;
+                    let x = self.register(&operator)? as u16;
+                    self.instruction(0xF029 | x << 8, token)
+                }
+                Some("bighex") => {
+                    let keyword = self.next(&operator)?;
+                    self.require(self.platform.supports_superchip(), &keyword)?;
+                    let x = self.register(&operator)? as u16;
+                    self.instruction(0xF030 | x << 8, token)
+                }
+                Some("long") => {
+                    let keyword = self.next(&operator)?;
+                    self.require(self.platform.supports_xo_chip(), &keyword)?;
+                    let here = self.here;
+                    let address = self.address(&keyword, Reference::Address16, here + 2)?;
+
+                    self.instruction(0xF000, token)?;
+                    self.instruction(address as u16, token)
+                }
+                _ => {
+                    let here = self.here;
+                    let address = self.address(&operator, Reference::Address12, here)?;
+                    self.instruction(0xA000 | address as u16, token)
+                }
+            },
+            "+=" => {
+                let x = self.register(&operator)? as u16;
+                self.instruction(0xF01E | x << 8, token)
+            }
+            _ => Err(operator.error(format!("Expected ':=' or '+=' after 'i', got '{}'.", operator.text))),
+        }
+    }
+
+    fn register_statement(&mut self, token: &Token) -> Result<(), Chip8Error> {
+        let x = self.register_value(token).unwrap_or(0) as u16;
+        let operator = self.next(token)?;
+
+        if self.is_register(0) {
+            let y = self.register(&operator)? as u16;
+            let operation = match &operator.text[..] {
+                ":=" => 0x0,
+                "|=" => 0x1,
+                "&=" => 0x2,
+                "^=" => 0x3,
+                "+=" => 0x4,
+                "-=" => 0x5,
+                ">>=" => 0x6,
+                "=-" => 0x7,
+                "<<=" => 0xE,
+                _ => return Err(operator.error(format!("Unknown register operator '{}'.", operator.text))),
+            };
+
+            return self.instruction(0x8000 | x << 8 | y << 4 | operation, &operator);
+        }
+
+        match &operator.text[..] {
+            ":=" => match self.peek(0) {
+                Some("random") => {
+                    let keyword = self.next(&operator)?;
+                    let mask = self.byte(&keyword)? as u16;
+                    self.instruction(0xC000 | x << 8 | mask, &operator)
+                }
+                Some("key") => {
+                    self.next(&operator)?;
+                    self.instruction(0xF00A | x << 8, &operator)
+                }
+                Some("delay") => {
+                    self.next(&operator)?;
+                    self.instruction(0xF007 | x << 8, &operator)
+                }
+                _ => {
+                    let byte = self.byte(&operator)? as u16;
+                    self.instruction(0x6000 | x << 8 | byte, &operator)
+                }
+            },
+            "+=" => {
+                let byte = self.byte(&operator)? as u16;
+                self.instruction(0x7000 | x << 8 | byte, &operator)
+            }
+            "-=" => {
+                let byte = self.byte(&operator)?.wrapping_neg() as u16;
+                self.instruction(0x7000 | x << 8 | byte, &operator)
+            }
+            _ => Err(operator.error(format!("Unknown register operator '{}'.", operator.text))),
+        }
+    }
+
+    fn finish(mut self) -> Result<Vec<u8>, Chip8Error> {
+        if let Some(block) = self.blocks.pop() {
+            return Err(match block {
+                Block::Loop { token, .. } => token.error("This 'loop' does not have a matching 'again'.".to_string()),
+                Block::If { token, .. } | Block::Else { token, .. } => token.error("This 'begin' does not have a matching 'end'.".to_string()),
+            });
+        }
+
+        if let Some(reference) = self.references.values().flatten().min_by_key(|reference| (reference.token.line, reference.token.column)) {
+            return Err(reference.token.error(format!("Undefined name '{}'.", reference.token.text)));
+        }
+
+        if self.main_jump {
+            let main = match self.labels.get("main") {
+                Some(t) => *t,
+                None => {
+                    return Err(Chip8Error::Assembly {
+                        line: 1,
+                        column: 1,
+                        message: "This program is missing a 'main' label.".to_string(),
+                    })
+                }
+            };
+
+            if main > 0xFFF {
+                return Err(Chip8Error::Assembly {
+                    line: 1,
+                    column: 1,
+                    message: "The 'main' label must be in the first 4096 bytes of memory.".to_string(),
+                });
+            }
+
+            let start = self.load_address as usize;
+            self.memory[start..start + 2].copy_from_slice(&(0x1000 | main as u16).to_be_bytes());
+            self.written[start] = true;
+        }
+
+        // Le programme s'arrête au dernier octet écrit.
+        let end = self.written.iter().rposition(|&written| written).map_or(0, |index| index + 1);
+        let start = (self.load_address as usize).min(end);
+
+        Ok(self.memory[start..end].to_vec())
+    }
+}
+
+/// Vérifie que `value` tient dans les bits corrigés par la référence.
+fn check_reference(value: i64, kind: Reference, token: &Token) -> Result<(), Chip8Error> {
+    let (max, description) = match kind {
+        Reference::Address12 | Reference::Unpack(_) => (0xFFF, "12 bits"),
+        Reference::Address16 => (0xFFFF, "16 bits"),
+    };
+
+    if !(0..=max).contains(&value) {
+        return Err(token.error(format!("Value '{}' does not fit in {description}.", token.text)));
+    }
+
+    Ok(())
+}
+
+/// Compile un programme écrit en Octo, chargé à `load_address`.
+/// Les instructions du SUPER-CHIP et du XO-CHIP ne sont acceptées que si `platform` les
+/// reconnaît. Les erreurs indiquent la ligne et la colonne du mot en cause.
+pub fn compile(source: &str, platform: Platform, load_address: u16) -> Result<Vec<u8>, Chip8Error> {
+    let mut compiler = Compiler::new(source, platform, load_address);
+
+    while !compiler.tokens.is_empty() {
+        compiler.statement()?;
+    }
+
+    compiler.finish()
+}
@@ -0,0 +1,149 @@
+use crate::{
+    chip::{DEFAULT_LOAD_ADDRESS, DEFAULT_MEMORY_SIZE},
+    megachip::{MEGA_HEIGHT, MEGA_MEMORY_SIZE, MEGA_WIDTH},
+    quirks::Quirks,
+    screen::{HIRES_CHIP8_HEIGHT, HIRES_CHIP8_WIDTH, HIRES_HEIGHT, HIRES_WIDTH, LORES_HEIGHT, LORES_WIDTH},
+};
+
+/// Premières instructions des programmes CHIP-8 hires, qui sautaient dans la partie modifiée de
+/// l'interpréteur chargée avec eux.
+pub const HIRES_CHIP8_HEADER: [u8; 2] = [0x12, 0x60];
+
+/// Adresse à laquelle commence réellement un programme CHIP-8 hires, après son en-tête et le code
+/// de l'interpréteur.
+pub const HIRES_CHIP8_START_ADDRESS: u16 = 0x2C0;
+
+/// Adresse à laquelle sont chargés les programmes CHIP-8X.
+pub const CHIP8X_LOAD_ADDRESS: u16 = 0x300;
+
+/// Jeu d'instructions reconnu par l'interpréteur.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Platform {
+    /// CHIP-8 d'origine.
+    #[default]
+    Chip8,
+    /// CHIP-8 hires du COSMAC VIP : écran de 64x64 pixels pour les programmes sur deux pages.
+    Chip8Hires,
+    /// CHIP-8X de la carte couleur RCA VP-590 : zones de couleur et second clavier.
+    Chip8X,
+    /// SUPER-CHIP 1.1 : haute résolution, défilement de l'écran et grands sprites.
+    SuperChip,
+    /// MEGA-CHIP : extension du SUPER-CHIP avec un écran de 256x192 pixels en couleurs et des
+    /// sons numérisés.
+    MegaChip,
+    /// XO-CHIP : extension du SUPER-CHIP avec 64 Kio de mémoire.
+    XoChip,
+}
+
+impl Platform {
+    /// Retourne la plateforme correspondant au nom donné en ligne de commande.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "chip8" => Some(Platform::Chip8),
+            "chip8-hires" => Some(Platform::Chip8Hires),
+            "chip8x" => Some(Platform::Chip8X),
+            "schip" => Some(Platform::SuperChip),
+            "megachip" => Some(Platform::MegaChip),
+            "xochip" => Some(Platform::XoChip),
+            _ => None,
+        }
+    }
+
+    /// Reconnaît les programmes CHIP-8 hires à leur en-tête lorsque le CHIP-8 classique est
+    /// demandé. Les autres plateformes sont retournées telles quelles.
+    pub fn detect(self, rom: &[u8], load_address: u16) -> Self {
+        if self == Platform::Chip8 && load_address == DEFAULT_LOAD_ADDRESS && rom.starts_with(&HIRES_CHIP8_HEADER) {
+            Platform::Chip8Hires
+        } else {
+            self
+        }
+    }
+
+    /// Interprétation des instructions ambiguës attendue par les programmes de la plateforme.
+    pub fn default_quirks(&self) -> Quirks {
+        match self {
+            Platform::Chip8 | Platform::Chip8Hires | Platform::Chip8X => Quirks::default(),
+            Platform::SuperChip | Platform::MegaChip => Quirks::superchip_modern(),
+            Platform::XoChip => Quirks::xo_chip(),
+        }
+    }
+
+    /// Nombre de drapeaux RPL accessibles par FX75 et FX85.
+    pub fn rpl_flags_count(&self) -> usize {
+        match self {
+            Platform::Chip8 | Platform::Chip8Hires | Platform::Chip8X => 0,
+            Platform::SuperChip | Platform::MegaChip => 8,
+            Platform::XoChip => 16,
+        }
+    }
+
+    /// Taille de la RAM adressable par les programmes.
+    pub fn memory_size(&self) -> usize {
+        match self {
+            Platform::MegaChip => MEGA_MEMORY_SIZE,
+            Platform::XoChip => 0x10000,
+            _ => DEFAULT_MEMORY_SIZE,
+        }
+    }
+
+    /// Adresse à laquelle sont chargés les programmes, l'interpréteur du CHIP-8X occupant une
+    /// page de plus que celui du CHIP-8.
+    pub fn load_address(&self) -> u16 {
+        match self {
+            Platform::Chip8X => CHIP8X_LOAD_ADDRESS,
+            _ => DEFAULT_LOAD_ADDRESS,
+        }
+    }
+
+    /// Résolution de l'écran au démarrage.
+    pub fn screen_size(&self) -> (usize, usize) {
+        match self {
+            Platform::Chip8Hires => (HIRES_CHIP8_WIDTH, HIRES_CHIP8_HEIGHT),
+            _ => (LORES_WIDTH, LORES_HEIGHT),
+        }
+    }
+
+    /// Plus grande résolution que peut prendre l'écran pendant l'exécution.
+    pub fn max_screen_size(&self) -> (usize, usize) {
+        if self.supports_megachip() {
+            (MEGA_WIDTH, MEGA_HEIGHT)
+        } else if self.supports_superchip() {
+            (HIRES_WIDTH, HIRES_HEIGHT)
+        } else {
+            self.screen_size()
+        }
+    }
+
+    /// Adresse de la première instruction exécutée pour un programme chargé à `load_address`.
+    pub fn start_address(&self, load_address: u16) -> u16 {
+        match self {
+            Platform::Chip8Hires => HIRES_CHIP8_START_ADDRESS,
+            _ => load_address,
+        }
+    }
+
+    /// Indique si les instructions du SUPER-CHIP sont disponibles.
+    pub fn supports_superchip(&self) -> bool {
+        matches!(self, Platform::SuperChip | Platform::MegaChip | Platform::XoChip)
+    }
+
+    /// Indique si les instructions du MEGA-CHIP sont disponibles.
+    pub fn supports_megachip(&self) -> bool {
+        matches!(self, Platform::MegaChip)
+    }
+
+    /// Indique si les instructions de couleur et du second clavier du CHIP-8X sont disponibles.
+    pub fn supports_chip8x(&self) -> bool {
+        matches!(self, Platform::Chip8X)
+    }
+
+    /// Indique si l'instruction 0230 du CHIP-8 hires est disponible.
+    pub fn supports_hires_chip8(&self) -> bool {
+        matches!(self, Platform::Chip8Hires)
+    }
+
+    /// Indique si les instructions du XO-CHIP sont disponibles.
+    pub fn supports_xo_chip(&self) -> bool {
+        matches!(self, Platform::XoChip)
+    }
+}
@@ -0,0 +1,100 @@
+use crate::{
+    chip::{DEFAULT_LOAD_ADDRESS, DEFAULT_MEMORY_SIZE},
+    font::{COSMAC_VIP_HEX_SPRITES, DREAM_6800_HEX_SPRITES, ETI_660_HEX_SPRITES},
+    platform::Platform,
+    quirks::Quirks,
+    timing::Timing,
+};
+
+/// Disposition du clavier hexadécimal du COSMAC VIP, ligne par ligne.
+pub const VIP_KEYPAD: [u8; 16] = [
+    0x1, 0x2, 0x3, 0xC, 0x4, 0x5, 0x6, 0xD, 0x7, 0x8, 0x9, 0xE, 0xA, 0x0, 0xB, 0xF,
+];
+
+/// Disposition du clavier dont les touches sont rangées dans l'ordre, ligne par ligne.
+pub const ORDERED_KEYPAD: [u8; 16] = [
+    0x0, 0x1, 0x2, 0x3, 0x4, 0x5, 0x6, 0x7, 0x8, 0x9, 0xA, 0xB, 0xC, 0xD, 0xE, 0xF,
+];
+
+/// Adresse à laquelle sont chargés les programmes de l'ETI-660.
+pub const ETI_660_LOAD_ADDRESS: u16 = 0x600;
+
+/// Ordinateur sur lequel tournait un interpréteur CHIP-8, qui fixe ensemble tous les paramètres
+/// dont dépendent ses programmes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Profile {
+    pub platform: Platform,
+    /// Adresse à laquelle le programme est copié et où démarre le registre PC.
+    pub load_address: u16,
+    pub memory_size: usize,
+    /// Sprites des caractères hexadécimaux, copiés au début de la RAM.
+    pub font: Vec<u8>,
+    /// Nombre d'appels de fonctions imbriqués que peut contenir la pile.
+    pub stack_depth: usize,
+    /// Vitesse approximative de l'interpréteur d'origine.
+    pub instructions_per_frame: u32,
+    /// Durée des instructions, qui donne la vitesse exacte de l'interpréteur d'origine lorsqu'elle
+    /// est connue.
+    pub timing: Timing,
+    pub quirks: Quirks,
+    /// Valeur de chaque touche du clavier de la machine, ligne par ligne, utilisée par l'hôte
+    /// pour placer les touches.
+    pub keypad: [u8; 16],
+}
+
+impl Profile {
+    /// RCA COSMAC VIP, pour lequel a été écrit le premier interpréteur CHIP-8.
+    pub fn cosmac_vip() -> Self {
+        Self {
+            platform: Platform::Chip8,
+            load_address: DEFAULT_LOAD_ADDRESS,
+            memory_size: DEFAULT_MEMORY_SIZE,
+            font: COSMAC_VIP_HEX_SPRITES.to_vec(),
+            stack_depth: 12,
+            instructions_per_frame: 15,
+            timing: Timing::CosmacVip,
+            quirks: Quirks::cosmac_vip(),
+            keypad: VIP_KEYPAD,
+        }
+    }
+
+    /// ETI-660, dont l'interpréteur occupe la mémoire jusqu'à l'adresse 0x600.
+    pub fn eti_660() -> Self {
+        Self {
+            load_address: ETI_660_LOAD_ADDRESS,
+            font: ETI_660_HEX_SPRITES.to_vec(),
+            timing: Timing::Fixed,
+            keypad: ORDERED_KEYPAD,
+            ..Self::cosmac_vip()
+        }
+    }
+
+    /// DREAM 6800, basé sur un Motorola 6800 et le système CHIPOS.
+    pub fn dream_6800() -> Self {
+        Self {
+            font: DREAM_6800_HEX_SPRITES.to_vec(),
+            instructions_per_frame: 20,
+            timing: Timing::Fixed,
+            keypad: ORDERED_KEYPAD,
+            ..Self::cosmac_vip()
+        }
+    }
+
+    /// Telmac 1800, clone finlandais du COSMAC VIP qui exécute le même interpréteur CHIP-8 sur le
+    /// même processeur. Ce profil est un alias de `cosmac_vip`, les programmes écrits pour l'une
+    /// des machines fonctionnant à l'identique sur l'autre.
+    pub fn telmac_1800() -> Self {
+        Self::cosmac_vip()
+    }
+
+    /// Retourne le profil correspondant au nom donné en ligne de commande.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "vip" => Some(Self::cosmac_vip()),
+            "eti660" => Some(Self::eti_660()),
+            "dream6800" => Some(Self::dream_6800()),
+            "telmac1800" => Some(Self::telmac_1800()),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,139 @@
+/// Comportement des instructions FX55 et FX65 vis-à-vis du registre I.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MemoryIncrement {
+    /// I n'est pas modifié.
+    None,
+    /// I est incrémenté de X (CHIP-48).
+    X,
+    /// I est incrémenté de X + 1 (COSMAC VIP).
+    XPlusOne,
+}
+
+/// Attente de la frame suivante par DXYN.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DisplayWait {
+    /// Les sprites sont dessinés immédiatement.
+    None,
+    /// DXYN attend toujours le début de la frame suivante (COSMAC VIP).
+    Always,
+    /// DXYN n'attend qu'en basse résolution (SUPER-CHIP 1.1 sur HP-48).
+    LoresOnly,
+}
+
+/// Interprétation des instructions ambiguës, qui diffère selon les plateformes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Quirks {
+    /// 8XY6 et 8XYE décalent Vy et stockent le résultat dans Vx au lieu de décaler Vx.
+    pub shift_uses_vy: bool,
+    /// Modification de I par FX55 et FX65.
+    pub memory_increment: MemoryIncrement,
+    /// 8XY1, 8XY2 et 8XY3 remettent VF à 0.
+    pub vf_reset: bool,
+    /// BNNN saute à l'adresse NNN + Vx, X étant le premier chiffre de NNN, au lieu de NNN + V0.
+    pub jump_uses_vx: bool,
+    /// Les sprites sont coupés au bord de l'écran au lieu de réapparaître de l'autre côté.
+    pub clip_sprites: bool,
+    /// DXYN attend le début de la frame suivante pour dessiner, ce qui limite l'affichage à un
+    /// sprite par frame et réduit le scintillement.
+    pub display_wait: DisplayWait,
+    /// En basse résolution, 00CN, 00DN, 00FB et 00FC décalent l'écran de la moitié de la distance
+    /// demandée, qui est comptée en pixels de la haute résolution. Le décalage d'un demi-pixel
+    /// n'étant pas représentable, une distance impaire est arrondie à l'inférieur.
+    pub lores_half_scroll: bool,
+}
+
+impl Quirks {
+    pub fn cosmac_vip() -> Self {
+        Self {
+            shift_uses_vy: true,
+            memory_increment: MemoryIncrement::XPlusOne,
+            vf_reset: true,
+            jump_uses_vx: false,
+            clip_sprites: true,
+            display_wait: DisplayWait::Always,
+            lores_half_scroll: false,
+        }
+    }
+
+    pub fn chip_48() -> Self {
+        Self {
+            shift_uses_vy: false,
+            memory_increment: MemoryIncrement::X,
+            vf_reset: false,
+            jump_uses_vx: true,
+            clip_sprites: true,
+            display_wait: DisplayWait::None,
+            lores_half_scroll: false,
+        }
+    }
+
+    /// SUPER-CHIP 1.1 tel qu'il fonctionnait sur la HP-48. En basse résolution, l'écran est
+    /// affiché en doublant les pixels de la haute résolution : DXYN attend l'affichage et le
+    /// défilement se fait en pixels de la haute résolution.
+    pub fn superchip_legacy() -> Self {
+        Self {
+            shift_uses_vy: false,
+            memory_increment: MemoryIncrement::None,
+            vf_reset: false,
+            jump_uses_vx: true,
+            clip_sprites: true,
+            display_wait: DisplayWait::LoresOnly,
+            lores_half_scroll: true,
+        }
+    }
+
+    /// SUPER-CHIP tel qu'implémenté par les émulateurs modernes, qui traitent la basse
+    /// résolution comme la haute : pas d'attente de l'affichage et défilement en pixels de
+    /// l'écran courant.
+    pub fn superchip_modern() -> Self {
+        Self {
+            shift_uses_vy: false,
+            memory_increment: MemoryIncrement::None,
+            vf_reset: false,
+            jump_uses_vx: true,
+            clip_sprites: true,
+            display_wait: DisplayWait::None,
+            lores_half_scroll: false,
+        }
+    }
+
+    pub fn xo_chip() -> Self {
+        Self {
+            shift_uses_vy: true,
+            memory_increment: MemoryIncrement::XPlusOne,
+            vf_reset: false,
+            jump_uses_vx: false,
+            clip_sprites: false,
+            display_wait: DisplayWait::None,
+            lores_half_scroll: false,
+        }
+    }
+
+    /// Retourne le préréglage correspondant au nom donné en ligne de commande.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "default" => Some(Self::default()),
+            "vip" => Some(Self::cosmac_vip()),
+            "chip48" => Some(Self::chip_48()),
+            "schip-legacy" => Some(Self::superchip_legacy()),
+            "schip-modern" => Some(Self::superchip_modern()),
+            "xochip" => Some(Self::xo_chip()),
+            _ => None,
+        }
+    }
+}
+
+impl Default for Quirks {
+    /// Comportement historique de cet interpréteur.
+    fn default() -> Self {
+        Self {
+            shift_uses_vy: false,
+            memory_increment: MemoryIncrement::None,
+            vf_reset: false,
+            jump_uses_vx: false,
+            clip_sprites: false,
+            display_wait: DisplayWait::None,
+            lores_half_scroll: false,
+        }
+    }
+}
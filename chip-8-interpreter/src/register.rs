@@ -1,14 +1,32 @@
-use std::time::{Duration, Instant};
+use crate::{
+    audio::{DigitizedSound, AUDIO_PATTERN_SIZE, DEFAULT_AUDIO_PATTERN, DEFAULT_PITCH},
+    chip::BIG_FONT_ADDRESS,
+    font::DEFAULT_FONT_ADDRESS,
+    rpl::RPL_FLAGS_COUNT,
+};
 
 pub struct Registers {
     pub v: [u8; 0x10],
     pub pc: u16,
-    pub sp: u8,
-    pub i: u16,
+    /// Position dans la pile en octets, chaque adresse de retour en occupant 2.
+    pub sp: u16,
+    /// Adresse sur 16 bits, ou sur 24 bits sur MEGA-CHIP.
+    pub i: u32,
     pub dt: u8,
     pub st: u8,
-    dt_instant: Instant,
-    st_instant: Instant,
+    /// Drapeaux utilisateur RPL de la HP-48, lus et écrits par FX85 et FX75.
+    pub rpl: [u8; RPL_FLAGS_COUNT],
+    /// Motif audio du XO-CHIP, chargé par F002.
+    pub pattern: [u8; AUDIO_PATTERN_SIZE],
+    /// Hauteur du motif audio du XO-CHIP, modifiée par FX3A.
+    pub pitch: u8,
+    /// Son numérisé du MEGA-CHIP en cours de lecture, lancé par 060N.
+    pub sample: Option<DigitizedSound>,
+    /// Adresses des sprites des caractères hexadécimaux utilisés par FX29 et FX30.
+    pub font_address: u16,
+    pub big_font_address: u16,
+    /// Le processeur a été arrêté par l'instruction 00FD.
+    pub halted: bool,
 }
 
 impl Registers {
@@ -19,24 +37,31 @@ impl Registers {
             sp: 0x0,
             i: 0x0,dt: 0x0,
             st: 0x0,
-            dt_instant: Instant::now(),
-            st_instant: Instant::now(),
+            rpl: [0x0; RPL_FLAGS_COUNT],
+            pattern: DEFAULT_AUDIO_PATTERN,
+            pitch: DEFAULT_PITCH,
+            sample: None,
+            font_address: DEFAULT_FONT_ADDRESS,
+            big_font_address: BIG_FONT_ADDRESS,
+            halted: false,
         }
     }
 
-    pub fn get_elapsed_time_since_last_dt(&self) -> Duration {
-        self.dt_instant.elapsed()
+    /// Avance PC de `bytes` octets. PC revient à 0 après 0xFFFF, la dernière instruction de la
+    /// RAM de 64 Kio du XO-CHIP pouvant être exécutée.
+    pub fn advance(&mut self, bytes: u16) {
+        self.pc = self.pc.wrapping_add(bytes);
     }
 
-    pub fn get_elapsed_time_since_last_st(&self) -> Duration {
-        self.st_instant.elapsed()
-    }
-
-    pub fn reset_dt_time(&mut self) {
-        self.dt_instant = Instant::now();
-    }
+    /// Décrémente le Delay Timer et le Sound Timer s'ils ont été définis.
+    /// Doit être appelée une fois par frame émulée (60Hz).
+    pub fn tick_timers(&mut self) {
+        if self.dt > 0 {
+            self.dt -= 1;
+        }
 
-    pub fn reset_st_time(&mut self) {
-        self.st_instant = Instant::now();
+        if self.st > 0 {
+            self.st -= 1;
+        }
     }
 }
\ No newline at end of file
@@ -0,0 +1,53 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::error::Chip8Error;
+
+/// Nombre de drapeaux RPL conservés, 8 sur SUPER-CHIP et 16 sur XO-CHIP.
+pub const RPL_FLAGS_COUNT: usize = 0x10;
+
+/// Empreinte d'un programme, utilisée pour retrouver ses drapeaux sauvegardés.
+/// Il s'agit d'un hash FNV-1a sur 64 bits, qui reste identique d'une version de Rust à l'autre.
+pub fn rom_hash(rom: &[u8]) -> u64 {
+    let mut hash: u64 = 0xCBF29CE484222325;
+
+    for byte in rom {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001B3);
+    }
+
+    hash
+}
+
+fn flags_path(dir: &Path, rom_hash: u64) -> PathBuf {
+    dir.join(format!("{rom_hash:016x}.rpl"))
+}
+
+/// Lit les drapeaux sauvegardés pour le programme dans le dossier `dir`.
+/// Retourne `None` si le programme n'a encore jamais sauvegardé de drapeaux.
+pub fn load_flags(dir: &Path, rom_hash: u64) -> Result<Option<[u8; RPL_FLAGS_COUNT]>, Chip8Error> {
+    let content = match fs::read(flags_path(dir, rom_hash)) {
+        Ok(t) => t,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+
+    let mut flags = [0; RPL_FLAGS_COUNT];
+
+    // Un fichier tronqué laisse simplement les drapeaux manquants à 0.
+    for (dest, from) in flags.iter_mut().zip(content) {
+        *dest = from;
+    }
+
+    Ok(Some(flags))
+}
+
+/// Sauvegarde les drapeaux du programme dans le dossier `dir`, qui est créé si besoin.
+pub fn save_flags(dir: &Path, rom_hash: u64, flags: &[u8]) -> Result<(), Chip8Error> {
+    fs::create_dir_all(dir)?;
+    fs::write(flags_path(dir, rom_hash), flags)?;
+
+    Ok(())
+}
@@ -0,0 +1,256 @@
+use crate::megachip::{MegaDisplay, MEGA_HEIGHT, MEGA_WIDTH};
+
+/// Résolution de l'écran d'un CHIP-8 classique.
+pub const LORES_WIDTH: usize = 64;
+pub const LORES_HEIGHT: usize = 32;
+
+/// Résolution de l'écran en mode haute résolution du SUPER-CHIP.
+pub const HIRES_WIDTH: usize = 128;
+pub const HIRES_HEIGHT: usize = 64;
+
+/// Résolution de l'écran du CHIP-8 hires, qui affiche deux fois plus de lignes.
+pub const HIRES_CHIP8_WIDTH: usize = 64;
+pub const HIRES_CHIP8_HEIGHT: usize = 64;
+
+/// Largeur en pixels des zones de couleur du CHIP-8X.
+pub const COLOR_ZONE_WIDTH: usize = 8;
+
+/// Hauteur en pixels des zones colorées par BXY0 sur CHIP-8X.
+pub const COLOR_ZONE_HEIGHT: usize = 4;
+
+/// Couleurs de la carte VP-590 du CHIP-8X : 0 noir, 1 rouge, 2 bleu, 3 violet, 4 vert, 5 jaune,
+/// 6 cyan et 7 blanc.
+pub const COLORS_COUNT: u8 = 8;
+
+/// Couleurs de fond parcourues par l'instruction 02A0, dans l'ordre : bleu, noir, vert et rouge.
+pub const BACKGROUND_COLORS: [u8; 4] = [2, 0, 4, 1];
+
+/// Couleur des pixels allumés avant que le programme n'en choisisse une.
+pub const DEFAULT_FOREGROUND_COLOR: u8 = 1;
+
+/// Nombre de plans de bits de l'écran du XO-CHIP.
+pub const PLANES_COUNT: usize = 2;
+
+/// Écran dont la résolution peut changer pendant l'exécution.
+/// Chaque pixel contient un indice de couleur sur 2 bits, le bit 0 appartenant au premier plan
+/// et le bit 1 au second plan du XO-CHIP. Les autres plateformes n'utilisent que le premier plan.
+pub struct Screen {
+    width: usize,
+    height: usize,
+    /// Résolution lorsque le mode haute résolution du SUPER-CHIP n'est pas actif.
+    lores_width: usize,
+    lores_height: usize,
+    high_resolution: bool,
+    selected_planes: u8,
+    pixels: Vec<u8>,
+    /// Position dans `BACKGROUND_COLORS` de la couleur de fond du CHIP-8X.
+    background: usize,
+    /// Couleur des pixels allumés du CHIP-8X, pour chaque zone de `COLOR_ZONE_WIDTH` pixels de
+    /// large et d'une ligne de haut.
+    foreground: Vec<u8>,
+    /// Affichage couleur utilisé à la place des pixels en mode MEGA-CHIP.
+    mega: MegaDisplay,
+}
+
+impl Screen {
+    pub fn new() -> Self {
+        Self::with_size(LORES_WIDTH, LORES_HEIGHT)
+    }
+
+    /// Crée un écran dont la résolution de base n'est pas celle du CHIP-8 classique.
+    pub fn with_size(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            lores_width: width,
+            lores_height: height,
+            high_resolution: false,
+            selected_planes: 0x1,
+            pixels: vec![0; width * height],
+            background: 0,
+            foreground: vec![DEFAULT_FOREGROUND_COLOR; width.div_ceil(COLOR_ZONE_WIDTH) * height],
+            mega: MegaDisplay::new(),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn is_high_resolution(&self) -> bool {
+        self.high_resolution
+    }
+
+    /// Change la résolution de l'écran, ce qui efface tous les plans.
+    pub fn set_high_resolution(&mut self, value: bool) {
+        self.high_resolution = value;
+
+        if value {
+            self.width = HIRES_WIDTH;
+            self.height = HIRES_HEIGHT;
+        } else {
+            self.width = self.lores_width;
+            self.height = self.lores_height;
+        }
+
+        self.pixels = vec![0; self.width * self.height];
+        self.foreground = vec![DEFAULT_FOREGROUND_COLOR; self.zones_per_line() * self.height];
+    }
+
+    /// Active ou désactive le mode MEGA-CHIP, ce qui efface l'écran.
+    /// En mode MEGA-CHIP, l'écran fait 256x192 pixels et son contenu se trouve dans `mega`.
+    pub(crate) fn set_mega_mode(&mut self, value: bool) {
+        self.mega.set_enabled(value);
+
+        if value {
+            self.high_resolution = true;
+            self.width = MEGA_WIDTH;
+            self.height = MEGA_HEIGHT;
+            self.pixels = vec![0; self.width * self.height];
+            self.foreground = vec![DEFAULT_FOREGROUND_COLOR; self.zones_per_line() * self.height];
+        } else {
+            self.set_high_resolution(false);
+        }
+    }
+
+    pub fn mega(&self) -> &MegaDisplay {
+        &self.mega
+    }
+
+    pub(crate) fn mega_mut(&mut self) -> &mut MegaDisplay {
+        &mut self.mega
+    }
+
+    /// Masque des plans sur lesquels dessinent, effacent et défilent les instructions.
+    pub fn selected_planes(&self) -> u8 {
+        self.selected_planes
+    }
+
+    pub(crate) fn set_selected_planes(&mut self, planes: u8) {
+        self.selected_planes = planes & 0x3;
+    }
+
+    /// Contenu de l'écran ligne par ligne, chaque octet est l'indice de couleur du pixel.
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Indice de couleur du pixel, compris entre 0 et 3.
+    pub fn get(&self, x: usize, y: usize) -> u8 {
+        self.pixels[y * self.width + x]
+    }
+
+    /// Applique un XOR au bit du plan `plane` du pixel et retourne `true` si un pixel allumé
+    /// a été éteint.
+    pub(crate) fn xor(&mut self, x: usize, y: usize, plane: u8) -> bool {
+        let pixel = &mut self.pixels[y * self.width + x];
+        let collision = (*pixel & plane) != 0;
+
+        *pixel ^= plane;
+
+        collision
+    }
+
+    fn zones_per_line(&self) -> usize {
+        self.width.div_ceil(COLOR_ZONE_WIDTH)
+    }
+
+    /// Couleur du fond de l'écran du CHIP-8X.
+    pub fn background_color(&self) -> u8 {
+        BACKGROUND_COLORS[self.background]
+    }
+
+    /// Passe à la couleur de fond suivante.
+    pub(crate) fn cycle_background(&mut self) {
+        self.background = (self.background + 1) % BACKGROUND_COLORS.len();
+    }
+
+    /// Couleur du pixel lorsqu'il est allumé sur CHIP-8X.
+    pub fn foreground_color(&self, x: usize, y: usize) -> u8 {
+        self.foreground[y * self.zones_per_line() + x / COLOR_ZONE_WIDTH]
+    }
+
+    /// Colore `zones` zones de large à partir de la zone `zone_x`, sur les lignes `y` à
+    /// `y + lines - 1`. Ce qui dépasse de l'écran est ignoré.
+    pub(crate) fn set_foreground_color(&mut self, zone_x: usize, zones: usize, y: usize, lines: usize, color: u8) {
+        let zones_per_line = self.zones_per_line();
+
+        for line in y..(y + lines).min(self.height) {
+            for zone in zone_x..(zone_x + zones).min(zones_per_line) {
+                self.foreground[line * zones_per_line + zone] = color % COLORS_COUNT;
+            }
+        }
+    }
+
+    /// Efface les plans sélectionnés.
+    pub fn clear(&mut self) {
+        let mask = !self.selected_planes;
+
+        for pixel in self.pixels.iter_mut() {
+            *pixel &= mask;
+        }
+    }
+
+    /// Décale les plans sélectionnés de `dx` colonnes et `dy` lignes, les pixels qui sortent de
+    /// l'écran sont perdus.
+    fn shift(&mut self, dx: isize, dy: isize) {
+        if self.mega.is_enabled() {
+            self.mega.shift(dx, dy);
+
+            return;
+        }
+
+        let planes = self.selected_planes;
+        let previous = self.pixels.clone();
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let from_x = x as isize - dx;
+                let from_y = y as isize - dy;
+
+                let moved = if from_x >= 0
+                    && from_y >= 0
+                    && (from_x as usize) < self.width
+                    && (from_y as usize) < self.height
+                {
+                    previous[from_y as usize * self.width + from_x as usize] & planes
+                } else {
+                    0
+                };
+
+                let pixel = &mut self.pixels[y * self.width + x];
+                *pixel = (*pixel & !planes) | moved;
+            }
+        }
+    }
+
+    /// Décale le contenu de l'écran de `n` lignes vers le bas.
+    pub(crate) fn scroll_down(&mut self, n: usize) {
+        self.shift(0, n as isize);
+    }
+
+    /// Décale le contenu de l'écran de `n` lignes vers le haut.
+    pub(crate) fn scroll_up(&mut self, n: usize) {
+        self.shift(0, -(n as isize));
+    }
+
+    /// Décale le contenu de l'écran de `n` colonnes vers la gauche.
+    pub(crate) fn scroll_left(&mut self, n: usize) {
+        self.shift(-(n as isize), 0);
+    }
+
+    /// Décale le contenu de l'écran de `n` colonnes vers la droite.
+    pub(crate) fn scroll_right(&mut self, n: usize) {
+        self.shift(n as isize, 0);
+    }
+}
+
+impl Default for Screen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
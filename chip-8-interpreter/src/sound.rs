@@ -0,0 +1,203 @@
+use std::{f64::consts::PI, io::Write};
+
+use crate::{
+    audio::{DigitizedSound, PatternSynth, AUDIO_PATTERN_SIZE},
+    error::Chip8Error,
+};
+
+/// Forme du signal joué par le buzzer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Waveform {
+    Square,
+    Triangle,
+    Sawtooth,
+    Sine,
+}
+
+impl Waveform {
+    /// Valeur du signal entre -1 et 1 pour une phase comprise entre 0 et 1.
+    fn sample(&self, phase: f64) -> f64 {
+        match self {
+            Waveform::Square => {
+                if phase < 0.5 {
+                    1.0
+                } else {
+                    -1.0
+                }
+            }
+            Waveform::Triangle => 1.0 - 4.0 * (phase - 0.5).abs(),
+            Waveform::Sawtooth => 2.0 * phase - 1.0,
+            Waveform::Sine => (2.0 * PI * phase).sin(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SoundSettings {
+    /// Nombre d'échantillons générés par seconde émulée.
+    pub sample_rate: u32,
+    /// Fréquence du buzzer en Hz.
+    pub frequency: f64,
+    /// Volume entre 0 et 1.
+    pub volume: f64,
+    pub waveform: Waveform,
+}
+
+impl Default for SoundSettings {
+    fn default() -> Self {
+        Self {
+            sample_rate: 44100,
+            frequency: 440.0,
+            volume: 0.25,
+            waveform: Waveform::Square,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SoundEventKind {
+    BeepStart,
+    BeepStop,
+}
+
+/// Source du son joué pendant une frame.
+pub(crate) enum Voice<'a> {
+    /// Buzzer actif tant que le Sound Timer n'est pas nul.
+    Beeper,
+    /// Motif audio du XO-CHIP et sa hauteur, joués tant que le Sound Timer n'est pas nul par le
+    /// synthétiseur de la machine.
+    Pattern(&'a mut PatternSynth, &'a [u8; AUDIO_PATTERN_SIZE], u8),
+    /// Son numérisé du MEGA-CHIP, joué jusqu'à sa fin indépendamment du Sound Timer.
+    Digitized(&'a mut DigitizedSound),
+}
+
+/// Début ou fin du son, avec le numéro de la frame émulée pendant laquelle il s'est produit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SoundEvent {
+    pub frame: u64,
+    pub kind: SoundEventKind,
+}
+
+/// Buzzer piloté par le Sound Timer.
+/// À chaque frame émulée, il génère les échantillons PCM 16 bits mono de cette frame, ce qui lie
+/// le son à l'horloge émulée plutôt qu'au temps réel.
+pub struct Sound {
+    settings: SoundSettings,
+    phase: f64,
+    beeping: bool,
+    /// Fraction d'échantillon qui n'a pas encore été générée, lorsque la fréquence
+    /// d'échantillonnage n'est pas un multiple de 60.
+    pending_samples: f64,
+    samples: Vec<i16>,
+    events: Vec<SoundEvent>,
+}
+
+impl Sound {
+    pub fn new(settings: SoundSettings) -> Self {
+        Self {
+            settings,
+            phase: 0.0,
+            beeping: false,
+            pending_samples: 0.0,
+            samples: Vec::new(),
+            events: Vec::new(),
+        }
+    }
+
+    pub fn settings(&self) -> &SoundSettings {
+        &self.settings
+    }
+
+    pub fn is_beeping(&self) -> bool {
+        self.beeping
+    }
+
+    /// Termine la frame émulée `frame`, pendant laquelle le Sound Timer valait `st`, en jouant
+    /// `voice`.
+    /// Retourne l'évènement produit si le son a commencé ou s'est arrêté.
+    pub(crate) fn end_frame(&mut self, frame: u64, st: u8, voice: Voice) -> Option<SoundEventKind> {
+        let beeping = match &voice {
+            Voice::Digitized(sound) => !sound.is_finished(),
+            _ => st > 0,
+        };
+
+        let kind = match (self.beeping, beeping) {
+            (false, true) => Some(SoundEventKind::BeepStart),
+            (true, false) => Some(SoundEventKind::BeepStop),
+            _ => None,
+        };
+
+        if let Some(kind) = kind {
+            self.events.push(SoundEvent { frame, kind });
+        }
+
+        self.beeping = beeping;
+
+        self.pending_samples += self.settings.sample_rate as f64 / 60.0;
+        let count = self.pending_samples as usize;
+        self.pending_samples -= count as f64;
+
+        let start = self.samples.len();
+        self.samples.resize(start + count, 0);
+        let out = &mut self.samples[start..];
+
+        match voice {
+            Voice::Pattern(synth, pattern, pitch) => synth.render(pattern, pitch, beeping, self.settings.sample_rate, out),
+            Voice::Digitized(sound) => sound.render(self.settings.sample_rate, out),
+            Voice::Beeper if beeping => {
+                let amplitude = self.settings.volume.clamp(0.0, 1.0) * i16::MAX as f64;
+                let step = self.settings.frequency / self.settings.sample_rate as f64;
+
+                for sample in out.iter_mut() {
+                    *sample = (self.settings.waveform.sample(self.phase) * amplitude) as i16;
+
+                    self.phase = (self.phase + step).fract();
+                }
+            }
+            Voice::Beeper => {
+                // Le signal reprend au début de sa période au prochain son.
+                self.phase = 0.0;
+            }
+        }
+
+        kind
+    }
+
+    /// Retourne les échantillons générés depuis le dernier appel.
+    pub fn take_samples(&mut self) -> Vec<i16> {
+        std::mem::take(&mut self.samples)
+    }
+
+    /// Retourne les évènements produits depuis le dernier appel.
+    pub fn take_events(&mut self) -> Vec<SoundEvent> {
+        std::mem::take(&mut self.events)
+    }
+}
+
+/// Écrit des échantillons PCM 16 bits mono au format WAV.
+pub fn write_wav(writer: &mut impl Write, sample_rate: u32, samples: &[i16]) -> Result<(), Chip8Error> {
+    let data_size = (samples.len() * 2) as u32;
+
+    writer.write_all(b"RIFF")?;
+    writer.write_all(&(36 + data_size).to_le_bytes())?;
+    writer.write_all(b"WAVE")?;
+
+    // Description du format : PCM, 1 canal, 16 bits par échantillon.
+    writer.write_all(b"fmt ")?;
+    writer.write_all(&16_u32.to_le_bytes())?;
+    writer.write_all(&1_u16.to_le_bytes())?;
+    writer.write_all(&1_u16.to_le_bytes())?;
+    writer.write_all(&sample_rate.to_le_bytes())?;
+    writer.write_all(&(sample_rate * 2).to_le_bytes())?;
+    writer.write_all(&2_u16.to_le_bytes())?;
+    writer.write_all(&16_u16.to_le_bytes())?;
+
+    writer.write_all(b"data")?;
+    writer.write_all(&data_size.to_le_bytes())?;
+
+    for sample in samples {
+        writer.write_all(&sample.to_le_bytes())?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use crate::vip::CYCLES_PER_FRAME;
+
+/// Modèle de durée des instructions utilisé par l'horloge.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Timing {
+    /// Chaque instruction dure autant, une frame en contient un nombre fixe.
+    #[default]
+    Fixed,
+    /// Chaque instruction dure autant de cycles machine qu'avec l'interpréteur d'origine du
+    /// COSMAC VIP, et DXYN attend l'interruption de l'affichage avant de dessiner.
+    CosmacVip,
+}
+
+/// Cycles machine laissés à l'interpréteur du COSMAC VIP à chaque frame, la routine
+/// d'interruption occupant le processeur pendant tout l'affichage de l'image.
+pub const VIP_AVAILABLE_CYCLES: u32 = CYCLES_PER_FRAME - VIP_INTERRUPT_CYCLES;
+
+/// Durée de la routine d'interruption du moniteur du COSMAC VIP.
+const VIP_INTERRUPT_CYCLES: u32 = 1832;
+
+/// Durée de la boucle de l'interpréteur qui lit et décode chaque instruction.
+const VIP_FETCH_CYCLES: u32 = 40;
+
+/// Durée supplémentaire des instructions conditionnelles lorsque l'instruction suivante est
+/// ignorée.
+const VIP_SKIP_CYCLES: u32 = 4;
+
+/// Durée de 00E0, qui efface la page d'affichage octet par octet.
+const VIP_CLS_CYCLES: u32 = 3078;
+
+/// Nombre de lignes de l'écran du COSMAC VIP, les lignes d'un sprite qui en dépassent ne sont
+/// pas dessinées.
+const VIP_SCREEN_HEIGHT: u32 = 32;
+
+/// Retourne le nombre de cycles machine que prend l'interpréteur du COSMAC VIP pour exécuter
+/// `instruction`, d'après l'état des registres `v` avant son exécution.
+/// `skipped` indique si l'instruction a fait ignorer l'instruction suivante.
+/// L'attente de l'interruption par DXYN n'est pas comptée, elle dépend de l'horloge.
+pub fn vip_cycles(instruction: u16, v: &[u8; 0x10], skipped: bool) -> u32 {
+    let x = ((instruction & 0x0F00) >> 8) as usize;
+    let y = ((instruction & 0x00F0) >> 4) as usize;
+    let nibble = (instruction & 0x000F) as u32;
+    let skip = if skipped { VIP_SKIP_CYCLES } else { 0 };
+
+    let execute = match instruction >> 12 {
+        0x0 => match instruction {
+            0x00E0 => VIP_CLS_CYCLES,
+            0x00EE => 10,
+            // Appel d'une routine en code machine, dont la durée est inconnue.
+            _ => 26,
+        },
+        0x1 => 12,
+        0x2 => 26,
+        0x3 | 0x4 => 10 + skip,
+        0x5 | 0x9 => 14 + skip,
+        0x6 => 6,
+        0x7 => 10,
+        // Les opérations logiques et arithmétiques sont exécutées par du code généré en RAM.
+        0x8 => 44,
+        0xA => 12,
+        0xB => {
+            // Le calcul de l'adresse prend 2 cycles de plus s'il change de page.
+            let nnn = instruction & 0x0FFF;
+            let page = if (nnn & 0xFF) + v[0] as u16 > 0xFF { 2 } else { 0 };
+
+            22 + page
+        }
+        0xC => 36,
+        0xD => {
+            let column = v[x] as u32 % 64;
+            let row = v[y] as u32 % VIP_SCREEN_HEIGHT;
+            let rows = nibble.min(VIP_SCREEN_HEIGHT - row);
+
+            // Un sprite qui n'est pas aligné sur un octet est décalé et écrit sur deux octets.
+            let row_cycles = if column.is_multiple_of(8) { 34 } else { 46 };
+
+            26 + rows * row_cycles
+        }
+        0xE => 14 + skip,
+        _ => match instruction & 0xFF {
+            0x0A => 19,
+            0x1E | 0x29 => 16,
+            0x33 => {
+                // Les chiffres sont obtenus par soustractions successives.
+                let value = v[x] as u32;
+
+                80 + 16 * (value / 100 + value / 10 % 10 + value % 10)
+            }
+            0x55 | 0x65 => 14 + 14 * (x as u32 + 1),
+            _ => 10,
+        },
+    };
+
+    VIP_FETCH_CYCLES + execute
+}
@@ -0,0 +1,272 @@
+use crate::{
+    cdp1802::{Bus, Cdp1802},
+    error::Chip8Error,
+    machine::Machine,
+};
+
+/// Taille de la RAM du COSMAC VIP, répétée dans toute la moitié basse de l'espace d'adressage.
+pub const VIP_MEMORY_SIZE: usize = 0x1000;
+
+/// Adresse de la ROM du moniteur, répétée dans toute la moitié haute de l'espace d'adressage.
+pub const MONITOR_ADDRESS: u16 = 0x8000;
+
+/// L'interpréteur CHIP-8 d'origine occupe la RAM jusqu'à l'adresse du programme.
+pub const INTERPRETER_SIZE: usize = 0x200;
+
+/// Résolution de l'image produite par le CDP1861.
+/// L'interpréteur CHIP-8 affiche chacune de ses 32 lignes 4 fois.
+pub const VIP_DISPLAY_WIDTH: usize = 64;
+pub const VIP_DISPLAY_HEIGHT: usize = 128;
+
+/// Durée d'une ligne de l'image du CDP1861, en cycles machine du processeur.
+pub const CYCLES_PER_LINE: u32 = 14;
+
+/// Nombre de lignes d'une image NTSC, soit environ 60 images par seconde.
+pub const LINES_PER_FRAME: u32 = 262;
+
+pub const CYCLES_PER_FRAME: u32 = CYCLES_PER_LINE * LINES_PER_FRAME;
+
+/// Première ligne affichée, dont les octets sont lus en DMA.
+const DISPLAY_FIRST_LINE: u32 = 64;
+
+/// Nombre de cycles de DMA par ligne affichée, un par octet.
+const DMA_CYCLES: u32 = 8;
+
+/// Cycle de la frame auquel le CDP1861 demande une interruption, afin que la routine de
+/// l'interruption ait préparé R0 lorsque le DMA de la première ligne commence.
+const INTERRUPT_CYCLE: u32 = 61 * CYCLES_PER_LINE + 11;
+
+/// Le CDP1861 active EF1 pendant les 4 lignes qui précèdent le début et la fin de l'affichage.
+fn display_flag(line: u32) -> bool {
+    matches!(line, 60..=63 | 188..=191)
+}
+
+/// Tout ce qui est relié au bus du CDP1802 : la mémoire, le CDP1861 et le clavier.
+struct VipBus {
+    ram: Vec<u8>,
+    monitor: Vec<u8>,
+    /// Au démarrage, la ROM du moniteur remplace la RAM à l'adresse 0 jusqu'au premier accès à
+    /// une adresse dont le bit A15 est à 1.
+    monitor_mapped_low: bool,
+    /// Le CDP1861 est activé par INP 1 et désactivé par OUT 1.
+    display_on: bool,
+    display_flag: bool,
+    /// Numéro de la touche testée par EF3, choisi par OUT 2.
+    key_latch: u8,
+    keys: [bool; 0x10],
+}
+
+impl Bus for VipBus {
+    fn read(&mut self, address: u16) -> u8 {
+        if address & MONITOR_ADDRESS != 0 {
+            self.monitor_mapped_low = false;
+        }
+
+        if address & MONITOR_ADDRESS != 0 || self.monitor_mapped_low {
+            self.monitor[address as usize % self.monitor.len()]
+        } else {
+            self.ram[address as usize % self.ram.len()]
+        }
+    }
+
+    fn write(&mut self, address: u16, value: u8) {
+        if address & MONITOR_ADDRESS != 0 {
+            self.monitor_mapped_low = false;
+        } else if !self.monitor_mapped_low {
+            let size = self.ram.len();
+            self.ram[address as usize % size] = value;
+        }
+    }
+
+    fn input(&mut self, port: u8) -> u8 {
+        if port == 1 {
+            self.display_on = true;
+        }
+
+        // Rien ne pilote le bus de données.
+        0x0
+    }
+
+    fn output(&mut self, port: u8, value: u8) {
+        match port {
+            1 => self.display_on = false,
+            2 => self.key_latch = value & 0xF,
+            _ => {}
+        }
+    }
+
+    fn flag(&self, line: u8) -> bool {
+        match line {
+            1 => self.display_flag,
+            3 => self.keys[self.key_latch as usize],
+            _ => false,
+        }
+    }
+}
+
+/// Émulation bas niveau du RCA COSMAC VIP : le CDP1802 exécute l'interpréteur CHIP-8 d'origine,
+/// le CDP1861 lit l'image en DMA et demande une interruption à chaque frame, et le clavier est
+/// lu par EF3 après avoir choisi la touche avec OUT 2.
+/// Les appels de code machine par 0NNN sont donc exécutés comme sur la machine d'origine.
+pub struct Vip {
+    cpu: Cdp1802,
+    bus: VipBus,
+    /// Cycles de la frame en cours déjà écoulés, l'instruction qui termine une frame pouvant
+    /// déborder sur la suivante.
+    frame_cycle: u32,
+    frames: u64,
+    /// Dernière image produite par le CDP1861, un octet par pixel.
+    display: Vec<u8>,
+}
+
+impl Vip {
+    /// Crée la machine à partir de la ROM du moniteur, de l'interpréteur CHIP-8 chargé à l'adresse
+    /// 0 et du programme chargé à l'adresse 0x200.
+    /// Le moniteur est nécessaire car l'interpréteur utilise sa routine d'interruption.
+    pub fn new(monitor: &[u8], interpreter: &[u8], program: &[u8]) -> Result<Self, Chip8Error> {
+        if monitor.is_empty() {
+            return Err(Chip8Error::EmptyRom);
+        }
+
+        if interpreter.len() > INTERPRETER_SIZE {
+            return Err(Chip8Error::RomTooLarge {
+                size: interpreter.len(),
+                max: INTERPRETER_SIZE,
+            });
+        }
+
+        if program.len() > VIP_MEMORY_SIZE - INTERPRETER_SIZE {
+            return Err(Chip8Error::RomTooLarge {
+                size: program.len(),
+                max: VIP_MEMORY_SIZE - INTERPRETER_SIZE,
+            });
+        }
+
+        let mut ram = vec![0x0; VIP_MEMORY_SIZE];
+        ram[..interpreter.len()].copy_from_slice(interpreter);
+        ram[INTERPRETER_SIZE..INTERPRETER_SIZE + program.len()].copy_from_slice(program);
+
+        Ok(Self {
+            cpu: Cdp1802::new(),
+            bus: VipBus {
+                ram,
+                monitor: monitor.to_vec(),
+                monitor_mapped_low: true,
+                display_on: false,
+                display_flag: false,
+                key_latch: 0x0,
+                keys: [false; 0x10],
+            },
+            frame_cycle: 0,
+            frames: 0,
+            display: vec![0x0; VIP_DISPLAY_WIDTH * VIP_DISPLAY_HEIGHT],
+        })
+    }
+
+    pub fn cpu(&self) -> &Cdp1802 {
+        &self.cpu
+    }
+
+    pub fn memory(&self) -> &[u8] {
+        &self.bus.ram
+    }
+
+    /// Nombre total de frames terminées.
+    pub fn frames(&self) -> u64 {
+        self.frames
+    }
+
+    pub fn is_display_on(&self) -> bool {
+        self.bus.display_on
+    }
+
+    /// Dernière image produite par le CDP1861, ligne par ligne, un octet valant 0 ou 1 par pixel.
+    pub fn display(&self) -> &[u8] {
+        &self.display
+    }
+
+    /// Copie les 8 octets d'une ligne lus en DMA dans l'image.
+    fn dma_line(&mut self, line: usize) {
+        for byte in 0..VIP_DISPLAY_WIDTH / 8 {
+            let value = self.cpu.dma_out(&mut self.bus);
+
+            for bit in 0..8 {
+                self.display[line * VIP_DISPLAY_WIDTH + byte * 8 + bit] = (value >> (7 - bit)) & 0x1;
+            }
+        }
+    }
+}
+
+impl Machine for Vip {
+    /// Exécute les 262 lignes d'une image du CDP1861.
+    fn run_frame(&mut self) -> Result<(), Chip8Error> {
+        let mut cycle = self.frame_cycle;
+        let mut interrupt_requested = false;
+        let mut next_line = 0;
+
+        while cycle < CYCLES_PER_FRAME {
+            self.bus.display_flag = display_flag(cycle / CYCLES_PER_LINE);
+
+            if !interrupt_requested && cycle >= INTERRUPT_CYCLE {
+                interrupt_requested = true;
+
+                if self.bus.display_on {
+                    cycle += self.cpu.interrupt();
+                    continue;
+                }
+            }
+
+            // Le DMA d'une ligne commence dès que le processeur termine son instruction.
+            let line_start = (DISPLAY_FIRST_LINE + next_line as u32) * CYCLES_PER_LINE;
+
+            if next_line < VIP_DISPLAY_HEIGHT && cycle >= line_start {
+                if self.bus.display_on && cycle < line_start + CYCLES_PER_LINE {
+                    self.dma_line(next_line);
+                    cycle += DMA_CYCLES;
+                } else {
+                    let start = next_line * VIP_DISPLAY_WIDTH;
+                    self.display[start..start + VIP_DISPLAY_WIDTH].fill(0x0);
+                }
+
+                next_line += 1;
+                continue;
+            }
+
+            cycle += self.cpu.step(&mut self.bus);
+        }
+
+        self.frame_cycle = cycle - CYCLES_PER_FRAME;
+        self.frames += 1;
+
+        Ok(())
+    }
+
+    fn set_key_pressed(&mut self, index: u8, value: bool) -> Result<(), Chip8Error> {
+        if index > 0xF {
+            return Err(Chip8Error::InvalidKey(index));
+        }
+
+        self.bus.keys[index as usize] = value;
+
+        Ok(())
+    }
+
+    /// Après IDL, seul le DMA de l'affichage peut réveiller le processeur, les interruptions
+    /// n'étant demandées que lorsque l'affichage est actif.
+    fn is_halted(&self) -> bool {
+        self.cpu.idle && !self.bus.display_on
+    }
+
+    /// Le buzzer est relié à la sortie Q.
+    fn is_beeping(&self) -> bool {
+        self.cpu.q
+    }
+
+    fn display_size(&self) -> (usize, usize) {
+        (VIP_DISPLAY_WIDTH, VIP_DISPLAY_HEIGHT)
+    }
+
+    fn display_pixel(&self, x: usize, y: usize) -> u8 {
+        self.display[y * VIP_DISPLAY_WIDTH + x]
+    }
+}
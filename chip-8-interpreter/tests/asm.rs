@@ -0,0 +1,118 @@
+use chip_8_interpreter::{
+    asm::assemble,
+    disasm::{disassemble, LineKind},
+    error::Chip8Error,
+    platform::Platform,
+};
+
+/// Désassemble `rom` puis réassemble le listing, qui doit redonner exactement les mêmes octets.
+fn round_trip(rom: &[u8], platform: Platform) {
+    let listing = disassemble(rom, platform, 0x200).unwrap();
+    let source = listing.to_source();
+    let program = assemble(&source, platform, 0x200).unwrap_or_else(|err| panic!("{err}\n{source}"));
+
+    assert_eq!(program, rom, "\n{source}");
+}
+
+#[test]
+fn round_trip_chip8() {
+    round_trip(
+        &[
+            0x00, 0xE0, 0xA2, 0x2A, 0x60, 0x0C, 0x61, 0x08, 0xD0, 0x15, 0x22, 0x18, 0x3A, 0xFF, 0x12, 0x0C,
+            0x4B, 0x01, 0x5A, 0xB0, 0x9A, 0xB0, 0x8A, 0xB6, 0x81, 0x06, 0x8A, 0xBE, 0xF0, 0x0A, 0xE1, 0x9E,
+            0xE1, 0xA1, 0xF2, 0x33, 0xF2, 0x65, 0x00, 0xEE, 0xB2, 0x00, 0xF0, 0x90, 0x90, 0x90, 0xF0,
+        ],
+        Platform::Chip8,
+    );
+}
+
+#[test]
+fn round_trip_extensions() {
+    // SUPER-CHIP : défilement, haute résolution et drapeaux RPL.
+    round_trip(&[0x00, 0xFF, 0x00, 0xC4, 0x00, 0xFB, 0xF1, 0x30, 0xF7, 0x75, 0xF7, 0x85, 0xD0, 0x10, 0x00, 0xFD], Platform::SuperChip);
+
+    // XO-CHIP : adresse sur 16 bits, plages de registres, plans et son.
+    round_trip(
+        &[0xF0, 0x00, 0x02, 0x10, 0x51, 0x32, 0x51, 0x33, 0xF3, 0x01, 0xF0, 0x02, 0xF1, 0x3A, 0x00, 0xD2, 0x12, 0x0E, 0xAA, 0x55],
+        Platform::XoChip,
+    );
+
+    // MEGA-CHIP : adresse sur 24 bits, palette et sons numérisés.
+    round_trip(&[0x00, 0x11, 0x01, 0x01, 0x02, 0x00, 0x02, 0x04, 0x03, 0x10, 0x06, 0x00, 0x08, 0x05, 0x00, 0xB2, 0x12, 0x10], Platform::MegaChip);
+
+    // CHIP-8X : couleurs et second clavier.
+    round_trip(&[0x02, 0xA0, 0xB1, 0x20, 0xB1, 0x23, 0x51, 0x21, 0xE0, 0xF2, 0x12, 0x00], Platform::Chip8X);
+
+    // CHIP-8 hires : 0230 s'affiche comme 00E0 et doit être écrit avec dw.
+    let mut hires = vec![0x00; 0xC0];
+    hires[..2].copy_from_slice(&[0x12, 0x60]);
+    hires.extend([0x02, 0x30, 0x00, 0xE0, 0x12, 0xC0]);
+    round_trip(&hires, Platform::Chip8);
+}
+
+#[test]
+fn register_ranges_are_not_skips() {
+    // L'instruction qui suit le saut est une donnée, que le désassembleur ne doit pas atteindre.
+    let cases = [
+        // 0300 ADDN V1, V2 ; 0302 JP $0302
+        ([0x51, 0x21, 0x13, 0x02, 0x60, 0x01], Platform::Chip8X, 0x300),
+        // 0200 LD [I], V1-V2 ; 0202 JP $0202
+        ([0x51, 0x22, 0x12, 0x02, 0x60, 0x01], Platform::XoChip, 0x200),
+    ];
+
+    for (rom, platform, load_address) in cases {
+        let listing = disassemble(&rom, platform, load_address).unwrap();
+        let kinds: Vec<_> = listing.lines.iter().map(|line| (line.address - load_address as u32, line.kind)).collect();
+
+        assert_eq!(kinds, [(0, LineKind::Code), (2, LineKind::Code), (4, LineKind::Data)], "{platform:?}");
+    }
+}
+
+#[test]
+fn labels_constants_and_directives() {
+    let source = "
+        SPEED equ 3          ; vitesse du sprite
+    start:
+        LD V0, SPEED
+        LD I, sprite
+        DRW V0, V1, 5
+        JP start
+    sprite: db $F0, %10010000, 144
+        dw $1234
+    ";
+
+    let program = assemble(source, Platform::Chip8, 0x200).unwrap();
+
+    assert_eq!(program, [0x60, 0x03, 0xA2, 0x08, 0xD0, 0x15, 0x12, 0x00, 0xF0, 0x90, 0x90, 0x12, 0x34]);
+}
+
+#[test]
+fn unprefixed_numbers() {
+    // Les octets des instructions sont en hexadécimal comme dans le désassemblage, les autres
+    // nombres en décimal.
+    let program = assemble("LD V0, 10\nDRW V0, V0, 10\nJP 528\ndb 10\ndw 10", Platform::Chip8, 0x200).unwrap();
+
+    assert_eq!(program, [0x60, 0x10, 0xD0, 0x0A, 0x12, 0x10, 0x0A, 0x00, 0x0A]);
+}
+
+#[test]
+fn errors_give_line_and_column() {
+    let errors = [
+        ("CLS\n  JP nowhere", 2, 6),
+        // 100 est lu en hexadécimal et ne tient pas dans un octet.
+        ("  LD V0, 100", 1, 10),
+        // Un symbole qui se lit comme un nombre hexadécimal masquerait l'octet 0xBE.
+        ("BE equ 3\nLD V0, BE", 1, 1),
+        ("face: CLS", 1, 1),
+        ("  SCD 4", 1, 3),
+        ("LD V0,", 1, 7),
+        ("x: CLS\nx: RET", 2, 1),
+    ];
+
+    for (source, expected_line, expected_column) in errors {
+        match assemble(source, Platform::Chip8, 0x200) {
+            Err(Chip8Error::Assembly { line, column, .. }) => assert_eq!((line, column), (expected_line, expected_column), "{source}"),
+            other => panic!("{source}: {other:?}"),
+        }
+    }
+}
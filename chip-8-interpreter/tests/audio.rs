@@ -0,0 +1,68 @@
+use chip_8_interpreter::{
+    audio::playback_rate,
+    chip::{Chip8, Chip8Builder},
+    platform::Platform,
+};
+
+/// Échantillons rendus par `render_audio` à 8000 Hz.
+fn render(chip: &mut Chip8, count: usize) -> Vec<i16> {
+    let mut out = vec![1; count];
+    chip.render_audio(8000, &mut out);
+
+    out
+}
+
+#[test]
+fn pitch_sets_the_playback_rate() {
+    assert_eq!(playback_rate(64), 4000.0);
+    assert_eq!(playback_rate(112), 8000.0);
+    assert_eq!(playback_rate(16), 2000.0);
+}
+
+#[test]
+fn pattern_and_pitch() {
+    let mut rom = vec![
+        0xA2, 0x0C, // 0200 LD I, $020C
+        0xF0, 0x02, // 0202 LD AUDIO, [I]
+        0x60, 0x70, // 0204 LD V0, 70
+        0xF0, 0x3A, // 0206 LD PITCH, V0
+        0xF0, 0x18, // 0208 LD ST, V0
+        0x12, 0x0A, // 020A JP $020A
+    ];
+    rom.extend([0xAA; 16]);
+
+    let mut chip = Chip8Builder::new().platform(Platform::XoChip).build(&rom).unwrap();
+
+    // Le Sound Timer est nul, le motif par défaut n'est pas joué.
+    assert!(render(&mut chip, 64).iter().all(|&sample| sample == 0));
+
+    chip.step(5).unwrap();
+    assert_eq!(chip.borrow_registers().pattern, [0xAA; 16]);
+    assert_eq!(chip.borrow_registers().pitch, 0x70);
+
+    // À 8000 bits par seconde, chaque échantillon correspond à un bit du motif.
+    let samples = render(&mut chip, 64);
+    assert!(samples.iter().step_by(2).all(|&sample| sample > 0));
+    assert!(samples.iter().skip(1).step_by(2).all(|&sample| sample < 0));
+}
+
+#[test]
+fn digitized_sound() {
+    let mut rom = vec![
+        0x01, 0x00, 0x03, 0x00, // 0200 LDHI I, $000300
+        0x06, 0x01, // 0204 DIGISND 1
+        0x12, 0x06, // 0206 JP $0206
+    ];
+    rom.resize(0x100, 0);
+    // 8000 Hz, 4 échantillons.
+    rom.extend([0x1F, 0x40, 0x00, 0x00, 0x04, 0x00, 0xFF, 0x80, 0x00, 0x80]);
+
+    let mut chip = Chip8Builder::new().platform(Platform::MegaChip).build(&rom).unwrap();
+    chip.step(3).unwrap();
+
+    assert_eq!(render(&mut chip, 6), [0x7F * 0x100 / 4, 0, -0x80 * 0x100 / 4, 0, 0, 0]);
+
+    // Le son est terminé et le Sound Timer est nul.
+    assert!(chip.borrow_registers().sample.is_none());
+    assert!(render(&mut chip, 6).iter().all(|&sample| sample == 0));
+}
@@ -0,0 +1,77 @@
+use chip_8_interpreter::{
+    chip::{Chip8, Chip8Builder, HEX_SPRITES},
+    error::Chip8Error,
+};
+
+/// Pixels allumés de la première ligne de l'écran.
+fn lit_columns(chip: &Chip8) -> Vec<usize> {
+    let screen = chip.borrow_screen();
+
+    (0..screen.width()).filter(|&x| screen.get(x, 0) != 0).collect()
+}
+
+#[test]
+fn load_address() {
+    let chip = Chip8::from_rom(&[0x12, 0x34]).unwrap();
+    assert_eq!(chip.fetch_next_instruction().unwrap(), 0x1234);
+
+    let chip = Chip8Builder::new().load_address(0x600).build(&[0x12, 0x34]).unwrap();
+    assert_eq!(chip.fetch_next_instruction().unwrap(), 0x1234);
+}
+
+#[test]
+fn memory_size() {
+    // Par défaut, la RAM fait 4 Kio et le programme est chargé à 0x200.
+    assert!(Chip8::from_rom(&[0; 0xE00]).is_ok());
+    assert!(matches!(Chip8::from_rom(&[0; 0xE01]), Err(Chip8Error::RomTooLarge { size: 0xE01, max: 0xE00 })));
+
+    let builder = || Chip8Builder::new().memory_size(0x800).load_address(0x300);
+    assert!(builder().build(&[0; 0x500]).is_ok());
+    assert!(matches!(builder().build(&[0; 0x501]), Err(Chip8Error::RomTooLarge { size: 0x501, max: 0x500 })));
+
+    // Le programme ne peut pas être chargé au-delà de la fin de la RAM.
+    assert!(matches!(
+        Chip8Builder::new().memory_size(0x100).build(&[0x00, 0xE0]),
+        Err(Chip8Error::RomTooLarge { size: 2, max: 0 })
+    ));
+}
+
+#[test]
+fn empty_rom() {
+    assert!(matches!(Chip8::from_rom(&[]), Err(Chip8Error::EmptyRom)));
+    assert!(matches!(Chip8Builder::new().build(&[]), Err(Chip8Error::EmptyRom)));
+}
+
+#[test]
+fn font() {
+    // LD V0, 0A ; LD F, V0 ; DRW V1, V1, 1
+    let rom = [0x60, 0x0A, 0xF0, 0x29, 0xD1, 0x11];
+
+    // Le caractère A de la police par défaut commence par 0xF0.
+    let mut chip = Chip8::from_rom(&rom).unwrap();
+    chip.step(3).unwrap();
+    assert_eq!(HEX_SPRITES[0x0A * 5], 0xF0);
+    assert_eq!(lit_columns(&chip), [0, 1, 2, 3]);
+
+    // Chaque caractère de cette police est une ligne différente.
+    let font: Vec<u8> = (0..80).map(|index| if index == 0x0A * 5 { 0x81 } else { 0x00 }).collect();
+    let mut chip = Chip8Builder::new().font(&font).build(&rom).unwrap();
+    chip.step(3).unwrap();
+    assert_eq!(lit_columns(&chip), [0, 7]);
+}
+
+#[test]
+fn stack_depth() {
+    // CALL $0200, qui s'appelle lui-même indéfiniment.
+    let rom = [0x22, 0x00];
+
+    let mut chip = Chip8Builder::new().stack_depth(2).build(&rom).unwrap();
+    chip.step(2).unwrap();
+    assert_eq!(chip.call_stack().unwrap().len(), 2);
+    assert!(matches!(chip.step(1), Err(Chip8Error::StackOverflow)));
+
+    // La pile par défaut contient 16 appels.
+    let mut chip = Chip8::from_rom(&rom).unwrap();
+    chip.step(16).unwrap();
+    assert!(matches!(chip.step(1), Err(Chip8Error::StackOverflow)));
+}
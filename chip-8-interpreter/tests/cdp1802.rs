@@ -0,0 +1,351 @@
+use chip_8_interpreter::{
+    cdp1802::{Bus, Cdp1802},
+    chip::Chip8,
+    machine::Machine,
+    vip::Vip,
+};
+
+/// Bus de 256 octets de RAM, sans entrées/sorties, dont les lignes EF1 à EF4 sont pilotées par
+/// le test.
+struct TestBus {
+    memory: [u8; 0x100],
+    flags: [bool; 4],
+}
+
+impl TestBus {
+    fn new(program: &[u8]) -> Self {
+        let mut memory = [0x0; 0x100];
+        memory[..program.len()].copy_from_slice(program);
+
+        Self { memory, flags: [false; 4] }
+    }
+}
+
+impl Bus for TestBus {
+    fn read(&mut self, address: u16) -> u8 {
+        self.memory[address as usize % 0x100]
+    }
+
+    fn write(&mut self, address: u16, value: u8) {
+        self.memory[address as usize % 0x100] = value;
+    }
+
+    fn input(&mut self, _: u8) -> u8 {
+        0x0
+    }
+
+    fn output(&mut self, _: u8, _: u8) {}
+
+    fn flag(&self, line: u8) -> bool {
+        self.flags[line as usize - 1]
+    }
+}
+
+#[test]
+fn arithmetic_sets_df() {
+    let mut bus = TestBus::new(&[
+        0xF8, 0xF0, // 00 LDI F0
+        0xF4, //       02 ADD
+        0xF8, 0x05, // 03 LDI 05
+        0xF5, //       05 SD
+        0xF8, 0x30, // 06 LDI 30
+        0xF5, //       08 SD
+        0xF8, 0x30, // 09 LDI 30
+        0xF7, //       0B SM
+        0xF8, 0x10, // 0C LDI 10
+        0xF7, //       0E SM
+        0xF8, 0xFF, // 0F LDI FF
+        0xFC, 0x01, // 11 ADI 01
+        0x7C, 0x00, // 13 ADCI 00
+    ]);
+    bus.memory[0x80] = 0x20;
+
+    let mut cpu = Cdp1802::new();
+    cpu.r[5] = 0x80;
+    cpu.x = 0x5;
+
+    // D, DF après chaque opération, M(RX) valant 0x20.
+    let expected = [
+        (0x10, true),  // F0 + 20, avec retenue
+        (0x1B, true),  // 20 - 05, sans emprunt
+        (0xF0, false), // 20 - 30, avec emprunt
+        (0x10, true),  // 30 - 20
+        (0xF0, false), // 10 - 20
+        (0x00, true),  // FF + 01
+        (0x01, false), // 00 + 00 + DF
+    ];
+
+    for (d, df) in expected {
+        cpu.step(&mut bus);
+        assert_eq!(cpu.step(&mut bus), 2);
+        assert_eq!((cpu.d, cpu.df), (d, df));
+    }
+}
+
+#[test]
+fn branches_and_long_skips() {
+    let mut bus = TestBus::new(&[
+        0xF8, 0x00, //       00 LDI 00
+        0x32, 0x06, //       02 BZ 06
+        0x00, 0x00, //       04
+        0x3A, 0x00, //       06 BNZ 00
+        0xC8, //             08 LSKP
+        0x00, 0x00, //       09
+        0xCE, //             0B LSZ
+        0x00, 0x00, //       0C
+        0xC0, 0x00, 0x20, // 0E LBR 0020
+    ]);
+    bus.memory[0x20..0x24].copy_from_slice(&[
+        0x34, 0x30, // 20 B1 30
+        0x34, 0x40, // 22 B1 40
+    ]);
+
+    let mut cpu = Cdp1802::new();
+
+    // Adresse de l'instruction suivante et cycles écoulés.
+    let expected = [(0x02, 2), (0x06, 2), (0x08, 2), (0x0B, 3), (0x0E, 3), (0x20, 3), (0x22, 2)];
+
+    for (pc, cycles) in expected {
+        assert_eq!(cpu.step(&mut bus), cycles);
+        assert_eq!(cpu.pc(), pc);
+    }
+
+    bus.flags[0] = true;
+    cpu.step(&mut bus);
+    assert_eq!(cpu.pc(), 0x40);
+}
+
+#[test]
+fn mark_saves_x_and_p_for_ret() {
+    let mut bus = TestBus::new(&[
+        0xD3, // 00 SEP 3
+    ]);
+    bus.memory[0x10..0x13].copy_from_slice(&[
+        0xE5, // 10 SEX 5
+        0x79, // 11 MARK
+        0xD4, // 12 SEP 4
+    ]);
+    bus.memory[0x40..0x43].copy_from_slice(&[
+        0xE2, // 40 SEX 2
+        0x12, // 41 INC R2
+        0x70, // 42 RET
+    ]);
+
+    let mut cpu = Cdp1802::new();
+    cpu.r[2] = 0x7F;
+    cpu.r[3] = 0x10;
+    cpu.r[4] = 0x40;
+
+    cpu.step(&mut bus);
+    assert_eq!((cpu.p, cpu.pc()), (0x3, 0x10));
+
+    cpu.step(&mut bus);
+    assert_eq!(cpu.x, 0x5);
+
+    // MARK sauvegarde X et P dans T et sur la pile, puis X prend la valeur de P.
+    cpu.step(&mut bus);
+    assert_eq!((cpu.t, bus.memory[0x7F], cpu.x, cpu.r[2]), (0x53, 0x53, 0x3, 0x7E));
+
+    cpu.step(&mut bus);
+    assert_eq!(cpu.pc(), 0x40);
+
+    cpu.ie = false;
+    cpu.step(&mut bus);
+    cpu.step(&mut bus);
+    cpu.step(&mut bus);
+
+    // RET restaure X et P, et autorise les interruptions.
+    assert_eq!((cpu.x, cpu.p, cpu.pc(), cpu.r[2]), (0x5, 0x3, 0x13, 0x80));
+    assert!(cpu.ie);
+}
+
+#[test]
+fn interrupts_need_ie() {
+    let mut bus = TestBus::new(&[
+        0x00, // 00 IDL
+    ]);
+    bus.memory[0x20..0x23].copy_from_slice(&[
+        0x22, // 20 DEC R2
+        0x78, // 21 SAV
+        0x70, // 22 RET
+    ]);
+
+    let mut cpu = Cdp1802::new();
+    cpu.r[1] = 0x20;
+    cpu.r[2] = 0x80;
+    cpu.x = 0x5;
+
+    cpu.step(&mut bus);
+    assert!(cpu.idle);
+    assert_eq!(cpu.step(&mut bus), 1);
+
+    assert_eq!(cpu.interrupt(), 1);
+    assert!(!cpu.idle);
+    assert!(!cpu.ie);
+    assert_eq!((cpu.t, cpu.x, cpu.p), (0x50, 0x2, 0x1));
+
+    // Une seconde demande est ignorée tant que la routine n'est pas terminée.
+    assert_eq!(cpu.interrupt(), 0);
+    assert_eq!(cpu.t, 0x50);
+
+    cpu.step(&mut bus);
+    cpu.step(&mut bus);
+    cpu.step(&mut bus);
+
+    assert_eq!((cpu.x, cpu.p, cpu.pc(), cpu.r[2]), (0x5, 0x0, 0x1, 0x80));
+    assert!(cpu.ie);
+}
+
+/// Moniteur qui passe sur R3, active l'affichage par INP 1, puis recopie à l'adresse 0x10 l'état
+/// de la touche 5 lu par EF3.
+/// Sa routine d'interruption fait pointer R0 sur l'adresse 0x100.
+fn display_monitor() -> Vec<u8> {
+    let mut monitor = vec![
+        0xF8, 0x80, 0xB3, //       00 R3 = 8007
+        0xF8, 0x07, 0xA3, //       03
+        0xD3, //                   06 SEP 3
+        0xF8, 0x80, 0xB1, //       07 R1 = 8042
+        0xF8, 0x42, 0xA1, //       0A
+        0xF8, 0x00, 0xB2, //       0D R2 = 00FF
+        0xF8, 0xFF, 0xA2, //       10
+        0xF8, 0x80, 0xB4, //       13 R4 = 8060
+        0xF8, 0x60, 0xA4, //       16
+        0xF8, 0x00, 0xB5, //       19 R5 = 0010
+        0xF8, 0x10, 0xA5, //       1C
+        0xE4, //                   1F SEX 4
+        0x69, //                   20 INP 1
+        0x62, //                   21 OUT 2
+        0x24, //                   22 DEC R4
+        0xF8, 0x00, //             23 LDI 00
+        0x3E, 0x29, //             25 BN3 29
+        0xF8, 0x01, //             27 LDI 01
+        0x55, //                   29 STR R5
+        0x30, 0x21, //             2A BR 21
+    ];
+    monitor.resize(0x40, 0x0);
+    monitor.extend([
+        0x72, //                   40 LDXA
+        0x70, //                   41 RET
+        0x22, 0x78, 0x22, 0x52, // 42 DEC R2, SAV, DEC R2, STR R2
+        0xF8, 0x01, 0xB0, //       46 R0 = 0100
+        0xF8, 0x00, 0xA0, //       49
+        0x30, 0x40, //             4C BR 40
+    ]);
+    monitor.resize(0x60, 0x0);
+    monitor.push(0x05);
+    monitor.resize(0x100, 0x0);
+
+    monitor
+}
+
+#[test]
+fn vip_frame_reads_display_and_keypad() {
+    // Les 128 lignes sont lues en DMA de 0x100 à 0x500.
+    let mut interpreter = vec![0x0; 0x200];
+    interpreter[0x100] = 0xFF;
+    interpreter[0x107] = 0x01;
+    let mut program = vec![0x0; 0x300];
+    program[0x4F8 - 0x200] = 0x80;
+
+    let mut vip = Vip::new(&display_monitor(), &interpreter, &program).unwrap();
+    vip.run_frame().unwrap();
+
+    assert_eq!(vip.frames(), 1);
+    assert!(vip.is_display_on());
+    assert_eq!(vip.cpu().r[0], 0x500);
+
+    let lit = vip.display().iter().filter(|&&pixel| pixel == 1).count();
+    assert_eq!(lit, 10);
+    assert_eq!((0..8).map(|x| vip.display_pixel(x, 0)).collect::<Vec<_>>(), [1; 8]);
+    assert_eq!(vip.display_pixel(63, 0), 1);
+    assert_eq!(vip.display_pixel(0, 127), 1);
+
+    // L'interruption a eu lieu pendant la boucle, où X vaut 4 et P vaut 3, et la routine a rendu
+    // la main.
+    assert_eq!(vip.cpu().t, 0x43);
+    assert_eq!((vip.cpu().p, vip.cpu().r[2]), (0x3, 0xFF));
+    assert!(vip.cpu().ie);
+
+    assert_eq!(vip.memory()[0x10], 0x0);
+
+    vip.set_key_pressed(0x5, true).unwrap();
+    vip.run_frame().unwrap();
+    assert_eq!(vip.memory()[0x10], 0x1);
+
+    // EF3 ne reflète que la touche choisie par OUT 2.
+    vip.set_key_pressed(0x5, false).unwrap();
+    vip.set_key_pressed(0x6, true).unwrap();
+    vip.run_frame().unwrap();
+    assert_eq!(vip.memory()[0x10], 0x0);
+}
+
+/// Interpréteur réduit à 1NNN, 6XNN et FX18, qui active Q tant que VX ne vaut pas 0.
+/// Les registres V0 à VF sont placés à l'adresse 0xEF0 comme dans l'interpréteur d'origine.
+const INTERPRETER: [u8; 0x4B] = [
+    0xF8, 0x00, 0xB3, //       00 R3 = 0007
+    0xF8, 0x07, 0xA3, //       03
+    0xD3, //                   06 SEP 3
+    0xF8, 0x02, 0xB5, //       07 R5 = 0200
+    0xF8, 0x00, 0xA5, //       0A
+    0xF8, 0x0E, 0xB6, //       0D R6 = 0EF0 + X
+    0x45, 0xAF, //             10 RF = M(R5++)
+    0xFA, 0x0F, 0xFC, 0xF0, // 12 ANI 0F, ADI F0
+    0xA6, //                   16 PLO R6
+    0x45, 0xAE, //             17 RE = M(R5++)
+    0x8F, //                   19 GLO RF
+    0xF6, 0xF6, 0xF6, 0xF6, // 1A SHR x4
+    0xFF, 0x01, 0x32, 0x30, // 1E 1NNN
+    0xFF, 0x05, 0x32, 0x38, // 22 6XNN
+    0xFF, 0x09, 0x32, 0x40, // 26 FXNN
+    0x30, 0x10, //             2A BR 10
+    0x00, 0x00, 0x00, 0x00, // 2C
+    0x8F, 0xFA, 0x0F, 0xB5, // 30 R5 = NNN
+    0x8E, 0xA5, //             34
+    0x30, 0x10, //             36 BR 10
+    0x8E, 0x56, //             38 VX = NN
+    0x30, 0x10, //             3A BR 10
+    0x00, 0x00, 0x00, 0x00, // 3C
+    0x8E, 0xFB, 0x18, //       40 XRI 18
+    0x3A, 0x10, //             43 BNZ 10
+    0x06, //                   45 LDN R6
+    0x32, 0x10, //             46 BZ 10
+    0x7B, //                   48 SEQ
+    0x30, 0x10, //             49 BR 10
+];
+
+/// Indique si la machine sonne avant, puis après une frame.
+fn beeps(machine: &mut dyn Machine) -> (bool, bool) {
+    let before = machine.is_beeping();
+    machine.run_frame().unwrap();
+
+    (before, machine.is_beeping())
+}
+
+#[test]
+fn chip8_and_vip_agree() {
+    // Le moniteur se contente de sauter à l'interpréteur.
+    let mut monitor = vec![
+        0xC0, 0x80, 0x03, // 00 LBR 8003
+        0xC0, 0x00, 0x00, // 03 LBR 0000
+    ];
+    monitor.resize(0x100, 0x0);
+
+    for (value, beeping) in [(0x3C, true), (0x00, false)] {
+        let rom = [
+            0x60, value, // 0200 LD V0, value
+            0xF0, 0x18, // 0202 LD ST, V0
+            0x12, 0x04, // 0204 JP 0204
+        ];
+
+        let mut chip = Chip8::from_rom(&rom).unwrap();
+        let mut vip = Vip::new(&monitor, &INTERPRETER, &rom).unwrap();
+
+        for machine in [&mut chip as &mut dyn Machine, &mut vip] {
+            assert_eq!(beeps(machine), (false, beeping));
+            assert!(!machine.is_halted());
+            assert_eq!(machine.display_pixel(0, 0), 0);
+        }
+    }
+
+    assert_eq!(Vip::new(&monitor, &INTERPRETER, &[]).unwrap().display_size(), (64, 128));
+}
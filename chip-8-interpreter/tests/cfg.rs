@@ -0,0 +1,128 @@
+use chip_8_interpreter::{
+    cfg::{analyze, ByteKind, Edge, EdgeKind},
+    platform::Platform,
+};
+
+/// Programme qui dessine une image dans une fonction, boucle tant qu'aucune touche n'est pressée
+/// puis saute dans une table avec BNNN.
+const ROM: [u8; 26] = [
+    0x22, 0x08, // 0200 CALL $0208
+    0xE0, 0xA1, // 0202 SKNP V0
+    0xB2, 0x12, // 0204 JP V0, $0212
+    0x12, 0x02, // 0206 JP $0202
+    0xA2, 0x14, // 0208 LD I, $0214
+    0x60, 0x00, // 020A LD V0, 00
+    0xD0, 0x03, // 020C DRW V0, V0, 3
+    0x00, 0xEE, // 020E RET
+    0xFF, 0xFF, // 0210 jamais exécuté
+    0x00, 0xE0, // 0212 jamais analysé, cible d'un saut calculé
+    0xF0, 0x90, 0xF0, // 0214 image
+    0x00, 0x00, 0x00,
+];
+
+#[test]
+fn blocks_and_call_graph() {
+    let graph = analyze(&ROM, Platform::Chip8, 0x200).unwrap();
+
+    assert_eq!(graph.blocks.keys().copied().collect::<Vec<_>>(), [0x200, 0x202, 0x204, 0x206, 0x208]);
+
+    assert_eq!(graph.blocks[&0x200].call, Some(0x208));
+    assert_eq!(graph.blocks[&0x200].successors, [Edge { target: 0x202, kind: EdgeKind::Return }]);
+    assert_eq!(
+        graph.blocks[&0x202].successors,
+        [Edge { target: 0x204, kind: EdgeKind::Fallthrough }, Edge { target: 0x206, kind: EdgeKind::Skip }]
+    );
+    assert_eq!(graph.blocks[&0x206].successors, [Edge { target: 0x202, kind: EdgeKind::Jump }]);
+    assert_eq!(graph.blocks[&0x208].instructions.len(), 4);
+    assert!(graph.blocks[&0x208].successors.is_empty());
+
+    assert_eq!(graph.functions.keys().copied().collect::<Vec<_>>(), [0x200, 0x208]);
+    assert_eq!(graph.functions[&0x200].blocks, [0x200, 0x202, 0x204, 0x206]);
+    assert_eq!(graph.functions[&0x200].calls, [0x208]);
+    assert_eq!(graph.functions[&0x208].blocks, [0x208]);
+}
+
+#[test]
+fn sprites_and_unresolved_jumps() {
+    let graph = analyze(&ROM, Platform::Chip8, 0x200).unwrap();
+
+    assert_eq!(graph.unresolved_jumps, [0x204]);
+    assert!(graph.blocks[&0x204].unresolved);
+
+    assert_eq!(graph.sprites.len(), 1);
+    assert_eq!(graph.sprites[&0x214].size, 3);
+    assert_eq!(graph.sprites[&0x214].drawn_by, [0x208]);
+
+    assert_eq!(graph.byte_kind(0x200), Some(ByteKind::Code));
+    assert_eq!(graph.byte_kind(0x210), Some(ByteKind::Unknown));
+    assert_eq!(graph.byte_kind(0x214), Some(ByteKind::Sprite));
+    assert_eq!(graph.byte_kind(0x216), Some(ByteKind::Sprite));
+    assert_eq!(graph.byte_kind(0x217), Some(ByteKind::Unknown));
+    assert_eq!(graph.byte_kind(0x300), None);
+}
+
+#[test]
+fn dot_export() {
+    let dot = analyze(&ROM, Platform::Chip8, 0x200).unwrap().to_dot();
+
+    for expected in [
+        "digraph cfg {",
+        "subgraph cluster_0208 {",
+        "b_0200 -> b_0208 [label=\"call\", style=dashed];",
+        "b_0206 -> b_0202 [label=\"jump\"];",
+        "b_0202 -> b_0206 [label=\"skip\"];",
+        "b_0204 -> u_0204",
+        "b_0208 -> s_0214 [style=dotted];",
+        "020C  DRW V0, V0, 3\\l",
+    ] {
+        assert!(dot.contains(expected), "{expected}\n{dot}");
+    }
+}
+
+#[test]
+fn sprite_address_follows_single_predecessors() {
+    let rom = [
+        0xA2, 0x10, // 0200 LD I, $0210
+        0x3A, 0x00, // 0202 SE VA, 00
+        0x12, 0x08, // 0204 JP $0208
+        0xD0, 0x02, // 0206 DRW V0, V0, 2
+        0xD0, 0x04, // 0208 DRW V0, V0, 4
+        0xA2, 0x14, // 020A LD I, $0214
+        0x12, 0x0E, // 020C JP $020E
+        0xD0, 0x01, // 020E DRW V0, V0, 1
+        0xFF, 0xFF, 0xFF, 0xFF, 0xFF, // 0210 images
+    ];
+    let graph = analyze(&rom, Platform::Chip8, 0x200).unwrap();
+
+    // 0206 n'est atteint que depuis 0202, 020E que depuis 020C, mais 0208 a deux prédécesseurs.
+    assert_eq!(graph.sprites.keys().copied().collect::<Vec<_>>(), [0x210, 0x214]);
+    assert_eq!(graph.sprites[&0x210].size, 2);
+    assert_eq!(graph.sprites[&0x210].drawn_by, [0x206]);
+    assert_eq!(graph.sprites[&0x214].drawn_by, [0x20E]);
+}
+
+#[test]
+fn xo_chip_sprites() {
+    let rom = [
+        0xF0, 0x00, 0x02, 0x10, // 0200 LD I, LONG $0210
+        0xF3, 0x01, // 0204 PLANE 3
+        0xD0, 0x03, // 0206 DRW V0, V0, 3
+        0x12, 0x08, // 0208 JP $0208
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // 020A
+        0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, // 0210 image sur deux plans
+    ];
+    let graph = analyze(&rom, Platform::XoChip, 0x200).unwrap();
+
+    assert_eq!(graph.sprites[&0x210].size, 6);
+    assert_eq!(graph.byte_kind(0x215), Some(ByteKind::Sprite));
+}
+
+#[test]
+fn register_range_is_not_a_skip() {
+    // 0200 LD I, $0208 ; 0202 LD [I], V1-V2 ; 0204 CLS ; 0206 JP $0206
+    let rom = [0xA2, 0x08, 0x51, 0x22, 0x00, 0xE0, 0x12, 0x06];
+    let graph = analyze(&rom, Platform::XoChip, 0x200).unwrap();
+
+    assert_eq!(graph.blocks.keys().copied().collect::<Vec<_>>(), [0x200, 0x206]);
+    assert_eq!(graph.blocks[&0x200].successors, [Edge { target: 0x206, kind: EdgeKind::Fallthrough }]);
+}
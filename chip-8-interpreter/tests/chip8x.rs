@@ -0,0 +1,124 @@
+use chip_8_interpreter::{
+    chip::{Chip8, Chip8Builder},
+    error::Chip8Error,
+    platform::{Platform, CHIP8X_LOAD_ADDRESS},
+    screen::{BACKGROUND_COLORS, DEFAULT_FOREGROUND_COLOR},
+};
+
+fn chip8x<'a>(rom: &[u8]) -> Chip8<'a> {
+    Chip8Builder::new().platform(Platform::Chip8X).build(rom).unwrap()
+}
+
+#[test]
+fn program_starts_at_0x300() {
+    let chip = chip8x(&[0x12, 0x34]);
+
+    assert_eq!(chip.borrow_registers().pc, CHIP8X_LOAD_ADDRESS);
+    assert_eq!(chip.fetch_next_instruction().unwrap(), 0x1234);
+}
+
+#[test]
+fn background_color_cycles() {
+    let rom = [
+        0x02, 0xA0, // 0300 BGC
+        0x13, 0x00, // 0302 JP 0300
+    ];
+
+    let mut chip = chip8x(&rom);
+    assert_eq!(chip.borrow_screen().background_color(), BACKGROUND_COLORS[0]);
+
+    for color in BACKGROUND_COLORS.iter().cycle().skip(1).take(5) {
+        chip.step(2).unwrap();
+        assert_eq!(chip.borrow_screen().background_color(), *color);
+    }
+}
+
+#[test]
+fn nibbles_wrap_around() {
+    let rom = [
+        0x60, 0x37, // 0300 LD V0, 37
+        0x61, 0x75, // 0302 LD V1, 75
+        0x50, 0x11, // 0304 ADDN V0, V1
+    ];
+
+    let mut chip = chip8x(&rom);
+    chip.step(3).unwrap();
+
+    // Chaque quartet est additionné sur 3 bits, sans retenue vers le suivant.
+    assert_eq!(chip.borrow_registers().v[0x0], 0x24);
+}
+
+#[test]
+fn color_zones() {
+    let rom = [
+        0x60, 0x21, // 0300 LD V0, 21
+        0x61, 0x10, // 0302 LD V1, 10
+        0x62, 0x05, // 0304 LD V2, 05
+        0xB0, 0x20, // 0306 COL V0, V2
+        0x63, 0x14, // 0308 LD V3, 14
+        0x64, 0x0A, // 030A LD V4, 0A
+        0x65, 0x06, // 030C LD V5, 06
+        0xB3, 0x53, // 030E COL V3, V5, 3
+    ];
+
+    let mut chip = chip8x(&rom);
+    chip.step(8).unwrap();
+
+    let screen = chip.borrow_screen();
+    let color = |x, y| screen.foreground_color(x, y);
+
+    // 3 zones à partir de la zone 1, sur 2 zones de 4 lignes à partir de la ligne 0.
+    assert_eq!((color(8, 0), color(31, 7)), (5, 5));
+    assert_eq!(color(7, 0), DEFAULT_FOREGROUND_COLOR);
+    assert_eq!(color(32, 0), DEFAULT_FOREGROUND_COLOR);
+    assert_eq!(color(8, 8), DEFAULT_FOREGROUND_COLOR);
+
+    // 3 lignes à partir du pixel (20, 10), dans la zone qui le contient.
+    assert_eq!((color(16, 10), color(23, 12)), (6, 6));
+    assert_eq!(color(16, 9), DEFAULT_FOREGROUND_COLOR);
+    assert_eq!(color(16, 13), DEFAULT_FOREGROUND_COLOR);
+    assert_eq!(color(24, 10), DEFAULT_FOREGROUND_COLOR);
+}
+
+#[test]
+fn color_needs_a_register_after_x() {
+    // COL VF, V2 et COL VF, V2, 1.
+    for rom in [[0xBF, 0x20], [0xBF, 0x21]] {
+        let mut chip = chip8x(&rom);
+
+        assert!(matches!(chip.step(1), Err(Chip8Error::InvalidRegister(0x10))));
+        assert_eq!(chip.borrow_registers().pc, CHIP8X_LOAD_ADDRESS);
+        assert_eq!(chip.borrow_screen().foreground_color(0, 0), DEFAULT_FOREGROUND_COLOR);
+    }
+}
+
+/// Exécute EXF2 puis EXF5 sur la touche 3 et indique lesquelles n'ont pas ignoré l'instruction
+/// suivante.
+fn second_keypad(first: bool, second: bool) -> (u8, u8) {
+    let rom = [
+        0x63, 0x03, // 0300 LD V3, 03
+        0xE3, 0xF2, // 0302 SKP2 V3
+        0x64, 0x01, // 0304 LD V4, 01
+        0xE3, 0xF5, // 0306 SKNP2 V3
+        0x65, 0x01, // 0308 LD V5, 01
+        0x13, 0x0A, // 030A JP 030A
+    ];
+
+    let mut chip = chip8x(&rom);
+    chip.set_key_pressed(0x3, first).unwrap();
+    chip.set_second_key_pressed(0x3, second).unwrap();
+    chip.step(6).unwrap();
+
+    let registers = chip.borrow_registers();
+
+    (registers.v[0x4], registers.v[0x5])
+}
+
+#[test]
+fn second_keypad_skips() {
+    assert_eq!(second_keypad(false, false), (1, 0));
+    assert_eq!(second_keypad(false, true), (0, 1));
+
+    // Le premier clavier n'a aucun effet.
+    assert_eq!(second_keypad(true, false), (1, 0));
+}
@@ -0,0 +1,50 @@
+use chip_8_interpreter::chip::{Chip8, Chip8Builder};
+
+#[test]
+fn timers_decrement_once_per_frame() {
+    // LD V0, 0A ; LD DT, V0 ; LD ST, V0 ; JP $0206
+    let rom = [0x60, 0x0A, 0xF0, 0x15, 0xF0, 0x18, 0x12, 0x06];
+    let mut chip = Chip8Builder::new().instructions_per_frame(10).build(&rom).unwrap();
+
+    for frame in 1..=10 {
+        chip.run_frame().unwrap();
+
+        let registers = chip.borrow_registers();
+        assert_eq!((registers.dt, registers.st), (10 - frame, 10 - frame), "frame {frame}");
+    }
+
+    // Les timers s'arrêtent à 0.
+    chip.run_frame().unwrap();
+    assert_eq!((chip.borrow_registers().dt, chip.borrow_registers().st), (0, 0));
+}
+
+#[test]
+fn instructions_per_frame() {
+    // JP $0200
+    let mut chip = Chip8Builder::new().instructions_per_frame(7).build(&[0x12, 0x00]).unwrap();
+
+    chip.run_frame().unwrap();
+    assert_eq!((chip.borrow_clock().frames(), chip.borrow_clock().instructions()), (1, 7));
+
+    chip.run_frame().unwrap();
+    chip.run_frame().unwrap();
+    assert_eq!((chip.borrow_clock().frames(), chip.borrow_clock().instructions()), (3, 21));
+
+    chip.set_instructions_per_frame(3);
+    chip.run_frame().unwrap();
+    assert_eq!((chip.borrow_clock().frames(), chip.borrow_clock().instructions()), (4, 24));
+}
+
+#[test]
+fn step_executes_one_instruction() {
+    // LD V0, 01 ; ADD V0, 01 ; ADD V0, 01 ; ADD V0, 01
+    let mut chip = Chip8::from_rom(&[0x60, 0x01, 0x70, 0x01, 0x70, 0x01, 0x70, 0x01]).unwrap();
+
+    chip.step(1).unwrap();
+    assert_eq!((chip.borrow_registers().v[0], chip.borrow_registers().pc), (1, 0x202));
+    assert_eq!(chip.borrow_clock().instructions(), 1);
+
+    chip.step(2).unwrap();
+    assert_eq!((chip.borrow_registers().v[0], chip.borrow_registers().pc), (3, 0x206));
+    assert_eq!(chip.borrow_clock().instructions(), 3);
+}
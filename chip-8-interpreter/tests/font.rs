@@ -0,0 +1,61 @@
+use std::{env, fs};
+
+use chip_8_interpreter::{
+    chip::{Chip8Builder, BIG_HEX_SPRITES},
+    error::Chip8Error,
+    font::{load_font, BIG_FONT_GLYPH_HEIGHT, FISH_N_CHIPS_HEX_SPRITES, FONT_GLYPH_HEIGHT},
+    platform::Platform,
+};
+
+#[test]
+fn font_address() {
+    let rom = [
+        0x60, 0x02, // 0200 LD V0, 02
+        0xF0, 0x29, // 0202 LD F, V0
+        0xD1, 0x15, // 0204 DRW V1, V1, 5
+        0xF0, 0x30, // 0206 LD HF, V0
+        0x61, 0x10, // 0208 LD V1, 10
+        0xD1, 0x1A, // 020A DRW V1, V1, 10
+    ];
+
+    let mut chip = Chip8Builder::new()
+        .platform(Platform::SuperChip)
+        .font(&FISH_N_CHIPS_HEX_SPRITES)
+        .font_address(0x50)
+        .build(&rom)
+        .unwrap();
+
+    chip.step(2).unwrap();
+    assert_eq!(chip.borrow_registers().i, 0x50 + 2 * FONT_GLYPH_HEIGHT as u32);
+
+    // Le 2 de cette police commence par 0xC0.
+    chip.step(1).unwrap();
+    let screen = chip.borrow_screen();
+    assert_eq!((0..8).map(|x| screen.get(x, 0)).collect::<Vec<_>>(), [1, 1, 0, 0, 0, 0, 0, 0]);
+
+    // Les grands caractères sont copiés juste après la police.
+    chip.step(1).unwrap();
+    assert_eq!(chip.borrow_registers().i, 0x50 + 80 + 2 * BIG_FONT_GLYPH_HEIGHT as u32);
+
+    chip.step(2).unwrap();
+    let screen = chip.borrow_screen();
+    let row = BIG_HEX_SPRITES[2 * BIG_FONT_GLYPH_HEIGHT + 2];
+    assert_eq!((0..8).map(|x| screen.get(0x10 + x, 0x12)).collect::<Vec<_>>(), (0..8).map(|bit| (row >> (7 - bit)) & 1).collect::<Vec<_>>());
+}
+
+#[test]
+fn load_font_checks_the_size() {
+    let path = env::temp_dir().join(format!("chip8-font-{}.bin", std::process::id()));
+
+    fs::write(&path, [0xF0; 80]).unwrap();
+    assert_eq!(load_font(&path, FONT_GLYPH_HEIGHT).unwrap(), [0xF0; 80]);
+    assert!(matches!(load_font(&path, BIG_FONT_GLYPH_HEIGHT), Err(Chip8Error::InvalidFont { size: 80, expected: 160 })));
+
+    fs::write(&path, [0xF0; 79]).unwrap();
+    assert!(matches!(load_font(&path, FONT_GLYPH_HEIGHT), Err(Chip8Error::InvalidFont { size: 79, expected: 80 })));
+
+    fs::write(&path, [0xF0; 81]).unwrap();
+    assert!(matches!(load_font(&path, FONT_GLYPH_HEIGHT), Err(Chip8Error::InvalidFont { size: 81, expected: 80 })));
+
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,55 @@
+use chip_8_interpreter::{
+    chip::Chip8Builder,
+    platform::{Platform, HIRES_CHIP8_HEADER, HIRES_CHIP8_START_ADDRESS},
+};
+
+/// Programme CHIP-8 hires qui dessine le caractère 0 en bas de l'écran, puis l'efface avec 0230.
+fn hires_rom() -> Vec<u8> {
+    let mut rom = HIRES_CHIP8_HEADER.to_vec();
+    rom.resize((HIRES_CHIP8_START_ADDRESS - 0x200) as usize, 0x0);
+    rom.extend([
+        0x60, 0x00, // 02C0 LD V0, 00
+        0x61, 0x3C, // 02C2 LD V1, 3C
+        0xF0, 0x29, // 02C4 LD F, V0
+        0xD0, 0x15, // 02C6 DRW V0, V1, 5
+        0x02, 0x30, // 02C8 CLS
+    ]);
+
+    rom
+}
+
+#[test]
+fn header_is_detected() {
+    let rom = hires_rom();
+
+    assert_eq!(Platform::Chip8.detect(&rom, 0x200), Platform::Chip8Hires);
+    assert_eq!(Platform::Chip8.detect(&[0x12, 0x00], 0x200), Platform::Chip8);
+
+    // Seul le CHIP-8 classique chargé à l'adresse habituelle est concerné.
+    assert_eq!(Platform::Chip8.detect(&rom, 0x600), Platform::Chip8);
+    assert_eq!(Platform::SuperChip.detect(&rom, 0x200), Platform::SuperChip);
+}
+
+#[test]
+fn program_starts_after_the_header() {
+    let chip = Chip8Builder::new().build(&hires_rom()).unwrap();
+
+    assert_eq!(*chip.borrow_platform(), Platform::Chip8Hires);
+    assert_eq!(chip.borrow_registers().pc, HIRES_CHIP8_START_ADDRESS);
+    assert_eq!(chip.fetch_next_instruction().unwrap(), 0x6000);
+}
+
+#[test]
+fn screen_is_64x64() {
+    let mut chip = Chip8Builder::new().build(&hires_rom()).unwrap();
+    assert_eq!((chip.borrow_screen().width(), chip.borrow_screen().height()), (64, 64));
+
+    // Le caractère dépasse du bas de l'écran et revient en haut.
+    chip.step(4).unwrap();
+    let screen = chip.borrow_screen();
+    assert_eq!((screen.get(0, 60), screen.get(0, 63), screen.get(0, 0)), (1, 1, 1));
+    assert_eq!(screen.get(0, 1), 0);
+
+    chip.step(1).unwrap();
+    assert!(chip.borrow_screen().pixels().iter().all(|&pixel| pixel == 0));
+}
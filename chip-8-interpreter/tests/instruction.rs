@@ -0,0 +1,50 @@
+use chip_8_interpreter::chip::Chip8;
+
+#[test]
+fn subtractions_wrap_around() {
+    let rom = [
+        0x60, 0x05, // 0200 LD V0, 05
+        0x61, 0x03, // 0202 LD V1, 03
+        0x80, 0x17, // 0204 SUBN V0, V1
+        0x62, 0x01, // 0206 LD V2, 01
+        0x82, 0x05, // 0208 SUB V2, V0
+    ];
+    let mut chip = Chip8::from_rom(&rom).unwrap();
+
+    chip.step(3).unwrap();
+    assert_eq!((chip.borrow_registers().v[0], chip.borrow_registers().v[0xF]), (0xFE, 0));
+
+    chip.step(2).unwrap();
+    assert_eq!((chip.borrow_registers().v[2], chip.borrow_registers().v[0xF]), (0x03, 0));
+}
+
+/// Valeur de VF après l'opération `opcode` sur VF et VE, qui valent `vf` et `ve`.
+fn flag_after(opcode: u16, vf: u8, ve: u8) -> u8 {
+    let mut rom = vec![0x6F, vf, 0x6E, ve];
+    rom.extend(opcode.to_be_bytes());
+
+    let mut chip = Chip8::from_rom(&rom).unwrap();
+    chip.step(3).unwrap();
+
+    chip.borrow_registers().v[0xF]
+}
+
+#[test]
+fn flag_is_kept_when_x_is_f() {
+    // ADD VF, VE
+    assert_eq!(flag_after(0x8FE4, 0xFF, 0x02), 1);
+    assert_eq!(flag_after(0x8FE4, 0x01, 0x02), 0);
+    // SUB VF, VE : VF vaut 1 sans emprunt, y compris lorsque les opérandes sont égaux.
+    assert_eq!(flag_after(0x8FE5, 0x05, 0x03), 1);
+    assert_eq!(flag_after(0x8FE5, 0x05, 0x05), 1);
+    assert_eq!(flag_after(0x8FE5, 0x03, 0x05), 0);
+    // SUBN VF, VE
+    assert_eq!(flag_after(0x8FE7, 0x03, 0x05), 1);
+    assert_eq!(flag_after(0x8FE7, 0x05, 0x05), 1);
+    assert_eq!(flag_after(0x8FE7, 0x05, 0x03), 0);
+    // SHR VF et SHL VF
+    assert_eq!(flag_after(0x8FE6, 0x03, 0x00), 1);
+    assert_eq!(flag_after(0x8FE6, 0x02, 0x00), 0);
+    assert_eq!(flag_after(0x8FEE, 0x80, 0x00), 1);
+    assert_eq!(flag_after(0x8FEE, 0x7F, 0x00), 0);
+}
@@ -0,0 +1,103 @@
+use std::cell::RefCell;
+
+use chip_8_interpreter::{
+    chip::{Chip8, Chip8Builder},
+    layout::MemoryLayout,
+};
+
+fn vip<'a>(rom: &[u8]) -> Chip8<'a> {
+    Chip8Builder::new().memory_layout(MemoryLayout::CosmacVip).build(rom).unwrap()
+}
+
+#[test]
+fn registers_are_read_back_from_ram() {
+    let rom = [
+        0x60, 0x42, // 0200 LD V0, 42
+        0xAE, 0xF5, // 0202 LD I, EF5
+        0xF0, 0x55, // 0204 LD [I], V0
+        0x12, 0x06, // 0206 JP 0206
+    ];
+
+    let mut chip = vip(&rom);
+    chip.step(4).unwrap();
+
+    // V0 a été écrit à l'adresse de V5.
+    assert_eq!(chip.borrow_registers().v[0x5], 0x42);
+
+    let mut chip = Chip8::from_rom(&rom).unwrap();
+    chip.step(4).unwrap();
+
+    assert_eq!(chip.borrow_registers().v[0x5], 0x00);
+}
+
+#[test]
+fn return_address_is_kept_in_ram() {
+    let rom = [
+        0x22, 0x06, // 0200 CALL 0206
+        0x6B, 0x01, // 0202 LD VB, 01
+        0x12, 0x04, // 0204 JP 0204
+        0xAE, 0xCE, // 0206 LD I, ECE
+        0xF1, 0x65, // 0208 LD V1, [I]
+        0x82, 0x00, // 020A LD V2, V0
+        0x83, 0x10, // 020C LD V3, V1
+        0x61, 0x16, // 020E LD V1, 16
+        0xAE, 0xCE, // 0210 LD I, ECE
+        0xF1, 0x55, // 0212 LD [I], V1
+        0x00, 0xEE, // 0214 RET
+        0x6C, 0x01, // 0216 LD VC, 01
+        0x12, 0x18, // 0218 JP 0218
+    ];
+
+    let mut chip = vip(&rom);
+    chip.step(12).unwrap();
+
+    let registers = chip.borrow_registers();
+
+    // CALL a écrit l'adresse de retour 0x202 en 0xECE et 0xECF.
+    assert_eq!((registers.v[0x2], registers.v[0x3]), (0x02, 0x02));
+
+    // RET retourne à l'adresse écrite par le programme.
+    assert_eq!((registers.v[0xB], registers.v[0xC]), (0x00, 0x01));
+    assert_eq!(registers.pc, 0x218);
+}
+
+#[test]
+fn display_is_read_back_from_ram() {
+    let rom = [
+        0x60, 0xC3, // 0200 LD V0, C3
+        0xAF, 0x08, // 0202 LD I, F08
+        0xF0, 0x55, // 0204 LD [I], V0
+        0x12, 0x06, // 0206 JP 0206
+    ];
+
+    let pixels = RefCell::new(Vec::new());
+
+    let mut chip = vip(&rom);
+    chip.borrow_mut_callbacks().set_set_pixel_callback(|_, x, y| pixels.borrow_mut().push((x, y)));
+    chip.step(4).unwrap();
+
+    // L'octet 0xF08 contient les pixels 0 à 7 de la deuxième ligne.
+    let lit = [(0, 1), (1, 1), (6, 1), (7, 1)];
+    let screen = chip.borrow_screen();
+
+    for x in 0..8 {
+        assert_eq!(screen.get(x, 1), lit.contains(&(x, 1)) as u8);
+    }
+    assert_eq!(screen.get(0, 0), 0);
+
+    drop(chip);
+    assert_eq!(pixels.into_inner(), lit.map(|(x, y)| (x as u8, y as u8)));
+}
+
+#[test]
+fn program_overlapping_the_vip_area_is_loaded() {
+    // Le programme s'étend jusqu'aux registres, qui prennent la valeur de ses derniers octets.
+    let mut rom = vec![0x12, 0x00]; // 0200 JP 0200
+    rom.resize(0xEF0 - 0x200, 0x0);
+    rom.extend(0x10..0x20);
+
+    let mut chip = vip(&rom);
+    chip.step(1).unwrap();
+
+    assert_eq!(chip.borrow_registers().v.to_vec(), (0x10..0x20).collect::<Vec<u8>>());
+}
@@ -0,0 +1,37 @@
+use chip_8_interpreter::{chip::Chip8Builder, platform::Platform};
+
+/// Dessine deux fois un pixel de couleur 1 au même endroit après avoir choisi la couleur de
+/// collision avec 09NN, et s'arrête avec 00FD dès que VF vaut 1.
+fn collides(collision_color: Option<u8>) -> bool {
+    let mut rom = vec![
+        0x00, 0x11, // MEGAON
+        0x01, 0x00, 0x03, 0x00, // LDHI I, $000300
+        0x03, 0x01, // SPRW 01
+        0x04, 0x01, // SPRH 01
+        0x60, 0x00, // LD V0, 00
+    ];
+    if let Some(color) = collision_color {
+        rom.extend([0x09, color]);
+    }
+    for _ in 0..2 {
+        rom.extend([0xD0, 0x01, 0x3F, 0x00, 0x00, 0xFD]);
+    }
+
+    let end = 0x200 + rom.len() as u16;
+    rom.extend((0x1000 | end).to_be_bytes());
+
+    rom.resize(0x100, 0);
+    rom.push(0x01);
+
+    let mut chip = Chip8Builder::new().platform(Platform::MegaChip).build(&rom).unwrap();
+    chip.step(20).unwrap();
+
+    chip.is_halted()
+}
+
+#[test]
+fn collisions_need_a_collision_color() {
+    assert!(!collides(None));
+    assert!(collides(Some(0x01)));
+    assert!(!collides(Some(0x02)));
+}
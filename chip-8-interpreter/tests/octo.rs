@@ -0,0 +1,169 @@
+use chip_8_interpreter::{chip::Chip8, disasm::disassemble, error::Chip8Error, octo::compile, platform::Platform};
+
+#[test]
+fn main_at_start_has_no_jump() {
+    let source = "
+        : main
+          v0 := 5
+          loop
+            v0 -= 1
+            if v0 == 0 then return
+          again
+    ";
+
+    let program = compile(source, Platform::Chip8, 0x200).unwrap();
+    assert_eq!(program, [0x60, 0x05, 0x70, 0xFF, 0x40, 0x00, 0x00, 0xEE, 0x12, 0x02]);
+}
+
+#[test]
+fn forward_labels_and_data() {
+    let source = "
+        : draw
+          i := box
+          sprite v0 v1 4
+        ;
+        : main
+          draw
+          jump main
+        : box
+          0xF0 0x90 0x90 0xF0
+    ";
+
+    let program = compile(source, Platform::Chip8, 0x200).unwrap();
+    assert_eq!(
+        program,
+        [0x12, 0x08, 0xA2, 0x0C, 0xD0, 0x14, 0x00, 0xEE, 0x22, 0x02, 0x12, 0x08, 0xF0, 0x90, 0x90, 0xF0]
+    );
+}
+
+#[test]
+fn directives_and_control_flow() {
+    let source = "
+        :alias x v3
+        :const SPEED 2
+        # Évalué de droite à gauche : SPEED * ( 2 + 1 ).
+        :calc DOUBLE { SPEED * 2 + 1 }
+        :macro add-twice reg n { reg += n reg += n }
+
+        : main
+          add-twice x SPEED
+          if x != DOUBLE begin
+            x := 1
+          else
+            x := 2
+          end
+          loop
+            x += 1
+            while x < 10
+          again
+        : data
+          :byte { DOUBLE + 1 }
+        :next patched
+          v4 := 0
+          :unpack 0xA data
+          i := patched
+          :org 0x230
+          0b10000001
+    ";
+
+    let mut expected = vec![
+        0x73, 0x02, 0x73, 0x02, 0x43, 0x06, 0x12, 0x0C, 0x63, 0x01, 0x12, 0x0E, 0x63, 0x02, 0x73, 0x01,
+        0x6F, 0x0A, 0x8F, 0x37, 0x4F, 0x01, 0x12, 0x1A, 0x12, 0x0E, 0x07, 0x64, 0x00, 0x60, 0xA2, 0x61,
+        0x1A, 0xA2, 0x1C,
+    ];
+    expected.resize(0x30, 0x00);
+    expected.push(0x81);
+
+    assert_eq!(compile(source, Platform::Chip8, 0x200).unwrap(), expected);
+}
+
+#[test]
+fn comparisons_run_on_the_interpreter() {
+    for (operator, expected) in [("<", [true, false, false]), (">", [false, false, true]), ("<=", [true, true, false]), (">=", [false, true, true])] {
+        for (value, expected) in [3, 5, 7].into_iter().zip(expected) {
+            let source = format!(": main v0 := {value} v1 := 0 if v0 {operator} 5 then v1 := 1 loop again");
+            let program = compile(&source, Platform::Chip8, 0x200).unwrap();
+
+            let mut chip = Chip8::from_rom(&program).unwrap();
+            chip.step(20).unwrap();
+
+            assert_eq!(chip.borrow_registers().v[1] == 1, expected, "{source}");
+        }
+    }
+}
+
+#[test]
+fn platform_instructions() {
+    let source = ": main hires scroll-down 4 i := bighex v2 saveflags v7 exit";
+    let program = compile(source, Platform::SuperChip, 0x200).unwrap();
+    assert_eq!(program, [0x00, 0xFF, 0x00, 0xC4, 0xF2, 0x30, 0xF7, 0x75, 0x00, 0xFD]);
+
+    let source = ": main i := long data plane 3 audio save v1 - v3 : data";
+    let program = compile(source, Platform::XoChip, 0x200).unwrap();
+    assert_eq!(program, [0xF0, 0x00, 0x02, 0x0A, 0xF3, 0x01, 0xF0, 0x02, 0x51, 0x32]);
+
+    assert!(compile(": main hires", Platform::Chip8, 0x200).is_err());
+    assert!(compile(": main plane 1", Platform::SuperChip, 0x200).is_err());
+}
+
+#[test]
+fn errors_give_line_and_column() {
+    let cases = [
+        (": main\n  jump nowhere", 2, 8, "Undefined name 'nowhere'."),
+        (": main v0 := 300", 1, 14, "Value '300' does not fit in a byte."),
+        (": main\n  loop v0 += 1", 2, 3, "This 'loop' does not have a matching 'again'."),
+        (": main\n: main", 2, 3, "The name 'main' has already been defined."),
+        (": start ;", 1, 1, "This program is missing a 'main' label."),
+    ];
+
+    for (source, expected_line, expected_column, expected_message) in cases {
+        match compile(source, Platform::Chip8, 0x200) {
+            Err(Chip8Error::Assembly { line, column, message }) => {
+                assert_eq!((line, column, &message[..]), (expected_line, expected_column, expected_message), "{source}")
+            }
+            other => panic!("{source}: {other:?}"),
+        }
+    }
+}
+
+/// Désassemble `rom` en Octo puis compile le code source, qui doit redonner exactement les mêmes
+/// octets.
+fn round_trip(rom: &[u8], platform: Platform) {
+    let source = disassemble(rom, platform, 0x200).unwrap().to_octo();
+    let program = compile(&source, platform, 0x200).unwrap_or_else(|err| panic!("{err}\n{source}"));
+
+    assert_eq!(program, rom, "\n{source}");
+}
+
+#[test]
+fn disassembly_round_trip() {
+    round_trip(
+        &[
+            0x00, 0xE0, 0xA2, 0x2A, 0x60, 0x0C, 0x61, 0x08, 0xD0, 0x15, 0x22, 0x18, 0x3A, 0xFF, 0x12, 0x0C,
+            0x4B, 0x01, 0x5A, 0xB0, 0x9A, 0xB0, 0x8A, 0xB6, 0x81, 0x06, 0x8A, 0xBE, 0xF0, 0x0A, 0xE1, 0x9E,
+            0xE1, 0xA1, 0xF2, 0x33, 0xF2, 0x65, 0x00, 0xEE, 0xB2, 0x00, 0xF0, 0x90, 0x90, 0x90, 0xF0,
+        ],
+        Platform::Chip8,
+    );
+
+    round_trip(&[0x00, 0xFF, 0x00, 0xC4, 0x00, 0xFB, 0xF1, 0x30, 0xF7, 0x75, 0xF7, 0x85, 0xD0, 0x10, 0x00, 0xFD], Platform::SuperChip);
+
+    round_trip(
+        &[0xF0, 0x00, 0x02, 0x10, 0x51, 0x32, 0x51, 0x33, 0xF3, 0x01, 0xF0, 0x02, 0xF1, 0x3A, 0x00, 0xD2, 0x12, 0x0E, 0xAA, 0x55],
+        Platform::XoChip,
+    );
+
+    // Les instructions du MEGA-CHIP et du CHIP-8X sont écrites octet par octet.
+    round_trip(&[0x00, 0x11, 0x01, 0x01, 0x02, 0x00, 0x02, 0x04, 0x03, 0x10, 0x06, 0x00, 0x08, 0x05, 0x00, 0xB2, 0x12, 0x10], Platform::MegaChip);
+    round_trip(&[0x02, 0xA0, 0xB1, 0x20, 0xB1, 0x23, 0x51, 0x21, 0xE0, 0xF2, 0x12, 0x00], Platform::Chip8X);
+}
+
+#[test]
+fn disassembly_uses_octo_syntax() {
+    let rom = [0xA2, 0x08, 0xD0, 0x15, 0x70, 0x01, 0x12, 0x00, 0xF0, 0x90];
+    let source = disassemble(&rom, Platform::Chip8, 0x200).unwrap().to_octo();
+
+    for statement in [": main", ": start", "i := data_0208", "sprite v0 v1 5", "v0 += 0x01", "jump start", "0xF0 0x90"] {
+        assert!(source.contains(statement), "{statement}\n{source}");
+    }
+}
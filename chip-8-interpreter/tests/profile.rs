@@ -0,0 +1,52 @@
+use chip_8_interpreter::{
+    chip::Chip8Builder,
+    font::DREAM_6800_HEX_SPRITES,
+    profile::{Profile, ETI_660_LOAD_ADDRESS, ORDERED_KEYPAD, VIP_KEYPAD},
+};
+
+#[test]
+fn eti_660_loads_at_0x600() {
+    let profile = Profile::from_name("eti660").unwrap();
+    assert_eq!(profile.load_address, ETI_660_LOAD_ADDRESS);
+
+    let chip = Chip8Builder::new().profile(&profile).build(&[0x12, 0x34]).unwrap();
+    assert_eq!(chip.borrow_registers().pc, 0x600);
+    assert_eq!(chip.fetch_next_instruction().unwrap(), 0x1234);
+
+    assert!(Profile::from_name("eti-660").is_none());
+}
+
+#[test]
+fn dream_6800_font_and_keypad() {
+    let profile = Profile::from_name("dream6800").unwrap();
+    assert_eq!(profile.keypad, ORDERED_KEYPAD);
+    assert_eq!(Profile::cosmac_vip().keypad, VIP_KEYPAD);
+
+    let rom = [
+        0x60, 0x02, // 0200 LD V0, 02
+        0xF0, 0x29, // 0202 LD F, V0
+        0x61, 0x00, // 0204 LD V1, 00
+        0xD1, 0x15, // 0206 DRW V1, V1, 5
+        0x12, 0x08, // 0208 JP 0208
+    ];
+
+    // Le profil attend l'affichage, DXYN n'est exécuté qu'au début de la frame suivante.
+    let mut chip = Chip8Builder::new().profile(&profile).build(&rom).unwrap();
+    chip.run_frame().unwrap();
+    chip.run_frame().unwrap();
+
+    // Le caractère 2 du DREAM 6800 fait 3 pixels de large.
+    let screen = chip.borrow_screen();
+
+    for (y, row) in DREAM_6800_HEX_SPRITES[2 * 5..3 * 5].iter().enumerate() {
+        let pixels = (0..8).fold(0, |byte, x| (byte << 1) | screen.get(x, y));
+        assert_eq!(pixels, *row);
+    }
+}
+
+#[test]
+fn telmac_1800_is_a_cosmac_vip() {
+    assert_eq!(Profile::telmac_1800(), Profile::cosmac_vip());
+    assert_eq!(Profile::from_name("telmac1800"), Some(Profile::cosmac_vip()));
+    assert_eq!(Profile::from_name("vip"), Some(Profile::cosmac_vip()));
+}
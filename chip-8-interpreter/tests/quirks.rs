@@ -0,0 +1,102 @@
+use std::cell::Cell;
+
+use chip_8_interpreter::{
+    chip::Chip8Builder,
+    platform::Platform,
+    quirks::{DisplayWait, Quirks},
+};
+
+/// Dessine un pixel en haut à gauche de l'écran en basse résolution, le décale de 4 pixels
+/// vers la droite avec 00FB puis boucle.
+const SCROLL_ROM: [u8; 11] = [
+    0xA2, 0x0A, // 0200 LD I, $020A
+    0x60, 0x00, // 0202 LD V0, 00
+    0xD0, 0x01, // 0204 DRW V0, V0, 1
+    0x00, 0xFB, // 0206 SCR
+    0x12, 0x08, // 0208 JP $0208
+    0x80, // 020A image
+];
+
+fn lit_columns(quirks: Quirks) -> Vec<usize> {
+    let mut chip = Chip8Builder::new().platform(Platform::SuperChip).quirks(quirks).build(&SCROLL_ROM).unwrap();
+
+    for _ in 0..3 {
+        chip.run_frame().unwrap();
+    }
+
+    let screen = chip.borrow_screen();
+    (0..screen.width()).filter(|&x| screen.get(x, 0) != 0).collect()
+}
+
+#[test]
+fn superchip_presets_differ_in_low_resolution() {
+    assert_ne!(Quirks::superchip_legacy(), Quirks::superchip_modern());
+
+    // Sur HP-48, le défilement est compté en pixels de la haute résolution.
+    assert_eq!(lit_columns(Quirks::superchip_legacy()), [2]);
+    assert_eq!(lit_columns(Quirks::superchip_modern()), [4]);
+}
+
+#[test]
+fn display_wait_ends_the_frame() {
+    let rom = [
+        0x60, 0x01, // 0200 LD V0, 01
+        0xD0, 0x01, // 0202 DRW V0, V0, 1
+        0x12, 0x04, // 0204 JP $0204
+    ];
+    let quirks = Quirks {
+        display_wait: DisplayWait::Always,
+        ..Quirks::default()
+    };
+
+    let frames = Cell::new(0);
+
+    let mut chip = Chip8Builder::new().quirks(quirks).instructions_per_frame(20).build(&rom).unwrap();
+    chip.borrow_mut_callbacks().set_frame_ready_callback(|_| frames.set(frames.get() + 1));
+
+    // DXYN en cours de frame termine la frame sans être exécuté.
+    chip.run_frame().unwrap();
+    assert_eq!(frames.get(), 1);
+    assert_eq!(chip.borrow_clock().instructions(), 1);
+    assert_eq!(chip.borrow_registers().pc, 0x202);
+    assert!(chip.borrow_screen().pixels().iter().all(|&pixel| pixel == 0));
+
+    // Il est exécuté au début de la frame suivante.
+    chip.run_frame().unwrap();
+    assert_eq!(frames.get(), 2);
+    assert_eq!(chip.borrow_clock().instructions(), 21);
+    assert_eq!(chip.borrow_screen().get(1, 1), 1);
+}
+
+/// Nombre d'instructions exécutées pendant la première frame par un programme SUPER-CHIP qui
+/// dessine un sprite, en haute résolution si `high_resolution` est vrai.
+fn first_frame_instructions(display_wait: DisplayWait, high_resolution: bool) -> u64 {
+    let rom = [
+        0x00, if high_resolution { 0xFF } else { 0xFE }, // 0200 HIGH ou LOW
+        0xD0, 0x01, // 0202 DRW V0, V0, 1
+        0x12, 0x04, // 0204 JP $0204
+    ];
+    let quirks = Quirks {
+        display_wait,
+        ..Quirks::superchip_modern()
+    };
+
+    let mut chip = Chip8Builder::new()
+        .platform(Platform::SuperChip)
+        .quirks(quirks)
+        .instructions_per_frame(20)
+        .build(&rom)
+        .unwrap();
+    chip.run_frame().unwrap();
+
+    chip.borrow_clock().instructions()
+}
+
+#[test]
+fn display_wait_lores_only() {
+    assert_eq!(first_frame_instructions(DisplayWait::LoresOnly, false), 1);
+    assert_eq!(first_frame_instructions(DisplayWait::LoresOnly, true), 20);
+
+    assert_eq!(first_frame_instructions(DisplayWait::Always, true), 1);
+    assert_eq!(first_frame_instructions(DisplayWait::None, false), 20);
+}
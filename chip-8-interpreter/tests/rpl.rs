@@ -0,0 +1,77 @@
+use std::{cell::RefCell, env, fs, process};
+
+use chip_8_interpreter::{
+    chip::Chip8Builder,
+    error::Chip8Error,
+    platform::Platform,
+    rpl::{load_flags, rom_hash, save_flags, RPL_FLAGS_COUNT},
+};
+
+#[test]
+fn flags_survive_a_restart() {
+    let rom = [
+        0x60, 0x07, // 0200 LD V0, 07
+        0x61, 0x03, // 0202 LD V1, 03
+        0x62, 0x2A, // 0204 LD V2, 2A
+        0xF2, 0x75, // 0206 LD R, V2
+        0xF2, 0x85, // 0208 LD V2, R
+    ];
+    let dir = env::temp_dir().join(format!("chip8-rpl-{}", process::id())).join("flags");
+
+    let saved = RefCell::new(Vec::new());
+
+    let mut chip = Chip8Builder::new().platform(Platform::SuperChip).build(&rom).unwrap();
+    chip.borrow_mut_callbacks().set_rpl_flags_saved_callback(|_, flags| *saved.borrow_mut() = flags.to_vec());
+    chip.step(4).unwrap();
+
+    assert_eq!(chip.rom_hash(), rom_hash(&rom));
+    assert_eq!(saved.borrow()[..4], [0x07, 0x03, 0x2A, 0x00]);
+    assert_eq!(chip.borrow_rpl_flags()[..], saved.borrow()[..]);
+
+    save_flags(&dir, chip.rom_hash(), &saved.borrow()).unwrap();
+
+    // Un autre programme n'a pas de drapeaux sauvegardés.
+    assert!(load_flags(&dir, rom_hash(&rom[..2])).unwrap().is_none());
+
+    // Au redémarrage, FX85 relit les drapeaux sauvegardés.
+    let flags = load_flags(&dir, rom_hash(&rom)).unwrap().unwrap();
+
+    let mut chip = Chip8Builder::new().platform(Platform::SuperChip).build(&rom).unwrap();
+    chip.set_rpl_flags(&flags);
+    chip.step(5).unwrap();
+    assert_eq!(chip.borrow_registers().v[..3], [0x07, 0x03, 0x2A]);
+
+    // Un fichier tronqué laisse les drapeaux manquants à 0.
+    save_flags(&dir, 0x1, &[0xFF; 2]).unwrap();
+    let mut expected = [0x0; RPL_FLAGS_COUNT];
+    expected[..2].fill(0xFF);
+    assert_eq!(load_flags(&dir, 0x1).unwrap(), Some(expected));
+
+    fs::remove_dir_all(dir.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn flags_count_depends_on_the_platform() {
+    let counts = [
+        (Platform::Chip8, 0),
+        (Platform::SuperChip, 8),
+        (Platform::MegaChip, 8),
+        (Platform::XoChip, 16),
+    ];
+
+    for (platform, count) in counts {
+        assert_eq!(platform.rpl_flags_count(), count);
+
+        for x in 0..0x10 {
+            for opcode in [0xF075 | x << 8, 0xF085 | x << 8] {
+                let mut chip = Chip8Builder::new().platform(platform).build(&u16::to_be_bytes(opcode)).unwrap();
+
+                if (x as usize) < count {
+                    chip.step(1).unwrap();
+                } else {
+                    assert!(matches!(chip.step(1), Err(Chip8Error::UnknownInstruction(instruction)) if instruction == opcode));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use chip_8_interpreter::{
+    chip::Chip8Builder,
+    sound::{write_wav, SoundEvent, SoundEventKind, SoundSettings},
+    timing::Timing,
+};
+
+/// LD V0, 03 ; LD ST, V0 ; JP $0204
+const BEEP_ROM: [u8; 6] = [0x60, 0x03, 0xF0, 0x18, 0x12, 0x04];
+
+/// Nombre d'échantillons générés à chaque frame, pour `frames` frames.
+fn samples_per_frame(sample_rate: u32, frames: usize) -> Vec<usize> {
+    let settings = SoundSettings {
+        sample_rate,
+        ..SoundSettings::default()
+    };
+    let mut chip = Chip8Builder::new().sound(settings).build(&BEEP_ROM).unwrap();
+
+    (0..frames)
+        .map(|_| {
+            chip.run_frame().unwrap();
+            chip.borrow_mut_sound().unwrap().take_samples().len()
+        })
+        .collect()
+}
+
+#[test]
+fn beep_events() {
+    let mut chip = Chip8Builder::new().sound(SoundSettings::default()).build(&BEEP_ROM).unwrap();
+
+    for _ in 0..10 {
+        chip.run_frame().unwrap();
+    }
+
+    // Le Sound Timer vaut 3 à la fin de la frame 0, puis 2, 1 et 0 à la fin de la frame 3.
+    let sound = chip.borrow_mut_sound().unwrap();
+    assert_eq!(
+        sound.take_events(),
+        [
+            SoundEvent { frame: 0, kind: SoundEventKind::BeepStart },
+            SoundEvent { frame: 3, kind: SoundEventKind::BeepStop },
+        ]
+    );
+    assert!(!sound.is_beeping());
+
+    let samples = sound.take_samples();
+    assert!(samples[..735 * 3].iter().any(|&sample| sample != 0));
+    assert!(samples[735 * 3..].iter().all(|&sample| sample == 0));
+}
+
+#[test]
+fn events_of_an_instruction_spanning_several_frames() {
+    let rom = [
+        0x00, 0xE0, // 0200 CLS
+        0x60, 0x01, // 0202 LD V0, 01
+        0xF0, 0x18, // 0204 LD ST, V0
+        0x00, 0xE0, // 0206 CLS
+        0x12, 0x08, // 0208 JP $0208
+    ];
+    let mut chip = Chip8Builder::new().timing(Timing::CosmacVip).sound(SoundSettings::default()).build(&rom).unwrap();
+
+    // Avec le timing du COSMAC VIP, le second 00E0 termine les frames 1 et 2 : le son commence à
+    // la fin de la première et s'arrête à la fin de la seconde.
+    chip.step(4).unwrap();
+    assert_eq!(chip.borrow_clock().frames(), 3);
+    assert_eq!(
+        chip.borrow_mut_sound().unwrap().take_events(),
+        [
+            SoundEvent { frame: 1, kind: SoundEventKind::BeepStart },
+            SoundEvent { frame: 2, kind: SoundEventKind::BeepStop },
+        ]
+    );
+}
+
+#[test]
+fn samples_per_frame_follow_the_sample_rate() {
+    assert_eq!(samples_per_frame(44100, 4), [735; 4]);
+
+    // 8000 / 60 = 133,33 : la fraction restante donne un échantillon de plus toutes les 3 frames.
+    assert_eq!(samples_per_frame(8000, 6), [133, 133, 134, 133, 133, 134]);
+    assert_eq!(samples_per_frame(8000, 60).iter().sum::<usize>(), 8000);
+}
+
+#[test]
+fn wav_header() {
+    let mut wav = Vec::new();
+    write_wav(&mut wav, 44100, &[0x0102, -2]).unwrap();
+
+    let expected = [
+        b'R', b'I', b'F', b'F', 40, 0, 0, 0, b'W', b'A', b'V', b'E',
+        // Format : PCM, 1 canal, 44100 Hz, 88200 octets par seconde, 2 octets par échantillon, 16 bits.
+        b'f', b'm', b't', b' ', 16, 0, 0, 0, 1, 0, 1, 0, 0x44, 0xAC, 0, 0, 0x88, 0x58, 0x01, 0, 2, 0, 16, 0,
+        b'd', b'a', b't', b'a', 4, 0, 0, 0,
+        0x02, 0x01, 0xFE, 0xFF,
+    ];
+    assert_eq!(wav, expected);
+}
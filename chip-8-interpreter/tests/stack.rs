@@ -0,0 +1,69 @@
+use chip_8_interpreter::{
+    chip::{Chip8, Chip8Builder, StackFrame},
+    error::Chip8Error,
+};
+
+#[test]
+fn underflow_halts() {
+    let rom = [
+        0x00, 0xEE, // 0200 RET
+    ];
+
+    let mut chip = Chip8::from_rom(&rom).unwrap();
+    assert!(!chip.is_halted());
+    assert!(matches!(chip.step(1), Err(Chip8Error::StackUnderflow)));
+    assert!(chip.is_halted());
+
+    // La machine arrêtée n'exécute plus rien.
+    chip.step(1).unwrap();
+    chip.run_frame().unwrap();
+    assert_eq!(chip.borrow_registers().pc, 0x200);
+}
+
+#[test]
+fn overflow_halts() {
+    let rom = [
+        0x22, 0x00, // 0200 CALL 0200
+    ];
+
+    let mut chip = Chip8Builder::new().stack_depth(2).build(&rom).unwrap();
+    chip.step(2).unwrap();
+    assert!(!chip.is_halted());
+    assert!(matches!(chip.step(1), Err(Chip8Error::StackOverflow)));
+    assert!(chip.is_halted());
+
+    // La pile reste dans l'état où elle était avant le débordement.
+    assert_eq!(chip.call_stack().unwrap().len(), 2);
+}
+
+#[test]
+fn call_stack() {
+    let rom = [
+        0x22, 0x06, // 0200 CALL 0206
+        0x12, 0x02, // 0202 JP 0202
+        0x00, 0x00, // 0204
+        0x22, 0x0A, // 0206 CALL 020A
+        0x00, 0xEE, // 0208 RET
+        0x12, 0x0A, // 020A JP 020A
+    ];
+
+    let mut chip = Chip8::from_rom(&rom).unwrap();
+    assert!(chip.call_stack().unwrap().is_empty());
+
+    chip.step(3).unwrap();
+    assert_eq!(
+        chip.call_stack().unwrap(),
+        [
+            StackFrame {
+                call_site: 0x200,
+                return_address: 0x202,
+                target: 0x206,
+            },
+            StackFrame {
+                call_site: 0x206,
+                return_address: 0x208,
+                target: 0x20A,
+            },
+        ]
+    );
+}
@@ -0,0 +1,130 @@
+use std::cell::RefCell;
+
+use chip_8_interpreter::{
+    chip::{Chip8, Chip8Builder, BIG_HEX_SPRITES},
+    platform::Platform,
+};
+
+fn superchip<'a>(rom: &[u8]) -> Chip8<'a> {
+    Chip8Builder::new().platform(Platform::SuperChip).build(rom).unwrap()
+}
+
+/// Coordonnées des pixels allumés, ligne par ligne.
+fn lit_pixels(chip: &Chip8) -> Vec<(usize, usize)> {
+    let screen = chip.borrow_screen();
+
+    (0..screen.height())
+        .flat_map(|y| (0..screen.width()).map(move |x| (x, y)))
+        .filter(|&(x, y)| screen.get(x, y) != 0)
+        .collect()
+}
+
+#[test]
+fn resolution_switches() {
+    let rom = [
+        0xD0, 0x05, // 0200 DRW V0, V0, 5
+        0x00, 0xFF, // 0202 HIGH
+        0xD0, 0x05, // 0204 DRW V0, V0, 5
+        0x00, 0xFE, // 0206 LOW
+    ];
+
+    let sizes = RefCell::new(Vec::new());
+
+    let mut chip = superchip(&rom);
+    chip.borrow_mut_callbacks().set_resolution_changed_callback(|_, width, height| sizes.borrow_mut().push((width, height)));
+
+    chip.step(1).unwrap();
+    assert!(!chip.borrow_screen().is_high_resolution());
+    assert!(!lit_pixels(&chip).is_empty());
+
+    // Chaque changement de résolution efface l'écran.
+    chip.step(1).unwrap();
+    assert!(chip.borrow_screen().is_high_resolution());
+    assert_eq!((chip.borrow_screen().width(), chip.borrow_screen().height()), (128, 64));
+    assert!(lit_pixels(&chip).is_empty());
+
+    chip.step(2).unwrap();
+    assert!(!chip.borrow_screen().is_high_resolution());
+    assert_eq!((chip.borrow_screen().width(), chip.borrow_screen().height()), (64, 32));
+    assert!(lit_pixels(&chip).is_empty());
+
+    drop(chip);
+    assert_eq!(sizes.into_inner(), [(128, 64), (64, 32)]);
+}
+
+#[test]
+fn scrolling() {
+    let rom = [
+        0x00, 0xFF, // 0200 HIGH
+        0xA2, 0x16, // 0202 LD I, 0216
+        0x60, 0x0A, // 0204 LD V0, 0A
+        0x61, 0x05, // 0206 LD V1, 05
+        0xD0, 0x11, // 0208 DRW V0, V1, 1
+        0x00, 0xC3, // 020A SCD 3
+        0x00, 0xFB, // 020C SCR
+        0x00, 0xFC, // 020E SCL
+        0x00, 0xFC, // 0210 SCL
+        0x00, 0xFC, // 0212 SCL
+        0x00, 0xFC, // 0214 SCL
+        0x80, //       0216
+    ];
+
+    let mut chip = superchip(&rom);
+    chip.step(5).unwrap();
+    assert_eq!(lit_pixels(&chip), [(10, 5)]);
+
+    // Le pixel qui sort de l'écran disparaît.
+    for expected in [vec![(10, 8)], vec![(14, 8)], vec![(10, 8)], vec![(6, 8)], vec![(2, 8)], vec![]] {
+        chip.step(1).unwrap();
+        assert_eq!(lit_pixels(&chip), expected);
+    }
+}
+
+#[test]
+fn large_sprite() {
+    let mut rom = vec![
+        0x00, 0xFF, // 0200 HIGH
+        0xA2, 0x0C, // 0202 LD I, 020C
+        0x60, 0x04, // 0204 LD V0, 04
+        0xD0, 0x00, // 0206 DRW V0, V0, 0
+        0xD0, 0x00, // 0208 DRW V0, V0, 0
+        0x12, 0x0A, // 020A JP 020A
+    ];
+    // 16 lignes de 16 pixels dont seuls le premier et le dernier sont allumés.
+    for _ in 0..16 {
+        rom.extend([0x80, 0x01]);
+    }
+
+    let mut chip = superchip(&rom);
+    chip.step(4).unwrap();
+
+    let expected: Vec<_> = (4..20).flat_map(|y| [(4, y), (19, y)]).collect();
+    assert_eq!(lit_pixels(&chip), expected);
+    assert_eq!(chip.borrow_registers().v[0xF], 0);
+
+    chip.step(1).unwrap();
+    assert!(lit_pixels(&chip).is_empty());
+    assert_eq!(chip.borrow_registers().v[0xF], 1);
+}
+
+#[test]
+fn big_font() {
+    let rom = [
+        0x00, 0xFF, // 0200 HIGH
+        0x60, 0x09, // 0202 LD V0, 09
+        0xF0, 0x30, // 0204 LD HF, V0
+        0x61, 0x00, // 0206 LD V1, 00
+        0xD1, 0x1A, // 0208 DRW V1, V1, 10
+    ];
+
+    let mut chip = superchip(&rom);
+    chip.step(5).unwrap();
+
+    // Le chiffre 9 de 8x10 pixels.
+    let screen = chip.borrow_screen();
+
+    for (y, row) in BIG_HEX_SPRITES[90..100].iter().enumerate() {
+        let pixels = (0..8).fold(0, |byte, x| (byte << 1) | screen.get(x, y));
+        assert_eq!(pixels, *row);
+    }
+}
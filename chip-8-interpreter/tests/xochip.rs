@@ -0,0 +1,85 @@
+use chip_8_interpreter::{
+    chip::{Chip8, Chip8Builder},
+    platform::Platform,
+};
+
+fn xo_chip<'a>(rom: &[u8]) -> Chip8<'a> {
+    Chip8Builder::new().platform(Platform::XoChip).build(rom).unwrap()
+}
+
+/// Indices de couleur des 8 premiers pixels de la première ligne.
+fn first_row(chip: &Chip8) -> Vec<u8> {
+    (0..8).map(|x| chip.borrow_screen().get(x, 0)).collect()
+}
+
+#[test]
+fn planes_hold_the_color_bits() {
+    let mut rom = vec![
+        0xF3, 0x01, // 0200 PLANE 3
+        0xA2, 0x20, // 0202 LD I, 0220
+        0xD0, 0x01, // 0204 DRW V0, V0, 1
+        0xF1, 0x01, // 0206 PLANE 1
+        0x00, 0xE0, // 0208 CLS
+        0xF2, 0x01, // 020A PLANE 2
+        0xA2, 0x22, // 020C LD I, 0222
+        0xD0, 0x01, // 020E DRW V0, V0, 1
+        0xF0, 0x01, // 0210 PLANE 0
+        0xD0, 0x01, // 0212 DRW V0, V0, 1
+    ];
+    rom.resize(0x20, 0x0);
+    rom.extend([
+        0xF0, 0x3C, // 0220 plans 1 et 2
+        0x81, //       0222 plan 2
+    ]);
+
+    let mut chip = xo_chip(&rom);
+    assert_eq!(chip.borrow_screen().selected_planes(), 1);
+
+    // Les données du plan 2 suivent celles du plan 1, chaque pixel prenant la couleur 0 à 3.
+    chip.step(3).unwrap();
+    assert_eq!(chip.borrow_screen().selected_planes(), 3);
+    assert_eq!(first_row(&chip), [1, 1, 3, 3, 2, 2, 0, 0]);
+
+    // CLS n'efface que les plans sélectionnés.
+    chip.step(2).unwrap();
+    assert_eq!(first_row(&chip), [0, 0, 2, 2, 2, 2, 0, 0]);
+
+    // Seul, le plan 2 utilise les premières données pointées par I.
+    chip.step(3).unwrap();
+    assert_eq!(first_row(&chip), [2, 0, 2, 2, 2, 2, 0, 2]);
+
+    // Sans plan sélectionné, rien n'est dessiné.
+    chip.step(2).unwrap();
+    assert_eq!(chip.borrow_screen().selected_planes(), 0);
+    assert_eq!(first_row(&chip), [2, 0, 2, 2, 2, 2, 0, 2]);
+}
+
+#[test]
+fn collisions_span_the_planes() {
+    let mut rom = vec![
+        0xA2, 0x20, // 0200 LD I, 0220
+        0xD0, 0x01, // 0202 DRW V0, V0, 1
+        0xF2, 0x01, // 0204 PLANE 2
+        0xD0, 0x01, // 0206 DRW V0, V0, 1
+        0xF3, 0x01, // 0208 PLANE 3
+        0xD0, 0x01, // 020A DRW V0, V0, 1
+        0xA2, 0x22, // 020C LD I, 0222
+        0xD0, 0x01, // 020E DRW V0, V0, 1
+    ];
+    rom.resize(0x20, 0x0);
+    rom.extend([
+        0x80, 0x00, // 0220 plan 1 seulement
+        0x00, 0x80, // 0222 plan 2 seulement
+    ]);
+
+    let mut chip = xo_chip(&rom);
+    chip.step(1).unwrap();
+
+    // Couleur du pixel (0, 0) et VF après chaque DXYN.
+    let expected = [(1, 0), (3, 0), (2, 1), (0, 1)];
+
+    for (step, (color, vf)) in [1, 2, 2, 2].into_iter().zip(expected) {
+        chip.step(step).unwrap();
+        assert_eq!((chip.borrow_screen().get(0, 0), chip.borrow_registers().v[0xF]), (color, vf));
+    }
+}
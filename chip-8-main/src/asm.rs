@@ -0,0 +1,58 @@
+use std::{fs, path::Path};
+
+use chip_8_interpreter::{asm, octo};
+
+use crate::Target;
+
+/// Assemble un fichier source et écrit le programme obtenu. Les fichiers `.8o` sont compilés
+/// comme des programmes Octo.
+/// Sans l'option `--output`, il est écrit à côté du fichier source avec l'extension `.ch8`.
+/// `args` contient les arguments qui suivent la commande `asm`.
+pub fn run(mut args: impl Iterator<Item = String>) -> Result<(), String> {
+    let mut source_path = None;
+    let mut output_path = None;
+    let mut target = Target::default();
+
+    while let Some(arg) = args.next() {
+        match &arg[..] {
+            "--output" | "-o" => {
+                if let Some(path) = args.next() {
+                    output_path = Some(path);
+                } else {
+                    return Err("no file specified after --output argument".to_string());
+                }
+            }
+            _ => {
+                if !target.parse_arg(&arg, &mut args)? {
+                    source_path = Some(arg);
+                }
+            }
+        }
+    }
+
+    let source_path = match source_path {
+        Some(t) => t,
+        None => return Err("no source file specified after asm command".to_string()),
+    };
+
+    let output_path = output_path.unwrap_or_else(|| {
+        Path::new(&source_path)
+            .with_extension("ch8")
+            .to_string_lossy()
+            .into_owned()
+    });
+
+    let source = fs::read_to_string(&source_path).map_err(|err| format!("reading \"{source_path}\": {err}"))?;
+    let program = if source_path.ends_with(".8o") {
+        octo::compile(&source, target.platform(), target.load_address())
+    } else {
+        asm::assemble(&source, target.platform(), target.load_address())
+    }
+    .map_err(|err| format!("{source_path}: {err}"))?;
+
+    fs::write(&output_path, &program).map_err(|err| format!("writing \"{output_path}\": {err}"))?;
+
+    println!("{} bytes written to \"{output_path}\"", program.len());
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use chip_8_interpreter::chip::Chip8;
 use graph_punk::{
@@ -6,7 +6,12 @@ use graph_punk::{
     window::user_input::{KeyStatus, Keys},
 };
 
-use crate::Config;
+use crate::{print_call_stack, Config};
+
+/// Touches du clavier AZERTY correspondant aux 4 lignes de 4 touches du clavier hexadécimal.
+const KEYBOARD: [&str; 16] = [
+    "1", "2", "3", "4", "a", "z", "e", "r", "q", "s", "d", "f", "w", "x", "c", "v",
+];
 
 fn check_key_state<'a>(keys: &Keys, key: &str, mut c: impl FnMut(KeyStatus, KeyStatus) + 'a) {
     if let Some((pressed, last_state)) = keys.get_key_status(key) {
@@ -23,55 +28,13 @@ pub fn update_callback(keys: &Keys, user_data: &mut UserData) {
         }
     };
 
-    // Vérifie si l'utilisateur appuie sur l'une des touches du CHIP-8.
-    check_key_state(keys, "1", |pressed, _| {
-        chip8.set_key_pressed(0x1, matches!(pressed, KeyStatus::Pressed))
-    });
-    check_key_state(keys, "2", |pressed, _| {
-        chip8.set_key_pressed(0x2, matches!(pressed, KeyStatus::Pressed))
-    });
-    check_key_state(keys, "3", |pressed, _| {
-        chip8.set_key_pressed(0x3, matches!(pressed, KeyStatus::Pressed))
-    });
-    check_key_state(keys, "4", |pressed, _| {
-        chip8.set_key_pressed(0xC, matches!(pressed, KeyStatus::Pressed))
-    });
-    check_key_state(keys, "a", |pressed, _| {
-        chip8.set_key_pressed(0x4, matches!(pressed, KeyStatus::Pressed))
-    });
-    check_key_state(keys, "z", |pressed, _| {
-        chip8.set_key_pressed(0x5, matches!(pressed, KeyStatus::Pressed))
-    });
-    check_key_state(keys, "e", |pressed, _| {
-        chip8.set_key_pressed(0x6, matches!(pressed, KeyStatus::Pressed))
-    });
-    check_key_state(keys, "r", |pressed, _| {
-        chip8.set_key_pressed(0xD, matches!(pressed, KeyStatus::Pressed))
-    });
-    check_key_state(keys, "q", |pressed, _| {
-        chip8.set_key_pressed(0x7, matches!(pressed, KeyStatus::Pressed))
-    });
-    check_key_state(keys, "s", |pressed, _| {
-        chip8.set_key_pressed(0x8, matches!(pressed, KeyStatus::Pressed))
-    });
-    check_key_state(keys, "d", |pressed, _| {
-        chip8.set_key_pressed(0x9, matches!(pressed, KeyStatus::Pressed))
-    });
-    check_key_state(keys, "f", |pressed, _| {
-        chip8.set_key_pressed(0xE, matches!(pressed, KeyStatus::Pressed))
-    });
-    check_key_state(keys, "w", |pressed, _| {
-        chip8.set_key_pressed(0xA, matches!(pressed, KeyStatus::Pressed))
-    });
-    check_key_state(keys, "x", |pressed, _| {
-        chip8.set_key_pressed(0x0, matches!(pressed, KeyStatus::Pressed))
-    });
-    check_key_state(keys, "c", |pressed, _| {
-        chip8.set_key_pressed(0xB, matches!(pressed, KeyStatus::Pressed))
-    });
-    check_key_state(keys, "v", |pressed, _| {
-        chip8.set_key_pressed(0xF, matches!(pressed, KeyStatus::Pressed))
-    });
+    // Vérifie si l'utilisateur appuie sur l'une des touches du CHIP-8, placées sur le clavier
+    // comme sur celui de la machine émulée.
+    for (key, value) in KEYBOARD.iter().zip(config.keypad) {
+        check_key_state(keys, key, |pressed, _| {
+            let _ = chip8.set_key_pressed(value, matches!(pressed, KeyStatus::Pressed));
+        });
+    }
 
     // Vérifie si l'utilisateur switch entre le mode "instruction par instruction" et "instructions
     // automatiques".
@@ -152,13 +115,30 @@ pub fn update_callback(keys: &Keys, user_data: &mut UserData) {
             }
         });
     } else {
-        let period = 1.0_f64 / 500.0_f64;
-        let nanos = period * 1_000_000_000.0_f64;
+        let result = if config.auto_next_instruction {
+            let period = 1.0_f64 / 60.0_f64;
+            let nanos = period * 1_000_000_000.0_f64;
 
-        if chip8.get_elapsed_time_since_last_instruction() >= Duration::from_nanos(nanos as u64) {
+            // L'interpréteur avance d'une frame émulée à chaque frame réelle de 60Hz.
+            if config.frame_instant.elapsed() < Duration::from_nanos(nanos as u64) {
+                return ();
+            }
+
+            config.frame_instant = Instant::now();
+
+            chip8.run_frame()
+        } else {
             // Exécute l'instruction.
-            chip8.execute_instruction();
-            chip8.set_need_to_fetch(true);
+            chip8.step(1)
+        };
+
+        if let Err(err) = result {
+            eprintln!("[CHIP-8 error] Execute instruction: {err}");
+            print_call_stack(chip8);
+
+            // La machine est arrêtée pour que l'utilisateur puisse inspecter les registres.
+            config.auto_next_instruction = false;
+            chip8.set_pause(true);
         }
     }
 }
@@ -0,0 +1,41 @@
+use std::fs;
+
+use chip_8_interpreter::cfg;
+
+use crate::Target;
+
+/// Analyse le flot de contrôle d'un programme sans l'exécuter et affiche le graphe au format DOT
+/// de Graphviz, ou seulement le graphe des appels avec l'option `--calls`.
+/// `args` contient les arguments qui suivent la commande `cfg`.
+pub fn run(mut args: impl Iterator<Item = String>) -> Result<(), String> {
+    let mut rom_path = None;
+    let mut calls = false;
+    let mut target = Target::default();
+
+    while let Some(arg) = args.next() {
+        match &arg[..] {
+            "--calls" => calls = true,
+            _ => {
+                if !target.parse_arg(&arg, &mut args)? {
+                    rom_path = Some(arg);
+                }
+            }
+        }
+    }
+
+    let rom_path = match rom_path {
+        Some(t) => t,
+        None => return Err("no program specified after cfg command".to_string()),
+    };
+
+    let rom = fs::read(&rom_path).map_err(|err| format!("reading \"{rom_path}\": {err}"))?;
+    let graph = cfg::analyze(&rom, target.platform(), target.load_address()).map_err(|err| err.to_string())?;
+
+    if calls {
+        print!("{}", graph.call_graph_to_dot());
+    } else {
+        print!("{}", graph.to_dot());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use std::fs;
+
+use chip_8_interpreter::disasm;
+
+use crate::Target;
+
+/// Désassemble un programme sans l'exécuter et affiche le listing sous forme de texte, de JSON,
+/// de code source pour l'assembleur ou de code source Octo.
+/// `args` contient les arguments qui suivent la commande `disasm`.
+pub fn run(mut args: impl Iterator<Item = String>) -> Result<(), String> {
+    let mut rom_path = None;
+    let mut json = false;
+    let mut source = false;
+    let mut octo = false;
+    let mut target = Target::default();
+
+    while let Some(arg) = args.next() {
+        match &arg[..] {
+            "--json" => json = true,
+            "--source" => source = true,
+            "--octo" => octo = true,
+            _ => {
+                if !target.parse_arg(&arg, &mut args)? {
+                    rom_path = Some(arg);
+                }
+            }
+        }
+    }
+
+    let rom_path = match rom_path {
+        Some(t) => t,
+        None => return Err("no program specified after disasm command".to_string()),
+    };
+
+    let rom = fs::read(&rom_path).map_err(|err| format!("reading \"{rom_path}\": {err}"))?;
+    let listing = disasm::disassemble(&rom, target.platform(), target.load_address()).map_err(|err| err.to_string())?;
+
+    if json {
+        print!("{}", listing.to_json());
+    } else if source {
+        print!("{}", listing.to_source());
+    } else if octo {
+        print!("{}", listing.to_octo());
+    } else {
+        print!("{listing}");
+    }
+
+    Ok(())
+}
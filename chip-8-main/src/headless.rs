@@ -0,0 +1,104 @@
+use std::{
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use chip_8_interpreter::{
+    screen::Screen,
+    sound::{self, SoundSettings},
+};
+
+use crate::{load_program, print_call_stack, Config};
+
+/// Couleurs RGB des indices de couleur des pixels, en dehors du mode MEGA-CHIP.
+const PIXEL_COLORS: [[u8; 3]; 4] = [
+    [0x00, 0x00, 0x00],
+    [0xFF, 0xFF, 0xFF],
+    [0xAA, 0xAA, 0xAA],
+    [0x55, 0x55, 0x55],
+];
+
+/// Contenu de l'écran en RGB ligne par ligne.
+fn screen_rgb(screen: &Screen) -> Vec<u8> {
+    if screen.mega().is_enabled() {
+        return screen
+            .mega()
+            .frame()
+            .chunks_exact(4)
+            .flat_map(|pixel| [pixel[0], pixel[1], pixel[2]])
+            .collect();
+    }
+
+    screen
+        .pixels()
+        .iter()
+        .flat_map(|&color| PIXEL_COLORS[color as usize & 0x3])
+        .collect()
+}
+
+/// Écrit l'écran au format PPM dans le dossier `dir`, une image par frame.
+fn dump_frame(dir: &Path, frame: u64, screen: &Screen) -> Result<(), String> {
+    let mut writer = BufWriter::new(
+        File::create(dir.join(format!("frame_{frame:06}.ppm"))).map_err(|err| err.to_string())?,
+    );
+
+    write!(writer, "P6\n{} {}\n255\n", screen.width(), screen.height()).map_err(|err| err.to_string())?;
+    writer.write_all(&screen_rgb(screen)).map_err(|err| err.to_string())?;
+    writer.flush().map_err(|err| err.to_string())?;
+
+    Ok(())
+}
+
+/// Exécute le programme sans fenêtre, aussi vite que possible, puis écrit le son généré dans le
+/// fichier WAV demandé. Chaque frame peut aussi être enregistrée en image.
+pub fn run(config: &Config) -> Result<(), String> {
+    let frames = config.headless_frames.unwrap_or(0);
+    let settings = SoundSettings::default();
+
+    let sound = config.wav_path.as_ref().map(|_| settings);
+    let mut chip8 = load_program(config, sound)?;
+
+    if let Some(dir) = &config.frames_dir {
+        fs::create_dir_all(dir).map_err(|err| err.to_string())?;
+    }
+
+    println!("Running {frames} frames without window...");
+
+    for frame in 0..frames {
+        if chip8.is_halted() {
+            break;
+        }
+
+        if let Err(err) = chip8.run_frame() {
+            print_call_stack(&chip8);
+
+            return Err(err.to_string());
+        }
+
+        if let Some(dir) = &config.frames_dir {
+            dump_frame(Path::new(dir), frame, chip8.borrow_screen())?;
+        }
+    }
+
+    if let Some(path) = &config.wav_path {
+        let (samples, events) = match chip8.borrow_mut_sound() {
+            Some(sound) => (sound.take_samples(), sound.take_events()),
+            None => (Vec::new(), Vec::new()),
+        };
+
+        let mut writer = BufWriter::new(File::create(path).map_err(|err| err.to_string())?);
+        sound::write_wav(&mut writer, settings.sample_rate, &samples).map_err(|err| err.to_string())?;
+        writer.flush().map_err(|err| err.to_string())?;
+
+        println!(
+            "Wrote {} samples ({} beep events) to \"{path}\".",
+            samples.len(),
+            events.len()
+        );
+    }
+
+    println!("Good-bye!");
+
+    Ok(())
+}
@@ -1,17 +1,93 @@
+mod asm;
 mod callback;
+mod cfg;
+mod disasm;
+mod headless;
 
-use std::{cell::RefCell, env, rc::Rc};
+use std::{cell::RefCell, env, fs, path::Path, rc::Rc, time::Instant};
 
 use callback::update_callback;
-use chip_8_interpreter::chip::{CallbackData, Chip8};
+use chip_8_interpreter::{
+    chip::{CallbackData, Chip8, Chip8Builder, MAX_STACK_DEPTH},
+    font::{self, Font, FONT_GLYPH_HEIGHT},
+    layout::MemoryLayout,
+    octo,
+    platform::Platform,
+    profile::{Profile, VIP_KEYPAD},
+    quirks::Quirks,
+    rpl,
+    sound::SoundSettings,
+};
 use graph_punk::{maths::vec::Vec2, message::MessageCaller, types::UserData, GraphPunk};
 
+/// Dossier dans lequel sont sauvegardés les drapeaux RPL de chaque programme.
+const RPL_FLAGS_DIR: &str = "Builtin/Flags";
+
 pub struct Config {
     pub auto_next_instruction: bool,
     pub program_name: String,
+    pub frame_instant: Instant,
+    pub quirks: Quirks,
+    pub platform: Platform,
+    /// Adresse à laquelle le programme est chargé, celle du profil ou de la plateforme par défaut.
+    pub load_address: u16,
+    /// Machine émulée, dont les paramètres sont précisés par `platform` et `quirks`.
+    pub profile: Option<Profile>,
+    /// Valeur de chaque touche du clavier hexadécimal, ligne par ligne.
+    pub keypad: [u8; 16],
+    /// Police choisie en ligne de commande, à la place de celle du profil.
+    pub font: Option<Vec<u8>>,
+    pub font_address: Option<u16>,
+    /// Emplacement de la pile, des registres et de l'écran.
+    pub memory_layout: MemoryLayout,
+    /// Profondeur de la pile choisie en ligne de commande, à la place de celle du profil.
+    pub stack_depth: Option<usize>,
+    /// Nombre de frames à exécuter sans ouvrir de fenêtre.
+    pub headless_frames: Option<u64>,
+    /// Fichier WAV dans lequel écrire le son d'une exécution sans fenêtre.
+    pub wav_path: Option<String>,
+    /// Dossier dans lequel enregistrer chaque frame d'une exécution sans fenêtre.
+    pub frames_dir: Option<String>,
+}
+
+/// Données transmises aux callbacks de l'interpréteur.
+/// La grille de la fenêtre a la plus grande résolution de la plateforme, les pixels des
+/// résolutions inférieures y sont donc agrandis.
+struct Display {
+    message_caller: Rc<RefCell<MessageCaller>>,
+    grid_width: usize,
+    scale: usize,
+}
+
+impl Display {
+    fn add_pixel_message(&self, message: &str, x: u8, y: u8) {
+        let mut borrowed_message_caller = self.message_caller.borrow_mut();
+
+        for dy in 0..self.scale {
+            for dx in 0..self.scale {
+                let _ = borrowed_message_caller.add_message(
+                    message,
+                    UserData::new(Box::new((
+                        x as usize * self.scale + dx,
+                        y as usize * self.scale + dy,
+                    ))),
+                );
+            }
+        }
+    }
 }
 
 fn main() -> Result<(), String> {
+    // Les commandes asm, cfg et disasm n'affichent que leur résultat, pour pouvoir le rediriger
+    // dans un fichier.
+    let mut args = env::args().skip(1);
+    match args.next().as_deref() {
+        Some("asm") => return asm::run(args),
+        Some("cfg") => return cfg::run(args),
+        Some("disasm") => return disasm::run(args),
+        _ => (),
+    }
+
     println!("=====[ CHIP-8 emulator ]=====");
 
     let config = match process_args(env::args()) {
@@ -21,14 +97,29 @@ fn main() -> Result<(), String> {
 
     println!("Loading program \"{}\"...", config.program_name);
 
+    if config.headless_frames.is_some() {
+        return headless::run(&config);
+    }
+
     let mut graph_punk = GraphPunk::new();
 
     graph_punk.create_window("chip8_window", "CHIP-8 emulator", 700, 400)?;
     graph_punk.init_basic_resources()?;
 
-    let mut chip8 = Chip8::build(&format!("Builtin/Programs/{}", config.program_name))?;
+    let mut chip8 = load_program(&config, None)?;
+    let rom_hash = chip8.rom_hash();
+
+    // La plateforme a pu être précisée d'après l'en-tête du programme.
+    let (grid_width, grid_height) = chip8.borrow_platform().max_screen_size();
+    let scale = grid_width / chip8.borrow_screen().width();
 
-    graph_punk.window_set_display_size("chip8_window", Vec2 { x: 64, y: 32 })?;
+    graph_punk.window_set_display_size(
+        "chip8_window",
+        Vec2 {
+            x: grid_width as u32,
+            y: grid_height as u32,
+        },
+    )?;
 
     let callbacks = chip8.borrow_mut_callbacks();
 
@@ -51,35 +142,60 @@ fn main() -> Result<(), String> {
 
     let message_caller = Rc::new(RefCell::new(message_caller));
 
-    callbacks.set_callback_data(CallbackData::new(Box::new(Rc::clone(&message_caller))));
+    callbacks.set_callback_data(CallbackData::new(Box::new(Display {
+        message_caller: Rc::clone(&message_caller),
+        grid_width,
+        scale,
+    })));
 
     callbacks.set_clear_pixel_callback(|callback_data| {
-        if let Some(rc_message_caller) = callback_data.get::<Rc<RefCell<MessageCaller>>>() {
-            let mut borrowed_message_caller = rc_message_caller.borrow_mut();
+        if let Some(display) = callback_data.get::<Display>() {
+            let mut borrowed_message_caller = display.message_caller.borrow_mut();
 
             let _ = borrowed_message_caller.add_message("clear_pixel", UserData::default());
         }
     });
 
     callbacks.set_set_pixel_callback(|callback_data, x, y| {
-        if let Some(rc_message_caller) = callback_data.get::<Rc<RefCell<MessageCaller>>>() {
-            let mut borrowed_message_caller = rc_message_caller.borrow_mut();
-
-            let _ = borrowed_message_caller.add_message(
-                "set_pixel",
-                UserData::new(Box::new((x as usize, y as usize))),
-            );
+        if let Some(display) = callback_data.get::<Display>() {
+            display.add_pixel_message("set_pixel", x, y);
         }
     });
 
     callbacks.set_unset_pixel_callback(|callback_data, x, y| {
-        if let Some(rc_message_caller) = callback_data.get::<Rc<RefCell<MessageCaller>>>() {
-            let mut borrowed_message_caller = rc_message_caller.borrow_mut();
+        if let Some(display) = callback_data.get::<Display>() {
+            display.add_pixel_message("unset_pixel", x, y);
+        }
+    });
+
+    callbacks.set_rpl_flags_saved_callback(move |_, flags| {
+        if let Err(err) = rpl::save_flags(Path::new(RPL_FLAGS_DIR), rom_hash, flags) {
+            eprintln!("[CHIP-8 error] Saving RPL flags: {err}");
+        }
+    });
+
+    // En mode MEGA-CHIP, la fenêtre n'affiche que les pixels qui ne sont pas noirs.
+    callbacks.set_mega_frame_callback(|callback_data, frame, width, _| {
+        if let Some(display) = callback_data.get::<Display>() {
+            let _ = display
+                .message_caller
+                .borrow_mut()
+                .add_message("clear_pixel", UserData::default());
+
+            for (index, pixel) in frame.chunks_exact(4).enumerate() {
+                if pixel[..3] != [0, 0, 0] {
+                    let x = index % width as usize;
+                    let y = index / width as usize;
 
-            let _ = borrowed_message_caller.add_message(
-                "unset_pixel",
-                UserData::new(Box::new((x as usize, y as usize))),
-            );
+                    display.add_pixel_message("set_pixel", x as u8, y as u8);
+                }
+            }
+        }
+    });
+
+    callbacks.set_resolution_changed_callback(|callback_data, width, _| {
+        if let Some(display) = callback_data.get_mut::<Display>() {
+            display.scale = display.grid_width / width as usize;
         }
     });
 
@@ -97,11 +213,155 @@ fn main() -> Result<(), String> {
     Ok(())
 }
 
+/// Charge le programme demandé puis les drapeaux RPL sauvegardés lors d'une session précédente.
+/// Le son n'est généré que si `sound` est renseigné.
+fn load_program<'a>(config: &Config, sound: Option<SoundSettings>) -> Result<Chip8<'a>, String> {
+    let path = format!("Builtin/Programs/{}", config.program_name);
+
+    let rom = if config.program_name.ends_with(".8o") {
+        let source = fs::read_to_string(&path).map_err(|err| err.to_string())?;
+
+        octo::compile(&source, config.platform, config.load_address).map_err(|err| format!("{}: {err}", config.program_name))?
+    } else {
+        fs::read(path).map_err(|err| err.to_string())?
+    };
+
+    let mut builder = Chip8Builder::new();
+
+    if let Some(profile) = &config.profile {
+        builder = builder.profile(profile);
+    }
+
+    builder = builder
+        .platform(config.platform)
+        .load_address(config.load_address)
+        .quirks(config.quirks)
+        .memory_layout(config.memory_layout);
+
+    if let Some(font) = &config.font {
+        builder = builder.font(font);
+    }
+
+    if let Some(address) = config.font_address {
+        builder = builder.font_address(address);
+    }
+
+    if let Some(depth) = config.stack_depth {
+        builder = builder.stack_depth(depth);
+    }
+
+    if let Some(settings) = sound {
+        builder = builder.sound(settings);
+    }
+
+    let mut chip8 = builder.build(&rom).map_err(|err| err.to_string())?;
+
+    match rpl::load_flags(Path::new(RPL_FLAGS_DIR), chip8.rom_hash()) {
+        Ok(Some(flags)) => chip8.set_rpl_flags(&flags),
+        Ok(None) => (),
+        Err(err) => eprintln!("[CHIP-8 error] Loading RPL flags: {err}"),
+    }
+
+    Ok(chip8)
+}
+
+/// Affiche les appels de fonctions en cours après une erreur, du plus récent au plus ancien.
+pub fn print_call_stack(chip8: &Chip8) {
+    match chip8.call_stack() {
+        Ok(frames) => {
+            for frame in frames.iter().rev() {
+                eprintln!("    in {:04X}, called from {:04X}", frame.target, frame.call_site);
+            }
+        }
+        Err(err) => eprintln!("[CHIP-8 error] Reading call stack: {err}"),
+    }
+}
+
+/// Lit une adresse écrite en hexadécimal avec le préfixe `0x`, ou en décimal.
+fn parse_address(value: &str) -> Option<u16> {
+    match value.strip_prefix("0x") {
+        Some(hex) => u16::from_str_radix(hex, 16).ok(),
+        None => value.parse().ok(),
+    }
+}
+
+/// Machine pour laquelle les commandes asm, cfg et disasm traduisent un programme.
+#[derive(Default)]
+struct Target {
+    platform: Option<Platform>,
+    profile: Option<Profile>,
+    load_address: Option<u16>,
+}
+
+impl Target {
+    /// Lit l'option `arg` et sa valeur si elle précise la machine.
+    /// Retourne `false` si l'option ne concerne pas la machine.
+    fn parse_arg(&mut self, arg: &str, args: &mut impl Iterator<Item = String>) -> Result<bool, String> {
+        match arg {
+            "--platform" | "-P" => {
+                if let Some(name) = args.next() {
+                    self.platform = match Platform::from_name(&name) {
+                        Some(t) => Some(t),
+                        None => return Err(format!("unknown platform \"{name}\"")),
+                    };
+                } else {
+                    return Err("no platform specified after --platform argument".to_string());
+                }
+            }
+            "--profile" | "-m" => {
+                if let Some(name) = args.next() {
+                    self.profile = match Profile::from_name(&name) {
+                        Some(t) => Some(t),
+                        None => return Err(format!("unknown profile \"{name}\"")),
+                    };
+                } else {
+                    return Err("no profile specified after --profile argument".to_string());
+                }
+            }
+            "--load-address" => {
+                if let Some(address) = args.next() {
+                    self.load_address = match parse_address(&address) {
+                        Some(t) => Some(t),
+                        None => return Err(format!("invalid load address \"{address}\"")),
+                    };
+                } else {
+                    return Err("no address specified after --load-address argument".to_string());
+                }
+            }
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    /// Les options explicites l'emportent sur le profil, qui l'emporte sur les valeurs par défaut.
+    fn platform(&self) -> Platform {
+        self.platform
+            .or(self.profile.as_ref().map(|profile| profile.platform))
+            .unwrap_or_default()
+    }
+
+    fn load_address(&self) -> u16 {
+        self.load_address
+            .or(self.profile.as_ref().map(|profile| profile.load_address))
+            .unwrap_or(self.platform().load_address())
+    }
+}
+
 fn process_args(mut args: impl Iterator<Item = String>) -> Result<Config, String> {
     // Saute le 1er argument car c'est normalement le nom du programme.
     args.next();
 
     let mut program_name = String::new();
+    let mut quirks = None;
+    let mut target = Target::default();
+    let mut headless_frames = None;
+    let mut wav_path = None;
+    let mut frames_dir = None;
+    let mut font = None;
+    let mut font_address = None;
+    let mut memory_layout = MemoryLayout::Separate;
+    let mut stack_depth = None;
 
     // Boucle tant qu'il y a reste des arguments.
     while let Some(arg) = args.next() {
@@ -114,14 +374,107 @@ fn process_args(mut args: impl Iterator<Item = String>) -> Result<Config, String
                 if let Some(name) = args.next() {
                     program_name = name;
 
-                    if let Some(index) = program_name.chars().rev().position(|c| c == '.') {
-                        program_name = program_name[..program_name.len() - index - 1].to_string();
+                    // Les programmes Octo sont compilés au chargement et gardent leur extension.
+                    if !program_name.ends_with(".8o") {
+                        if let Some(index) = program_name.chars().rev().position(|c| c == '.') {
+                            program_name = program_name[..program_name.len() - index - 1].to_string();
+                        }
                     }
                 } else {
                     return Err("no program name specified after --program argument".to_string());
                 }
             }
-            _ => (),
+            // Lit le préréglage des instructions ambiguës à utiliser.
+            "--quirks" | "-q" => {
+                if let Some(name) = args.next() {
+                    quirks = match Quirks::from_name(&name) {
+                        Some(t) => Some(t),
+                        None => return Err(format!("unknown quirks preset \"{name}\"")),
+                    };
+                } else {
+                    return Err("no preset specified after --quirks argument".to_string());
+                }
+            }
+            // Exécute le programme sans fenêtre pendant le nombre de frames indiqué.
+            "--headless" => {
+                if let Some(frames) = args.next() {
+                    headless_frames = match frames.parse::<u64>() {
+                        Ok(t) => Some(t),
+                        Err(_) => return Err(format!("invalid frame count \"{frames}\"")),
+                    };
+                } else {
+                    return Err("no frame count specified after --headless argument".to_string());
+                }
+            }
+            // Lit le nom du fichier WAV dans lequel écrire le son.
+            "--wav" => {
+                if let Some(path) = args.next() {
+                    wav_path = Some(path);
+                } else {
+                    return Err("no file specified after --wav argument".to_string());
+                }
+            }
+            // Lit le dossier dans lequel enregistrer les frames.
+            "--dump-frames" => {
+                if let Some(dir) = args.next() {
+                    frames_dir = Some(dir);
+                } else {
+                    return Err("no directory specified after --dump-frames argument".to_string());
+                }
+            }
+            // Lit le nom de la police intégrée à utiliser.
+            "--font" => {
+                if let Some(name) = args.next() {
+                    font = match Font::from_name(&name) {
+                        Some(t) => Some(t.sprites().to_vec()),
+                        None => return Err(format!("unknown font \"{name}\"")),
+                    };
+                } else {
+                    return Err("no font specified after --font argument".to_string());
+                }
+            }
+            // Lit le fichier contenant la police à utiliser.
+            "--font-file" => {
+                if let Some(path) = args.next() {
+                    font = match font::load_font(Path::new(&path), FONT_GLYPH_HEIGHT) {
+                        Ok(t) => Some(t),
+                        Err(err) => return Err(format!("loading font \"{path}\": {err}")),
+                    };
+                } else {
+                    return Err("no file specified after --font-file argument".to_string());
+                }
+            }
+            // Lit l'adresse à laquelle copier la police.
+            "--font-address" => {
+                if let Some(address) = args.next() {
+                    font_address = match parse_address(&address) {
+                        Some(t) => Some(t),
+                        None => return Err(format!("invalid font address \"{address}\"")),
+                    };
+                } else {
+                    return Err("no address specified after --font-address argument".to_string());
+                }
+            }
+            // Place la pile, les registres et l'écran en RAM comme sur le COSMAC VIP.
+            "--vip-layout" => memory_layout = MemoryLayout::CosmacVip,
+            // Lit le nombre d'appels imbriqués que peut contenir la pile.
+            "--stack-depth" => {
+                if let Some(depth) = args.next() {
+                    stack_depth = match &depth[..] {
+                        "unlimited" => Some(MAX_STACK_DEPTH),
+                        _ => match depth.parse::<usize>() {
+                            Ok(t) => Some(t),
+                            Err(_) => return Err(format!("invalid stack depth \"{depth}\"")),
+                        },
+                    };
+                } else {
+                    return Err("no depth specified after --stack-depth argument".to_string());
+                }
+            }
+            // Lit la machine à émuler, son jeu d'instructions et l'adresse de chargement.
+            _ => {
+                target.parse_arg(&arg, &mut args)?;
+            }
         }
     }
 
@@ -129,10 +482,43 @@ fn process_args(mut args: impl Iterator<Item = String>) -> Result<Config, String
         return Err("no program name provided".to_string());
     }
 
-    program_name.push_str(".ch8");
+    // Le son n'est enregistré que lorsque l'exécution n'est pas cadencée par la fenêtre.
+    if wav_path.is_some() && headless_frames.is_none() {
+        return Err("--wav can only be used with --headless".to_string());
+    }
+
+    if frames_dir.is_some() && headless_frames.is_none() {
+        return Err("--dump-frames can only be used with --headless".to_string());
+    }
+
+    if !program_name.ends_with(".8o") {
+        program_name.push_str(".ch8");
+    }
+
+    // Les options explicites l'emportent sur le profil, qui l'emporte sur les valeurs par défaut.
+    let platform = target.platform();
+    let load_address = target.load_address();
+    let profile = target.profile;
+    let quirks = quirks
+        .or(profile.as_ref().map(|profile| profile.quirks))
+        .unwrap_or(platform.default_quirks());
+    let keypad = profile.as_ref().map_or(VIP_KEYPAD, |profile| profile.keypad);
 
     Ok(Config {
         auto_next_instruction: false,
         program_name,
+        frame_instant: Instant::now(),
+        quirks,
+        platform,
+        load_address,
+        profile,
+        keypad,
+        font,
+        font_address,
+        memory_layout,
+        stack_depth,
+        headless_frames,
+        wav_path,
+        frames_dir,
     })
 }